@@ -1,11 +1,23 @@
 use anyhow::Result;
-use sysinfo::System;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
 
-use crate::data::{
-    cpu::CpuData, disk::DiskData, logs::LogsData, memory::MemoryData, network::NetworkData,
-    BatteryData, DockerData, ProcessData, SystemHistory, TemperatureData, BYTES_PER_GB, BYTES_PER_MB,
-    PERCENTAGE_MULTIPLIER,
+use crate::cli::ExportFormat;
+use crate::collector::{CollectorCommand, CollectorHandle, DataCollector, Snapshot};
+use crate::config::Config;
+use crate::data::process::{
+    ProcessColumn, ProcessInfo, ProcessSort, ProcessSortField, ProcessTable, ProcessView,
 };
+use crate::data::process_control::{self, Signal, NICE_MAX, NICE_MIN};
+use crate::data::process_filter::{parse_patterns, ProcessFilter, ProcessPattern};
+use crate::data::process_watch::WatchEventKind;
+use crate::data::{procfs, SysRoot};
+use crate::export;
+use crate::i18n;
+use crate::recording::{Player, Recording, ReplayStatus};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActiveTab {
@@ -58,6 +70,28 @@ impl ActiveTab {
     }
 }
 
+/// CPU 标签页中各核心的显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CpuCoreView {
+    /// 当前使用率列表
+    #[default]
+    Table,
+    /// 每个核心一条迷你曲线
+    Grid,
+    /// 核心 × 时间的热力图
+    Heatmap,
+}
+
+impl CpuCoreView {
+    pub fn next(self) -> Self {
+        match self {
+            CpuCoreView::Table => CpuCoreView::Grid,
+            CpuCoreView::Grid => CpuCoreView::Heatmap,
+            CpuCoreView::Heatmap => CpuCoreView::Table,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LogCategory {
     System,
@@ -132,38 +166,180 @@ impl LogCategory {
     }
 }
 
+/// 提示信息的显示时长
+const NOTICE_DURATION: Duration = Duration::from_secs(5);
+
+/// 显示在界面底部的临时提示，例如导出结果
+#[derive(Debug, Clone)]
+pub struct Notice {
+    pub message: String,
+    pub is_error: bool,
+    shown_at: Instant,
+}
+
+impl Notice {
+    pub fn is_expired(&self) -> bool {
+        self.shown_at.elapsed() >= NOTICE_DURATION
+    }
+}
+
+/// 进程表的滚动位置和选中行
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessCursor {
+    /// 第一行可见进程的下标
+    pub offset: usize,
+    /// 选中进程的下标
+    pub selected: usize,
+    /// 为 `true` 时滚动表格使选中行可见，为 `false` 时（拖动滚动条后）选中行跟随可见区域
+    pub follow_selection: bool,
+}
+
+impl ProcessCursor {
+    /// 按可见行数调整滚动位置和选中行，保证两者都在有效范围内
+    pub fn clamp(&mut self, total: usize, visible_rows: usize) {
+        if total == 0 || visible_rows == 0 {
+            self.offset = 0;
+            self.selected = 0;
+            return;
+        }
+        self.selected = self.selected.min(total - 1);
+        if self.follow_selection {
+            if self.selected < self.offset {
+                self.offset = self.selected;
+            } else if self.selected >= self.offset + visible_rows {
+                self.offset = self.selected + 1 - visible_rows;
+            }
+        }
+        self.offset = self.offset.min(total.saturating_sub(visible_rows));
+        if !self.follow_selection {
+            self.selected = self
+                .selected
+                .clamp(self.offset, (self.offset + visible_rows).min(total) - 1);
+        }
+    }
+}
+
+/// CPU 亲和性对话框中每行显示的 CPU 数
+pub const AFFINITY_GRID_COLUMNS: usize = 8;
+
+/// 对话框操作的目标进程，命令行已脱敏
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessTarget {
+    pub pid: u32,
+    pub name: String,
+    pub command: String,
+}
+
+impl ProcessTarget {
+    fn new(process: &ProcessInfo) -> Self {
+        Self {
+            pid: process.pid,
+            name: process.name.clone(),
+            command: process.display_command(),
+        }
+    }
+}
+
+/// 进程页上弹出的对话框
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessDialog {
+    /// 从 [`Signal::ALL`] 中选择要发送的信号
+    SignalPicker {
+        target: ProcessTarget,
+        selected: usize,
+    },
+    /// 发送前确认
+    Confirm {
+        target: ProcessTarget,
+        signal: Signal,
+    },
+    /// 修改 nice 值
+    Renice { target: ProcessTarget, nice: i32 },
+    /// 勾选允许运行的 CPU
+    Affinity {
+        target: ProcessTarget,
+        cpus: Vec<bool>,
+        selected: usize,
+    },
+    /// 勾选进程表显示的列，顺序同 [`ProcessColumn::ALL`]
+    Columns { visible: Vec<bool>, selected: usize },
+}
+
+/// 进程详情面板的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetailPane {
+    pub pid: u32,
+    /// 是否显示未脱敏的命令行
+    pub reveal_command: bool,
+    /// 环境变量列表的滚动位置
+    pub scroll: u16,
+}
+
+/// 选中行代表的进程或分组，刷新或切换视图后据此重新定位选中行
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selection {
+    Process(u32),
+    Group(String),
+    /// 线程行，线程退出后选中所属进程
+    Thread {
+        tid: u32,
+        owner: u32,
+    },
+}
+
+/// 界面数据的来源
+enum DataSource {
+    /// 后台采集线程实时采集
+    Live(CollectorHandle),
+    /// 回放录制文件
+    Replay(Box<Player>),
+}
+
 /// 应用程序主状态结构体
 ///
-/// 管理所有系统数据、UI状态和用户交互状态
+/// 管理界面和用户交互状态，系统数据由后台采集线程或回放文件以快照形式提供
 pub struct App {
-    pub system: System,
-    pub cpu_data: CpuData,
-    pub memory_data: MemoryData,
-    pub disk_data: DiskData,
-    pub network_data: NetworkData,
-    pub process_data: ProcessData,
-    pub logs_data: LogsData,
-    pub temperature_data: TemperatureData,
-    pub battery_data: BatteryData,
-    pub docker_data: DockerData,
-    pub history: SystemHistory,
+    pub snapshot: Arc<Snapshot>,
+    source: DataSource,
     pub active_tab: ActiveTab,
     pub active_log_category: LogCategory,
+    pub cpu_core_view: CpuCoreView,
     pub logs_scroll_offset: u16,
-    pub process_scroll_offset: usize,
+    pub process_cursor: ProcessCursor,
+    pub process_view: ProcessView,
+    pub process_sort: ProcessSort,
+    /// 进程表显示的列，初始值来自 `process.columns` 配置
+    pub process_columns: Vec<ProcessColumn>,
+    /// 树形视图中折叠了子树的进程
+    pub collapsed_pids: HashSet<u32>,
+    /// 分组视图中展开的分组
+    pub expanded_groups: HashSet<String>,
+    /// 在进程下列出各个线程
+    pub show_threads: bool,
+    pub process_dialog: Option<ProcessDialog>,
+    pub process_detail: Option<DetailPane>,
     pub is_dragging_scrollbar: bool,
     pub search_mode: bool,
     pub search_query: String,
+    /// 搜索框中表达式的解析错误
+    pub search_error: Option<String>,
     pub mouse_x: u16,
     pub mouse_y: u16,
     pub paused: bool,
-    pub has_alert: bool,
+    pub notice: Option<Notice>,
+    /// 置顶规则，修改后写回配置文件
+    pinned_patterns: Vec<ProcessPattern>,
+    /// 监视规则，修改后写回配置文件
+    watched_patterns: Vec<ProcessPattern>,
+    config_path: Option<PathBuf>,
+    /// 已经提示过的最后一条监视事件的序号
+    last_watch_event: u64,
 }
 
 impl App {
     /// 创建新的应用程序实例
     ///
-    /// 初始化系统信息采集器、所有数据模块、历史记录和配置
+    /// 根据配置初始化所有数据模块，并启动后台采集线程
     ///
     /// # Returns
     ///
@@ -172,126 +348,805 @@ impl App {
     /// # Errors
     ///
     /// 如果数据模块初始化失败，返回错误
-    pub fn new() -> Result<Self> {
-        let mut system = System::new_all();
-        system.refresh_all();
-
-        let core_count = system.cpus().len();
-
-        Ok(Self {
-            system,
-            cpu_data: CpuData::new()?,
-            memory_data: MemoryData::new()?,
-            disk_data: DiskData::new()?,
-            network_data: NetworkData::new()?,
-            process_data: ProcessData::new()?,
-            logs_data: LogsData::new(),
-            temperature_data: TemperatureData::new()?,
-            battery_data: BatteryData::new()?,
-            docker_data: DockerData::new()?,
-            history: SystemHistory::new(core_count),
+    pub fn new(config: Config) -> Result<Self> {
+        let collector = CollectorHandle::spawn(DataCollector::new(&config)?);
+        Ok(Self::with_source(
+            collector.latest(),
+            DataSource::Live(collector),
+            &config,
+        ))
+    }
+
+    /// 创建回放录制文件的应用程序实例，不启动采集线程
+    pub fn replay(config: &Config, recording: Recording) -> Self {
+        let player = Player::new(recording, config);
+        Self::with_source(
+            Arc::new(player.snapshot()),
+            DataSource::Replay(Box::new(player)),
+            config,
+        )
+    }
+
+    fn with_source(snapshot: Arc<Snapshot>, source: DataSource, config: &Config) -> Self {
+        Self {
+            snapshot,
+            source,
             active_tab: ActiveTab::Cpu,
             active_log_category: LogCategory::System,
+            cpu_core_view: CpuCoreView::default(),
             logs_scroll_offset: 0,
-            process_scroll_offset: 0,
+            process_cursor: ProcessCursor::default(),
+            process_view: ProcessView::default(),
+            process_sort: ProcessSort::default(),
+            process_columns: config.process.columns.clone(),
+            collapsed_pids: HashSet::new(),
+            expanded_groups: HashSet::new(),
+            show_threads: false,
+            process_dialog: None,
+            process_detail: None,
             is_dragging_scrollbar: false,
             search_mode: false,
             search_query: String::new(),
+            search_error: None,
             mouse_x: 0,
             mouse_y: 0,
             paused: false,
-            has_alert: false,
+            notice: None,
+            pinned_patterns: parse_patterns(&config.process.pinned),
+            watched_patterns: parse_patterns(&config.process.watched),
+            config_path: config.path.clone(),
+            last_watch_event: 0,
+        }
+    }
+
+    /// 获取最新快照：实时模式读取采集线程发布的快照，回放模式推进回放时钟
+    pub fn sync_snapshot(&mut self) {
+        match &mut self.source {
+            DataSource::Live(collector) => {
+                let snapshot = collector.latest();
+                if !Arc::ptr_eq(&snapshot, &self.snapshot) {
+                    self.replace_snapshot(snapshot);
+                }
+            }
+            DataSource::Replay(player) => {
+                let changed = player.tick(Instant::now());
+                // 播放到末尾时会自动暂停
+                self.paused = player.is_paused();
+                if changed {
+                    let snapshot = Arc::new(player.snapshot());
+                    self.replace_snapshot(snapshot);
+                }
+            }
+        }
+    }
+
+    /// 替换快照，进程表的选中行跟随原来选中的进程或分组
+    fn replace_snapshot(&mut self, snapshot: Arc<Snapshot>) {
+        let selection = self.selection();
+        self.snapshot = snapshot;
+        if let Some(selection) = selection {
+            self.restore_selection(&selection);
+        }
+        self.notify_watch_events();
+    }
+
+    /// 提示监视的进程退出或重新启动，同一时刻只显示最新的一条
+    fn notify_watch_events(&mut self) {
+        let message = self
+            .snapshot
+            .process
+            .watch_events
+            .last()
+            .filter(|event| event.id > self.last_watch_event)
+            .map(|event| {
+                let status = match event.kind {
+                    WatchEventKind::Exited { pid } => {
+                        format!("{} (PID {pid})", i18n::t("watch_exited"))
+                    }
+                    WatchEventKind::Restarted { pid } => {
+                        format!("{} (PID {pid})", i18n::t("watch_restarted"))
+                    }
+                };
+                (event.id, format!("{}: {status}", event.name))
+            });
+        if let Some((id, message)) = message {
+            self.last_watch_event = id;
+            self.notify(message, true);
+        }
+    }
+
+    fn selection(&self) -> Option<Selection> {
+        let table = self.process_table();
+        let row = table.rows.get(self.process_cursor.selected)?;
+        let process = self.snapshot.process.row_process(row);
+        Some(match &row.group {
+            Some(key) => Selection::Group(key.clone()),
+            None if row.thread => Selection::Thread {
+                tid: process.pid,
+                owner: process.parent.unwrap_or(process.pid),
+            },
+            None => Selection::Process(process.pid),
         })
     }
 
-    /// 更新所有系统数据
-    ///
-    /// 刷新系统信息并更新所有数据模块，包括 CPU、内存、磁盘、网络、进程和日志数据
-    /// 同时更新历史记录
-    ///
-    /// # Returns
+    /// 选中指定进程或分组所在的行
     ///
-    /// 如果数据更新成功，返回 Ok(())
-    ///
-    /// # Errors
-    ///
-    /// 如果数据更新失败，返回错误
-    pub fn update_data(&mut self) -> Result<()> {
-        if self.paused {
-            return Ok(());
+    /// 进程所在的分组已折叠时选中分组行，都不在表中时不改变选中行
+    fn restore_selection(&mut self, selection: &Selection) {
+        if let Selection::Thread { tid, owner } = *selection {
+            let threads = &self.snapshot.process.threads;
+            let position = self
+                .process_table()
+                .rows
+                .iter()
+                .position(|row| row.thread && threads[row.index].pid == tid);
+            match position {
+                Some(position) => self.process_cursor.selected = position,
+                None => self.restore_selection(&Selection::Process(owner)),
+            }
+            return;
         }
 
-        self.system.refresh_all();
+        let processes = &self.snapshot.process.processes;
+        let group = match selection {
+            Selection::Thread { .. } => None,
+            Selection::Group(key) => Some(key.as_str()),
+            Selection::Process(pid) => processes
+                .iter()
+                .find(|p| p.pid == *pid)
+                .map(|p| p.group_key(self.process_view)),
+        };
+        let rows = self.process_table().rows;
+        let position = rows
+            .iter()
+            .position(|row| match selection {
+                Selection::Process(pid) => {
+                    row.group.is_none() && !row.thread && processes[row.index].pid == *pid
+                }
+                Selection::Group(_) | Selection::Thread { .. } => false,
+            })
+            .or_else(|| {
+                let group = group?;
+                rows.iter()
+                    .position(|row| row.group.as_deref() == Some(group))
+            });
+        if let Some(position) = position {
+            self.process_cursor.selected = position;
+        }
+    }
 
-        self.cpu_data.update()?;
-        self.memory_data.update()?;
-        self.disk_data.update()?;
-        self.network_data.update()?;
-        self.temperature_data.update()?;
-        self.battery_data.update()?;
+    /// 订阅采集线程发布的快照，用于在界面之外导出数据；回放模式下返回 `None`
+    pub fn subscribe(&self) -> Option<watch::Receiver<Arc<Snapshot>>> {
+        match &self.source {
+            DataSource::Live(collector) => Some(collector.subscribe()),
+            DataSource::Replay(_) => None,
+        }
+    }
 
-        if self.active_tab == ActiveTab::Process {
-            if let Err(_e) = self.process_data.update() {}
+    fn send(&self, command: CollectorCommand) {
+        if let DataSource::Live(collector) = &self.source {
+            collector.send(command);
         }
+    }
 
-        #[cfg(target_os = "linux")]
-        if self.active_tab == ActiveTab::Docker {
-            if let Err(_e) = self.docker_data.update() {}
+    /// 请求采集线程立即刷新一次数据
+    pub fn refresh(&self) {
+        self.send(CollectorCommand::RefreshNow);
+    }
+
+    /// 暂停或继续刷新；回放模式下暂停或继续播放
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        match &mut self.source {
+            DataSource::Live(collector) => collector.send(CollectorCommand::SetPaused(paused)),
+            DataSource::Replay(player) => player.set_paused(paused),
         }
+    }
 
-        self.history
-            .cpu
-            .update(self.cpu_data.global_cpu_usage, &self.cpu_data.core_usages);
+    /// 在界面底部显示一条提示
+    pub fn notify(&mut self, message: String, is_error: bool) {
+        self.notice = Some(Notice {
+            message,
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// 将当前的历史曲线导出到工作目录下的文件
+    pub fn export_history(&mut self, format: ExportFormat) {
+        let path = export::default_file_name(format);
+        match export::export_to_file(&self.snapshot.history, format, &path) {
+            Ok(()) => self.notify(
+                format!("{} {}", i18n::t("history_exported"), path.display()),
+                false,
+            ),
+            Err(e) => self.notify(format!("{}: {:#}", i18n::t("export_failed"), e), true),
+        }
+    }
+
+    /// 当前回放状态，实时模式下返回 `None`
+    pub fn replay_status(&self) -> Option<ReplayStatus> {
+        match &self.source {
+            DataSource::Live(_) => None,
+            DataSource::Replay(player) => Some(player.status()),
+        }
+    }
+
+    /// 回放定位，`seconds` 为负时后退
+    pub fn seek_replay(&mut self, seconds: i64) {
+        if let DataSource::Replay(player) = &mut self.source {
+            if player.seek(seconds) {
+                let snapshot = Arc::new(player.snapshot());
+                self.replace_snapshot(snapshot);
+            }
+        }
+    }
+
+    /// 调整回放速度
+    pub fn change_replay_speed(&mut self, faster: bool) {
+        if let DataSource::Replay(player) = &mut self.source {
+            if faster {
+                player.faster();
+            } else {
+                player.slower();
+            }
+        }
+    }
+
+    /// 按当前显示方式排列的进程表
+    pub fn process_table(&self) -> ProcessTable {
+        self.snapshot.process.table(
+            self.process_view,
+            &self.collapsed_pids,
+            &self.expanded_groups,
+        )
+    }
 
-        let memory_percent = if self.memory_data.total_memory > 0 {
-            (self.memory_data.used_memory as f32 / self.memory_data.total_memory as f32)
-                * PERCENTAGE_MULTIPLIER
+    /// 选中的进程，选中分组行时为 `None`，选中线程行时为该线程
+    pub fn selected_process(&self) -> Option<&ProcessInfo> {
+        let table = self.process_table();
+        let row = table.rows.get(self.process_cursor.selected)?;
+        if row.group.is_some() {
+            return None;
+        }
+        Some(self.snapshot.process.row_process(row))
+    }
+
+    /// 选中的进程；选中线程行时为所属进程
+    fn selected_owner(&self) -> Option<&ProcessInfo> {
+        let table = self.process_table();
+        let row = table.rows.get(self.process_cursor.selected)?;
+        if !row.thread {
+            return self.selected_process();
+        }
+        let owner = self.snapshot.process.threads[row.index].parent?;
+        self.snapshot
+            .process
+            .processes
+            .iter()
+            .find(|p| p.pid == owner)
+    }
+
+    /// 选中的分组行的键
+    pub fn selected_group(&self) -> Option<String> {
+        let table = self.process_table();
+        table.rows.get(self.process_cursor.selected)?.group.clone()
+    }
+
+    /// 移动进程表的选中行，`delta` 为负时向上
+    pub fn move_process_selection(&mut self, delta: isize) {
+        let selected = self.process_cursor.selected.saturating_add_signed(delta);
+        self.select_process(selected);
+    }
+
+    /// 选中指定下标的进程，超出范围时选中最后一个
+    pub fn select_process(&mut self, index: usize) {
+        let last = self.process_table().rows.len().saturating_sub(1);
+        self.process_cursor.selected = index.min(last);
+        self.process_cursor.follow_selection = true;
+    }
+
+    /// 切换平铺和树形视图
+    pub fn toggle_process_tree(&mut self) {
+        self.set_process_view(match self.process_view {
+            ProcessView::Tree => ProcessView::Flat,
+            _ => ProcessView::Tree,
+        });
+    }
+
+    /// 依次切换按名称分组、按可执行文件分组和平铺视图
+    pub fn cycle_process_grouping(&mut self) {
+        self.set_process_view(match self.process_view {
+            ProcessView::GroupByName => ProcessView::GroupByExe,
+            ProcessView::GroupByExe => ProcessView::Flat,
+            _ => ProcessView::GroupByName,
+        });
+    }
+
+    /// 在进程下列出或隐藏线程；回放文件中没有线程信息
+    pub fn toggle_threads(&mut self) {
+        if self.replay_status().is_some() {
+            self.notify(i18n::t("not_in_replay").to_string(), true);
+            return;
+        }
+        self.show_threads = !self.show_threads;
+        self.send(CollectorCommand::SetShowThreads(self.show_threads));
+    }
+
+    /// 切换进程表的显示方式，保持选中的进程不变
+    fn set_process_view(&mut self, view: ProcessView) {
+        let selection = self.selection();
+        self.process_view = view;
+        self.expanded_groups.clear();
+        match &mut self.source {
+            DataSource::Live(collector) => {
+                collector.send(CollectorCommand::SetProcessView(self.process_view))
+            }
+            DataSource::Replay(player) => {
+                player.set_view(self.process_view);
+                let snapshot = Arc::new(player.snapshot());
+                self.snapshot = snapshot;
+            }
+        }
+        if let Some(selection) = selection {
+            self.restore_selection(&selection);
+        }
+        self.process_cursor.follow_selection = true;
+    }
+
+    /// 按列排序，再次选择同一列时反转方向；不能排序的列忽略
+    pub fn sort_by_column(&mut self, column: ProcessColumn) {
+        if let Some(field) = column.sort_field() {
+            self.set_process_sort(self.process_sort.toggle(field));
+        }
+    }
+
+    /// 把排序字段移到前一个或后一个可见的可排序列
+    pub fn cycle_sort_column(&mut self, delta: isize) {
+        let mut fields: Vec<ProcessSortField> = Vec::new();
+        for field in self.process_columns.iter().filter_map(|c| c.sort_field()) {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        if fields.is_empty() {
+            return;
+        }
+        let len = fields.len() as isize;
+        let next = match fields.iter().position(|&f| f == self.process_sort.field) {
+            Some(index) => (index as isize + delta).rem_euclid(len),
+            None => 0,
+        };
+        self.set_process_sort(self.process_sort.toggle(fields[next as usize]));
+    }
+
+    /// 反转当前的排序方向
+    pub fn reverse_sort(&mut self) {
+        self.set_process_sort(self.process_sort.toggle(self.process_sort.field));
+    }
+
+    fn set_process_sort(&mut self, sort: ProcessSort) {
+        let selection = self.selection();
+        self.process_sort = sort;
+        match &mut self.source {
+            DataSource::Live(collector) => collector.send(CollectorCommand::SetProcessSort(sort)),
+            DataSource::Replay(player) => {
+                player.set_sort(sort);
+                let snapshot = Arc::new(player.snapshot());
+                self.snapshot = snapshot;
+            }
+        }
+        if let Some(selection) = selection {
+            self.restore_selection(&selection);
+        }
+        self.process_cursor.follow_selection = true;
+    }
+
+    /// 打开列选择器
+    pub fn open_column_chooser(&mut self) {
+        let visible = ProcessColumn::ALL
+            .iter()
+            .map(|column| self.process_columns.contains(column))
+            .collect();
+        self.process_dialog = Some(ProcessDialog::Columns {
+            visible,
+            selected: 0,
+        });
+    }
+
+    /// 折叠选中进程的子树或选中的分组；已折叠或没有子进程时选中其父进程或所在分组
+    pub fn collapse_selected(&mut self) {
+        let table = self.process_table();
+        let Some(row) = table.rows.get(self.process_cursor.selected) else {
+            return;
+        };
+        let process = self.snapshot.process.row_process(row);
+        if row.thread {
+            if let Some(owner) = process.parent {
+                self.restore_selection(&Selection::Process(owner));
+                self.process_cursor.follow_selection = true;
+            }
+        } else if self.process_view.is_grouped() {
+            match &row.group {
+                Some(key) => {
+                    self.expanded_groups.remove(key);
+                }
+                None => {
+                    let key = process.group_key(self.process_view).to_string();
+                    self.restore_selection(&Selection::Group(key));
+                    self.process_cursor.follow_selection = true;
+                }
+            }
+        } else if row.has_children && !row.collapsed {
+            self.collapsed_pids.insert(process.pid);
+        } else if let Some(parent) = process.parent {
+            self.restore_selection(&Selection::Process(parent));
+            self.process_cursor.follow_selection = true;
+        }
+    }
+
+    /// 展开选中进程的子树或选中的分组
+    pub fn expand_selected(&mut self) {
+        if let Some(key) = self.selected_group() {
+            self.expanded_groups.insert(key);
+        } else if let Some(pid) = self.selected_process().map(|p| p.pid) {
+            self.collapsed_pids.remove(&pid);
+        }
+    }
+
+    /// 展开或折叠选中的分组
+    pub fn toggle_selected_group(&mut self) {
+        if let Some(key) = self.selected_group() {
+            if !self.expanded_groups.remove(&key) {
+                self.expanded_groups.insert(key);
+            }
+        }
+    }
+
+    /// 置顶或取消置顶选中的进程
+    ///
+    /// 置顶时添加匹配同名进程的规则，取消时移除所有匹配该进程的规则
+    pub fn toggle_pin_selected(&mut self) {
+        let Some(process) = self.selected_owner() else {
+            return;
+        };
+        let pid = process.pid;
+        if process.pinned {
+            let process = process.clone();
+            self.pinned_patterns
+                .retain(|pattern| !pattern.matches(&process));
         } else {
-            0.0
+            let pattern = ProcessPattern::for_name(&process.name);
+            self.pinned_patterns.push(pattern);
+        }
+        self.save_process_patterns();
+
+        let pins = self.pinned_patterns.clone();
+        match &mut self.source {
+            DataSource::Live(collector) => {
+                collector.send(CollectorCommand::SetPinnedProcesses(pins));
+            }
+            DataSource::Replay(player) => {
+                player.set_pins(pins);
+                let snapshot = Arc::new(player.snapshot());
+                self.replace_snapshot(snapshot);
+            }
+        }
+        self.restore_selection(&Selection::Process(pid));
+        self.process_cursor.follow_selection = true;
+    }
+
+    /// 开始或停止监视选中的进程
+    pub fn toggle_watch_selected(&mut self) {
+        if self.replay_status().is_some() {
+            self.notify(i18n::t("not_in_replay").to_string(), true);
+            return;
+        }
+        let Some(process) = self.selected_owner().cloned() else {
+            return;
         };
-        let used_memory_gb = self.memory_data.used_memory as f32 / BYTES_PER_GB as f32;
-        let swap_percent = if self.memory_data.total_swap > 0 {
-            (self.memory_data.used_swap as f32 / self.memory_data.total_swap as f32)
-                * PERCENTAGE_MULTIPLIER
+        let before = self.watched_patterns.len();
+        self.watched_patterns
+            .retain(|pattern| !pattern.matches(&process));
+        if self.watched_patterns.len() == before {
+            self.watched_patterns
+                .push(ProcessPattern::for_name(&process.name));
+            self.notify(
+                format!("{}: {}", i18n::t("watch_started"), process.name),
+                false,
+            );
         } else {
-            0.0
+            self.notify(
+                format!("{}: {}", i18n::t("watch_stopped"), process.name),
+                false,
+            );
+        }
+        self.save_process_patterns();
+        self.send(CollectorCommand::SetWatchedProcesses(
+            self.watched_patterns.clone(),
+        ));
+    }
+
+    /// 把置顶和监视规则写回配置文件，失败时提示
+    fn save_process_patterns(&mut self) {
+        let Some(path) = &self.config_path else {
+            return;
+        };
+        let sources = |patterns: &[ProcessPattern]| -> Vec<String> {
+            patterns.iter().map(|p| p.source().to_string()).collect()
+        };
+        let result = Config::save_process_patterns(
+            path,
+            &sources(&self.pinned_patterns),
+            &sources(&self.watched_patterns),
+        );
+        if let Err(e) = result {
+            self.notify(e.to_string(), true);
+        }
+    }
+
+    /// 打开选中进程的详情面板，采集线程开始采集该进程的详情和历史
+    pub fn open_process_detail(&mut self) {
+        if self.replay_status().is_some() {
+            self.notify(i18n::t("not_in_replay").to_string(), true);
+            return;
+        }
+        let Some(pid) = self.selected_process().map(|p| p.pid) else {
+            return;
         };
-        self.history
-            .memory
-            .update(memory_percent, used_memory_gb, swap_percent);
+        self.process_detail = Some(DetailPane {
+            pid,
+            reveal_command: false,
+            scroll: 0,
+        });
+        self.send(CollectorCommand::SetDetailPid(Some(pid)));
+    }
 
-        let (total_rx_speed, total_tx_speed): (f32, f32) = self
-            .network_data
-            .interfaces
-            .iter()
-            .fold((0.0, 0.0), |(rx, tx), (_, _, _, r, t)| {
-                (
-                    rx + *r as f32 / BYTES_PER_MB as f32,
-                    tx + *t as f32 / BYTES_PER_MB as f32,
-                )
+    pub fn close_process_detail(&mut self) {
+        if self.process_detail.take().is_some() {
+            self.send(CollectorCommand::SetDetailPid(None));
+        }
+    }
+
+    /// 切换详情面板中命令行的脱敏显示
+    pub fn toggle_reveal_command(&mut self) {
+        if let Some(pane) = &mut self.process_detail {
+            pane.reveal_command = !pane.reveal_command;
+        }
+    }
+
+    /// 滚动详情面板中的环境变量列表，`delta` 为负时向上
+    pub fn scroll_process_detail(&mut self, delta: i16) {
+        if let Some(pane) = &mut self.process_detail {
+            pane.scroll = pane.scroll.saturating_add_signed(delta);
+        }
+    }
+
+    /// 为选中的进程打开信号选择器
+    pub fn open_signal_picker(&mut self) {
+        if let Some(target) = self.dialog_target() {
+            self.process_dialog = Some(ProcessDialog::SignalPicker {
+                target,
+                selected: 0,
             });
-        self.history.network.update(total_rx_speed, total_tx_speed);
+        }
+    }
 
-        if let Err(_e) = self.logs_data.update() {}
+    /// 请求向选中的进程发送信号，确认后才会发送
+    pub fn request_signal(&mut self, signal: Signal) {
+        if let Some(target) = self.dialog_target() {
+            self.process_dialog = Some(ProcessDialog::Confirm { target, signal });
+        }
+    }
 
-        self.has_alert = self.temperature_data.has_warning
-            || self.memory_data.has_warning
-            || self.disk_data.has_warning;
+    /// 以当前 nice 值打开调整对话框
+    ///
+    /// 进程表没有用到 nice 列时不会采集该值，因此直接从 `/proc` 读取
+    pub fn open_renice(&mut self) {
+        if let Some(target) = self.dialog_target() {
+            let nice =
+                procfs::read_stat(&SysRoot::from_env(), target.pid).map_or(0, |stat| stat.nice);
+            self.process_dialog = Some(ProcessDialog::Renice { target, nice });
+        }
+    }
 
-        Ok(())
+    /// 打开 CPU 亲和性对话框，CPU 数量取自 `CpuData::cpu_cores`，当前亲和性从 `/proc` 读取
+    pub fn open_affinity(&mut self) {
+        let cpu_count = self.snapshot.cpu.cpu_cores;
+        if let Some(target) = self.dialog_target() {
+            let affinity = procfs::read_status(&SysRoot::from_env(), target.pid)
+                .and_then(|status| status.cpus_allowed)
+                .unwrap_or_default();
+            // 读取不到亲和性时视为可以在所有 CPU 上运行
+            let cpus = (0..cpu_count)
+                .map(|cpu| affinity.is_empty() || affinity.contains(&cpu))
+                .collect();
+            self.process_dialog = Some(ProcessDialog::Affinity {
+                target,
+                cpus,
+                selected: 0,
+            });
+        }
+    }
+
+    /// 选中的进程；回放中的进程不是本机进程，提示后返回 `None`
+    fn dialog_target(&mut self) -> Option<ProcessTarget> {
+        if self.replay_status().is_some() {
+            self.notify(i18n::t("not_in_replay").to_string(), true);
+            return None;
+        }
+        self.selected_process().map(ProcessTarget::new)
+    }
+
+    /// 对话框中的方向键：信号列表上下移动，nice 值增减，CPU 网格内移动
+    pub fn move_dialog_selection(&mut self, dx: isize, dy: isize) {
+        match &mut self.process_dialog {
+            Some(ProcessDialog::SignalPicker { selected, .. }) => {
+                *selected = selected
+                    .saturating_add_signed(dy)
+                    .min(Signal::ALL.len() - 1);
+            }
+            Some(ProcessDialog::Renice { nice, .. }) => {
+                // 向上、向右增大 nice 值
+                *nice = (*nice + (dx - dy) as i32).clamp(NICE_MIN, NICE_MAX);
+            }
+            Some(ProcessDialog::Affinity { cpus, selected, .. }) => {
+                let delta = dx + dy * AFFINITY_GRID_COLUMNS as isize;
+                if let Some(index) = selected.checked_add_signed(delta) {
+                    if index < cpus.len() {
+                        *selected = index;
+                    }
+                }
+            }
+            Some(ProcessDialog::Columns { visible, selected }) => {
+                *selected = selected.saturating_add_signed(dy).min(visible.len() - 1);
+            }
+            _ => {}
+        }
+    }
+
+    /// 在 CPU 亲和性对话框或列选择器中勾选或取消当前项
+    pub fn toggle_dialog_item(&mut self) {
+        if let Some(
+            ProcessDialog::Affinity {
+                cpus: items,
+                selected,
+                ..
+            }
+            | ProcessDialog::Columns {
+                visible: items,
+                selected,
+            },
+        ) = &mut self.process_dialog
+        {
+            if let Some(checked) = items.get_mut(*selected) {
+                *checked = !*checked;
+            }
+        }
+    }
+
+    /// 在 CPU 亲和性对话框或列选择器中全选，已全选时全部取消
+    pub fn toggle_all_dialog_items(&mut self) {
+        if let Some(
+            ProcessDialog::Affinity { cpus: items, .. }
+            | ProcessDialog::Columns { visible: items, .. },
+        ) = &mut self.process_dialog
+        {
+            let all = items.iter().all(|checked| *checked);
+            items.iter_mut().for_each(|checked| *checked = !all);
+        }
+    }
+
+    /// 选择器中选定信号后进入确认，其余对话框直接执行
+    pub fn accept_dialog(&mut self) {
+        match self.process_dialog.take() {
+            Some(ProcessDialog::SignalPicker { target, selected }) => {
+                self.process_dialog = Some(ProcessDialog::Confirm {
+                    target,
+                    signal: Signal::ALL[selected],
+                });
+            }
+            Some(ProcessDialog::Confirm { target, signal }) => {
+                let result = process_control::send_signal(target.pid, signal);
+                self.report_action(
+                    &target,
+                    signal.name(),
+                    "signal_sent",
+                    "signal_failed",
+                    result,
+                );
+            }
+            Some(ProcessDialog::Renice { target, nice }) => {
+                let result = process_control::set_nice(target.pid, nice);
+                let value = format!("nice {}", nice);
+                self.report_action(&target, &value, "setting_applied", "setting_failed", result);
+            }
+            Some(ProcessDialog::Affinity { target, cpus, .. }) => {
+                let cpus: Vec<usize> = cpus
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(cpu, checked)| checked.then_some(cpu))
+                    .collect();
+                let result = process_control::set_affinity(target.pid, &cpus);
+                let value = format!("{} {}", i18n::t("affinity"), procfs::format_cpu_list(&cpus));
+                self.report_action(&target, &value, "setting_applied", "setting_failed", result);
+            }
+            Some(ProcessDialog::Columns { visible, selected }) => {
+                let columns: Vec<ProcessColumn> = ProcessColumn::ALL
+                    .iter()
+                    .zip(&visible)
+                    .filter_map(|(column, checked)| checked.then_some(*column))
+                    .collect();
+                if columns.is_empty() {
+                    self.notify(i18n::t("select_one_column").to_string(), true);
+                    self.process_dialog = Some(ProcessDialog::Columns { visible, selected });
+                } else {
+                    self.process_columns = columns.clone();
+                    self.send(CollectorCommand::SetProcessColumns(columns));
+                }
+            }
+            None => {}
+        }
+    }
+
+    pub fn cancel_dialog(&mut self) {
+        self.process_dialog = None;
+    }
+
+    /// 在界面底部显示操作结果，成功后立即刷新进程列表
+    fn report_action(
+        &mut self,
+        target: &ProcessTarget,
+        action: &str,
+        success_key: &str,
+        failure_key: &str,
+        result: Result<(), process_control::ProcessControlError>,
+    ) {
+        match result {
+            Ok(()) => {
+                self.notify(
+                    format!(
+                        "{} {} → {} ({})",
+                        i18n::t(success_key),
+                        action,
+                        target.pid,
+                        target.name
+                    ),
+                    false,
+                );
+                self.refresh();
+            }
+            Err(e) => self.notify(format!("{} {}: {}", i18n::t(failure_key), action, e), true),
+        }
+    }
+
+    pub fn toggle_paused(&mut self) {
+        self.set_paused(!self.paused);
+    }
+
+    pub fn toggle_log_level(&mut self) {
+        self.send(CollectorCommand::ToggleLogLevel);
+        self.logs_scroll_offset = 0;
+    }
+
+    pub fn set_active_tab(&mut self, tab: ActiveTab) {
+        self.active_tab = tab;
+        self.send(CollectorCommand::SetActiveTab(tab));
     }
 
     pub fn next_tab(&mut self) {
-        self.active_tab = self.active_tab.next();
+        self.set_active_tab(self.active_tab.next());
     }
 
     pub fn previous_tab(&mut self) {
-        self.active_tab = self.active_tab.previous();
+        self.set_active_tab(self.active_tab.previous());
     }
 
-    /// 清理资源
+    /// 清理资源，停止后台采集线程
     pub fn cleanup(&mut self) -> Result<()> {
-        self.logs_data.cleanup()?;
+        if let DataSource::Live(collector) = &mut self.source {
+            collector.shutdown();
+        }
         Ok(())
     }
 
@@ -299,19 +1154,41 @@ impl App {
     pub fn enter_search_mode(&mut self) {
         self.search_mode = true;
         self.search_query.clear();
+        self.search_error = None;
     }
 
     /// 退出搜索模式
     pub fn exit_search_mode(&mut self) {
         self.search_mode = false;
-        self.process_data.set_filter(String::new());
+        self.search_error = None;
+        self.set_process_filter(ProcessFilter::default());
     }
 
-    /// 应用搜索
+    /// 应用搜索，表达式有错误时留在搜索模式
     pub fn apply_search(&mut self) {
-        if self.search_mode {
-            self.search_mode = false;
-            self.process_data.set_filter(self.search_query.clone());
+        if !self.search_mode {
+            return;
+        }
+        match ProcessFilter::parse(&self.search_query) {
+            Ok(filter) => {
+                self.search_mode = false;
+                self.search_error = None;
+                self.set_process_filter(filter);
+            }
+            Err(e) => self.search_error = Some(e.to_string()),
+        }
+    }
+
+    fn set_process_filter(&mut self, filter: ProcessFilter) {
+        match &mut self.source {
+            DataSource::Live(collector) => {
+                collector.send(CollectorCommand::SetProcessFilter(filter))
+            }
+            DataSource::Replay(player) => {
+                player.set_filter(filter);
+                let snapshot = Arc::new(player.snapshot());
+                self.replace_snapshot(snapshot);
+            }
         }
     }
 
@@ -319,6 +1196,7 @@ impl App {
     pub fn add_to_search_query(&mut self, c: char) {
         if self.search_mode && self.search_query.len() < crate::data::MAX_SEARCH_QUERY_LENGTH {
             self.search_query.push(c);
+            self.check_search_query();
         }
     }
 
@@ -326,6 +1204,93 @@ impl App {
     pub fn remove_from_search_query(&mut self) {
         if self.search_mode && !self.search_query.is_empty() {
             self.search_query.pop();
+            self.check_search_query();
         }
     }
+
+    /// 输入时即时检查表达式，在搜索框中提示错误
+    fn check_search_query(&mut self) {
+        self.search_error = ProcessFilter::parse(&self.search_query)
+            .err()
+            .map(|e| e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_cursor_follows_selection() {
+        let mut cursor = ProcessCursor {
+            offset: 0,
+            selected: 25,
+            follow_selection: true,
+        };
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (16, 25));
+
+        cursor.selected = 3;
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (3, 3));
+
+        // 进程减少后选中行和滚动位置都收回到末尾
+        cursor.selected = 80;
+        cursor.clamp(20, 10);
+        assert_eq!((cursor.offset, cursor.selected), (10, 19));
+
+        cursor.clamp(0, 10);
+        assert_eq!((cursor.offset, cursor.selected), (0, 0));
+    }
+
+    #[test]
+    fn test_process_cursor_stays_in_view_after_scrolling() {
+        let mut cursor = ProcessCursor {
+            offset: 50,
+            selected: 2,
+            follow_selection: false,
+        };
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (50, 50));
+
+        cursor.offset = 0;
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (0, 9));
+    }
+
+    #[test]
+    fn test_process_cursor_bounds() {
+        let mut cursor = ProcessCursor {
+            offset: 0,
+            selected: 9,
+            follow_selection: true,
+        };
+        // 最后一个可见行不需要滚动，再往下一行滚动一行
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (0, 9));
+        cursor.selected = 10;
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (1, 10));
+
+        // 进程比可见行少时不滚动
+        cursor.selected = 99;
+        cursor.clamp(5, 10);
+        assert_eq!((cursor.offset, cursor.selected), (0, 4));
+
+        // 滚动位置超出末尾时收回，选中行留在可见区域的最后一行
+        cursor = ProcessCursor {
+            offset: 95,
+            selected: 0,
+            follow_selection: false,
+        };
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (90, 90));
+        cursor.selected = 99;
+        cursor.clamp(100, 10);
+        assert_eq!((cursor.offset, cursor.selected), (90, 99));
+
+        // 没有可见行时归零
+        cursor.clamp(100, 0);
+        assert_eq!((cursor.offset, cursor.selected), (0, 0));
+    }
 }
@@ -0,0 +1,294 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::app::ActiveTab;
+use crate::config::{Config, MAX_UPDATE_INTERVAL_MS, MIN_UPDATE_INTERVAL_MS};
+use crate::i18n::Language;
+
+/// 命令行参数
+#[derive(Debug, Parser)]
+#[command(name = "tui-dash", version, about)]
+pub struct Cli {
+    /// 配置文件路径（默认：~/.config/tui-dash/config.toml）
+    #[arg(long, value_name = "PATH", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// 数据刷新间隔，例如 500ms、2s（覆盖配置文件）
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, global = true)]
+    pub interval: Option<Duration>,
+
+    /// 启动时显示的标签页
+    #[arg(long, value_enum)]
+    pub tab: Option<TabArg>,
+
+    /// 界面语言（默认根据 LANG/LC_ALL/LC_MESSAGES 检测）
+    #[arg(long, value_enum, global = true)]
+    pub lang: Option<LangArg>,
+
+    /// 不捕获鼠标事件，保留终端原生的选择和复制
+    #[arg(long)]
+    pub no_mouse: bool,
+
+    /// 启动时暂停数据刷新
+    #[arg(long)]
+    pub paused: bool,
+
+    /// 在指定地址提供 Prometheus 指标（`/metrics`），例如 127.0.0.1:9184
+    #[arg(long, value_name = "ADDR")]
+    pub serve: Option<SocketAddr>,
+
+    /// 只提供指标服务，不启动终端界面（需要 --serve）
+    #[arg(long, requires = "serve")]
+    pub headless: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// 用命令行参数覆盖配置文件中的对应项
+    pub fn apply_to(&self, config: &mut Config) -> anyhow::Result<()> {
+        if let Some(interval) = self.interval {
+            let millis = interval.as_millis() as u64;
+            if !(MIN_UPDATE_INTERVAL_MS..=MAX_UPDATE_INTERVAL_MS).contains(&millis) {
+                anyhow::bail!(
+                    "--interval must be between {}ms and {}ms",
+                    MIN_UPDATE_INTERVAL_MS,
+                    MAX_UPDATE_INTERVAL_MS
+                );
+            }
+            config.general.update_interval_ms = millis;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 采集一次系统数据并打印到标准输出
+    Snapshot {
+        /// 输出格式
+        #[arg(long, value_enum, default_value_t = SnapshotFormat::Text)]
+        format: SnapshotFormat,
+    },
+    /// 按刷新间隔持续采集系统数据，以 NDJSON 格式逐帧追加到文件
+    Record {
+        /// 输出文件路径，例如 out.tdrec
+        path: PathBuf,
+        /// 采集次数，不指定时一直运行直到被中断
+        #[arg(long, value_name = "N")]
+        count: Option<u64>,
+    },
+    /// 在界面中回放 `record` 生成的文件
+    Replay {
+        /// 录制文件路径
+        path: PathBuf,
+    },
+    /// 导出 CPU、内存和网络的历史数据
+    Export {
+        /// 从录制文件导出；不指定时实时采集 `--count` 次后导出
+        recording: Option<PathBuf>,
+        /// 导出格式
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// 输出文件路径，不指定时写到标准输出
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// 实时采集的次数
+        #[arg(long, value_name = "N", default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+        count: u64,
+    },
+}
+
+/// `snapshot` 子命令的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SnapshotFormat {
+    /// 便于阅读的文本
+    Text,
+    /// 供脚本和监控工具使用的 JSON
+    Json,
+}
+
+/// 历史数据的导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// 每个时间点一行的 CSV
+    Csv,
+    /// InfluxDB 行协议
+    Influx,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TabArg {
+    Cpu,
+    Memory,
+    Disk,
+    Network,
+    Process,
+    Docker,
+    Logs,
+    Temperature,
+}
+
+impl From<TabArg> for ActiveTab {
+    fn from(tab: TabArg) -> Self {
+        match tab {
+            TabArg::Cpu => ActiveTab::Cpu,
+            TabArg::Memory => ActiveTab::Memory,
+            TabArg::Disk => ActiveTab::Disk,
+            TabArg::Network => ActiveTab::Network,
+            TabArg::Process => ActiveTab::Process,
+            TabArg::Docker => ActiveTab::Docker,
+            TabArg::Logs => ActiveTab::Logs,
+            TabArg::Temperature => ActiveTab::Temperature,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LangArg {
+    En,
+    Zh,
+}
+
+impl From<LangArg> for Language {
+    fn from(lang: LangArg) -> Self {
+        match lang {
+            LangArg::En => Language::English,
+            LangArg::Zh => Language::Chinese,
+        }
+    }
+}
+
+/// 解析时间间隔，支持 `ms`、`s`、`m` 后缀，不带后缀时按毫秒处理
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration `{}`", value))?;
+
+    let millis = match unit.trim() {
+        "" | "ms" => number,
+        "s" => number * 1000.0,
+        "m" => number * 60_000.0,
+        other => {
+            return Err(format!(
+                "unknown duration unit `{}` (expected ms, s or m)",
+                other
+            ))
+        }
+    };
+
+    if !millis.is_finite() || millis < 1.0 {
+        return Err(format!("duration `{}` is too short", value));
+    }
+
+    Ok(Duration::from_millis(millis as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("250"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("1m"), Ok(Duration::from_secs(60)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("5h").is_err());
+        assert!(parse_duration("0ms").is_err());
+    }
+
+    #[test]
+    fn test_cli_flags() {
+        let cli = Cli::try_parse_from([
+            "tui-dash",
+            "--interval",
+            "500ms",
+            "--tab",
+            "process",
+            "--lang",
+            "en",
+            "--no-mouse",
+        ])
+        .unwrap();
+        assert_eq!(cli.interval, Some(Duration::from_millis(500)));
+        assert_eq!(cli.tab, Some(TabArg::Process));
+        assert_eq!(cli.lang, Some(LangArg::En));
+        assert!(cli.no_mouse);
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn test_cli_serve() {
+        let cli =
+            Cli::try_parse_from(["tui-dash", "--serve", "127.0.0.1:9184", "--headless"]).unwrap();
+        assert_eq!(cli.serve, Some("127.0.0.1:9184".parse().unwrap()));
+        assert!(cli.headless);
+
+        assert!(Cli::try_parse_from(["tui-dash", "--headless"]).is_err());
+        assert!(Cli::try_parse_from(["tui-dash", "--serve", "localhost"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_subcommands() {
+        let cli = Cli::try_parse_from(["tui-dash", "snapshot"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Snapshot {
+                format: SnapshotFormat::Text
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["tui-dash", "snapshot", "--format", "json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Snapshot {
+                format: SnapshotFormat::Json
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["tui-dash", "record", "out.log", "--count", "3"]).unwrap();
+        match cli.command {
+            Some(Command::Record { path, count }) => {
+                assert_eq!(path, PathBuf::from("out.log"));
+                assert_eq!(count, Some(3));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["tui-dash", "export", "--format", "influx", "-o", "out.lp"])
+            .unwrap();
+        match cli.command {
+            Some(Command::Export {
+                recording,
+                format,
+                output,
+                count,
+            }) => {
+                assert!(recording.is_none());
+                assert_eq!(format, ExportFormat::Influx);
+                assert_eq!(output, Some(PathBuf::from("out.lp")));
+                assert_eq!(count, 10);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(Cli::try_parse_from(["tui-dash", "export", "--count", "0"]).is_err());
+
+        let cli = Cli::try_parse_from(["tui-dash", "replay", "out.tdrec"]).unwrap();
+        assert!(
+            matches!(cli.command, Some(Command::Replay { path }) if path == Path::new("out.tdrec"))
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use anyhow::Result;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Instant;
+use tokio::sync::watch;
+
+use crate::app::ActiveTab;
+use crate::config::Config;
+use crate::data::{
+    cpu::CpuData,
+    disk::DiskData,
+    load::LoadData,
+    logs::LogsData,
+    memory::MemoryData,
+    network::NetworkData,
+    process::{ProcessColumn, ProcessSort, ProcessView},
+    process_filter::{ProcessFilter, ProcessPattern},
+    BatteryData, CollectorStatus, DockerData, ProcessData, SampleNeeds, Sampler, Scheduled,
+    SystemHistory, TemperatureData,
+};
+
+/// 一次采集周期结束后发布的不可变数据快照
+///
+/// 界面线程只读取最新的快照，不直接调用任何数据采集代码
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub cpu: CpuData,
+    pub memory: MemoryData,
+    pub disk: DiskData,
+    pub network: NetworkData,
+    pub load: LoadData,
+    pub process: ProcessData,
+    pub logs: LogsData,
+    pub temperature: TemperatureData,
+    pub battery: BatteryData,
+    pub docker: DockerData,
+    pub history: SystemHistory,
+    pub has_alert: bool,
+    /// 各采集模块的运行状态，用于在界面中显示采集失败
+    pub collectors: Vec<CollectorStatus>,
+}
+
+impl Snapshot {
+    /// 最近一次采集失败的模块
+    pub fn failing_collectors(&self) -> impl Iterator<Item = &CollectorStatus> {
+        self.collectors
+            .iter()
+            .filter(|status| status.last_error.is_some())
+    }
+}
+
+/// 界面线程发送给采集线程的命令
+#[derive(Debug, Clone)]
+pub enum CollectorCommand {
+    SetActiveTab(ActiveTab),
+    SetPaused(bool),
+    RefreshNow,
+    SetProcessFilter(ProcessFilter),
+    SetProcessView(ProcessView),
+    SetProcessSort(ProcessSort),
+    /// 开始或停止采集某个进程的详情
+    SetDetailPid(Option<u32>),
+    /// 在进程下列出各个线程
+    SetShowThreads(bool),
+    /// 进程表显示的列变化后，只采集用到的数据
+    SetProcessColumns(Vec<ProcessColumn>),
+    SetPinnedProcesses(Vec<ProcessPattern>),
+    SetWatchedProcesses(Vec<ProcessPattern>),
+    ToggleLogLevel,
+    Shutdown,
+}
+
+/// 数据采集器
+///
+/// 持有共享采样器和所有采集模块的注册表，每个模块按自己的刷新间隔调度。
+/// 可以在后台线程中运行，也可以被无界面模式直接调用
+pub struct DataCollector {
+    sampler: Sampler,
+    cpu: Scheduled<CpuData>,
+    memory: Scheduled<MemoryData>,
+    disk: Scheduled<DiskData>,
+    network: Scheduled<NetworkData>,
+    load: Scheduled<LoadData>,
+    process: Scheduled<ProcessData>,
+    logs: Scheduled<LogsData>,
+    temperature: Scheduled<TemperatureData>,
+    battery: Scheduled<BatteryData>,
+    docker: Scheduled<DockerData>,
+    history: SystemHistory,
+    has_alert: bool,
+    active_tab: ActiveTab,
+    paused: bool,
+    /// 不论当前标签页，采集所有模块（无界面模式使用）
+    all_modules: bool,
+}
+
+impl DataCollector {
+    /// 创建采集器并初始化所有数据模块
+    ///
+    /// # Errors
+    ///
+    /// 如果数据模块初始化失败，返回错误
+    pub fn new(config: &Config) -> Result<Self> {
+        let sampler = Sampler::new();
+        let core_count = sampler.system().cpus().len();
+
+        // 日志在创建时已经读取过一次，从现在开始计算下一次刷新时间
+        let mut logs = Scheduled::new(LogsData::new(config));
+        logs.defer(Instant::now());
+
+        Ok(Self {
+            cpu: Scheduled::new(CpuData::new(config, &sampler)?),
+            memory: Scheduled::new(MemoryData::new(config, &sampler)?),
+            disk: Scheduled::new(DiskData::new(config, &sampler)?),
+            network: Scheduled::new(NetworkData::new(config, &sampler)?),
+            load: Scheduled::new(LoadData::new(config, &sampler)?),
+            process: Scheduled::new(ProcessData::new(config, &sampler)?),
+            logs,
+            temperature: Scheduled::new(TemperatureData::new(config)?),
+            battery: Scheduled::new(BatteryData::new(config)?),
+            docker: Scheduled::new(DockerData::new(config)?),
+            history: SystemHistory::new(core_count, config.general.history_points),
+            has_alert: false,
+            active_tab: ActiveTab::Cpu,
+            paused: false,
+            all_modules: false,
+            sampler,
+        })
+    }
+
+    /// 有监视的进程时，不在进程标签页也需要采集进程以记录其历史
+    fn process_enabled(&self) -> bool {
+        self.all_modules
+            || self.active_tab == ActiveTab::Process
+            || !self.process.data.watches.is_empty()
+    }
+
+    fn docker_enabled(&self) -> bool {
+        cfg!(target_os = "linux") && (self.all_modules || self.active_tab == ActiveTab::Docker)
+    }
+
+    /// 启用所有按标签页采集的模块和所有进程列，并立即完成一次进程和容器采集
+    pub fn enable_all_modules(&mut self) {
+        let now = Instant::now();
+        self.all_modules = true;
+        self.process.data.set_columns(ProcessColumn::ALL.to_vec());
+        self.sampler.refresh(SampleNeeds {
+            processes: true,
+            ..SampleNeeds::default()
+        });
+        self.process.run(&self.sampler, now);
+        if self.docker_enabled() {
+            self.docker.run(&self.sampler, now);
+        }
+    }
+
+    /// 本周期需要采样器刷新的数据
+    ///
+    /// 只刷新已到期模块依赖的数据；进程表扫描开销最大，只在进程标签页可见或有监视的进程时刷新
+    fn sample_needs(&self, now: Instant) -> SampleNeeds {
+        SampleNeeds {
+            cpu: self.cpu.is_due(now),
+            memory: self.memory.is_due(now),
+            processes: self.process_enabled() && self.process.is_due(now),
+            disks: self.disk.is_due(now),
+            networks: self.network.is_due(now),
+            proc_stat: self.cpu.is_due(now) || self.load.is_due(now),
+        }
+    }
+
+    /// 所有启用的模块中最早的下一次采集时间
+    pub fn next_due(&self) -> Instant {
+        let mut due = vec![
+            self.cpu.next_due(),
+            self.memory.next_due(),
+            self.disk.next_due(),
+            self.network.next_due(),
+            self.load.next_due(),
+            self.logs.next_due(),
+            self.temperature.next_due(),
+            self.battery.next_due(),
+        ];
+        if self.process_enabled() {
+            due.push(self.process.next_due());
+        }
+        if self.docker_enabled() {
+            due.push(self.docker.next_due());
+        }
+
+        // 从未运行过的模块立即到期
+        due.into_iter()
+            .map(|next| next.unwrap_or_else(Instant::now))
+            .min()
+            .unwrap_or_else(Instant::now)
+    }
+
+    /// 运行所有已到期的模块并更新历史记录，返回是否有数据更新
+    ///
+    /// 进程和容器数据只在对应标签页处于活动状态时采集。
+    /// 模块失败不会中断其他模块，错误记录在各自的状态中
+    pub fn collect(&mut self) -> bool {
+        if self.paused {
+            return false;
+        }
+
+        let now = Instant::now();
+        let timestamp = chrono::Local::now();
+        let needs = self.sample_needs(now);
+        self.sampler.refresh(needs);
+
+        let mut updated = false;
+
+        if self.cpu.poll(&self.sampler, now) {
+            self.history.record_cpu(timestamp, &self.cpu.data);
+            updated = true;
+        }
+
+        if self.memory.poll(&self.sampler, now) {
+            self.history.record_memory(timestamp, &self.memory.data);
+            updated = true;
+        }
+
+        if self.network.poll(&self.sampler, now) {
+            self.history.record_network(timestamp, &self.network.data);
+            updated = true;
+        }
+
+        if self.load.poll(&self.sampler, now) {
+            self.history.record_load(timestamp, &self.load.data);
+            updated = true;
+        }
+
+        updated |= self.disk.poll(&self.sampler, now);
+        updated |= self.temperature.poll(&self.sampler, now);
+        updated |= self.battery.poll(&self.sampler, now);
+        updated |= self.logs.poll(&self.sampler, now);
+
+        if needs.processes && self.process.poll(&self.sampler, now) {
+            self.history.record_process(timestamp, &self.process.data);
+            updated = true;
+        }
+
+        if self.docker_enabled() {
+            updated |= self.docker.poll(&self.sampler, now);
+        }
+
+        self.has_alert = self.temperature.data.has_warning
+            || self.memory.data.has_warning
+            || self.disk.data.has_warning;
+
+        updated
+    }
+
+    /// 处理一条命令，返回是否需要重新发布快照
+    pub fn handle_command(&mut self, command: CollectorCommand) -> bool {
+        let now = Instant::now();
+        match command {
+            CollectorCommand::SetActiveTab(tab) => {
+                let was_process = self.process_enabled();
+                let was_docker = self.docker_enabled();
+                self.active_tab = tab;
+
+                // 切换到进程或容器标签页时立即采集一次，避免等待下一个周期才显示列表
+                if self.process_enabled() && !was_process {
+                    self.sampler.refresh(SampleNeeds {
+                        processes: true,
+                        ..SampleNeeds::default()
+                    });
+                    self.process.run(&self.sampler, now);
+                    return true;
+                }
+                if self.docker_enabled() && !was_docker {
+                    self.docker.run(&self.sampler, now);
+                    return true;
+                }
+                false
+            }
+            CollectorCommand::SetPaused(paused) => {
+                self.paused = paused;
+                false
+            }
+            CollectorCommand::RefreshNow => {
+                self.cpu.force();
+                self.memory.force();
+                self.disk.force();
+                self.network.force();
+                self.load.force();
+                self.process.force();
+                self.logs.force();
+                self.temperature.force();
+                self.battery.force();
+                self.docker.force();
+                self.collect();
+                true
+            }
+            CollectorCommand::SetProcessFilter(filter) => {
+                self.process.data.set_filter(filter);
+                // 过滤条件变化只需要重新筛选上一次的采样结果
+                self.process.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::SetProcessView(view) => {
+                self.process.data.set_view(view);
+                self.process.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::SetProcessSort(sort) => {
+                self.process.data.set_sort(sort);
+                self.process.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::SetShowThreads(show) => {
+                self.process.data.set_show_threads(show);
+                self.process.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::SetProcessColumns(columns) => {
+                self.process.data.set_columns(columns);
+                self.process.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::SetDetailPid(pid) => {
+                self.process.data.set_detail_pid(pid);
+                self.process.run(&self.sampler, now);
+                self.history
+                    .record_process(chrono::Local::now(), &self.process.data);
+                true
+            }
+            CollectorCommand::SetPinnedProcesses(pins) => {
+                self.process.data.set_pins(pins);
+                self.process.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::SetWatchedProcesses(patterns) => {
+                let was_process = self.process_enabled();
+                self.process.data.set_watches(patterns);
+                // 新的监视规则需要一次新的采样才能找到对应的进程
+                if !was_process && self.process_enabled() {
+                    self.sampler.refresh(SampleNeeds {
+                        processes: true,
+                        ..SampleNeeds::default()
+                    });
+                }
+                self.process.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::ToggleLogLevel => {
+                self.logs.data.toggle_log_level();
+                self.logs.run(&self.sampler, now);
+                true
+            }
+            CollectorCommand::Shutdown => false,
+        }
+    }
+
+    /// 生成当前数据的快照
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            cpu: self.cpu.data.clone(),
+            memory: self.memory.data.clone(),
+            disk: self.disk.data.clone(),
+            network: self.network.data.clone(),
+            load: self.load.data.clone(),
+            process: self.process.data.clone(),
+            logs: self.logs.data.clone(),
+            temperature: self.temperature.data.clone(),
+            battery: self.battery.data.clone(),
+            docker: self.docker.data.clone(),
+            history: self.history.clone(),
+            has_alert: self.has_alert,
+            collectors: vec![
+                self.cpu.status().clone(),
+                self.memory.status().clone(),
+                self.disk.status().clone(),
+                self.network.status().clone(),
+                self.load.status().clone(),
+                self.process.status().clone(),
+                self.logs.status().clone(),
+                self.temperature.status().clone(),
+                self.battery.status().clone(),
+                self.docker.status().clone(),
+            ],
+        }
+    }
+
+    fn cleanup(&mut self) -> Result<()> {
+        self.logs.data.cleanup()
+    }
+}
+
+/// 后台采集线程的句柄
+pub struct CollectorHandle {
+    snapshots: watch::Receiver<Arc<Snapshot>>,
+    commands: mpsc::Sender<CollectorCommand>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl CollectorHandle {
+    /// 在后台线程中运行采集器
+    ///
+    /// 采集涉及阻塞的系统调用和外部命令（journalctl、docker），因此使用独立线程，
+    /// 通过 watch 通道发布最新快照，通过 mpsc 通道接收界面命令
+    pub fn spawn(collector: DataCollector) -> Self {
+        let (snapshot_tx, snapshot_rx) = watch::channel(Arc::new(collector.snapshot()));
+        let (command_tx, command_rx) = mpsc::channel();
+
+        let thread = std::thread::Builder::new()
+            .name("tui-dash-collector".to_string())
+            .spawn(move || run(collector, snapshot_tx, command_rx))
+            .expect("failed to spawn collector thread");
+
+        Self {
+            snapshots: snapshot_rx,
+            commands: command_tx,
+            thread: Some(thread),
+        }
+    }
+
+    /// 获取最新发布的快照
+    pub fn latest(&self) -> Arc<Snapshot> {
+        self.snapshots.borrow().clone()
+    }
+
+    /// 订阅快照更新，供界面之外的消费者（如指标导出）读取
+    pub fn subscribe(&self) -> watch::Receiver<Arc<Snapshot>> {
+        self.snapshots.clone()
+    }
+
+    /// 向采集线程发送命令，线程已退出时忽略
+    pub fn send(&self, command: CollectorCommand) {
+        let _ = self.commands.send(command);
+    }
+
+    /// 停止采集线程并等待其退出
+    pub fn shutdown(&mut self) {
+        self.send(CollectorCommand::Shutdown);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for CollectorHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn run(
+    mut collector: DataCollector,
+    snapshots: watch::Sender<Arc<Snapshot>>,
+    commands: mpsc::Receiver<CollectorCommand>,
+) {
+    loop {
+        let received = if collector.paused {
+            // 暂停时没有模块会运行，只等待界面命令
+            commands.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            let timeout = collector
+                .next_due()
+                .saturating_duration_since(Instant::now());
+            commands.recv_timeout(timeout)
+        };
+
+        match received {
+            Ok(CollectorCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+            Ok(command) => {
+                if collector.handle_command(command) {
+                    snapshots.send_replace(Arc::new(collector.snapshot()));
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if collector.collect() {
+                    snapshots.send_replace(Arc::new(collector.snapshot()));
+                }
+            }
+        }
+    }
+
+    if let Err(_e) = collector.cleanup() {}
+}
@@ -0,0 +1,514 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use crate::data::process::ProcessColumn;
+use crate::data::process_filter::ProcessPattern;
+use crate::data::{
+    DEFAULT_DOCKER_REFRESH_INTERVAL_MS, DEFAULT_HISTORY_POINTS, DEFAULT_LOGS_REFRESH_INTERVAL_MS,
+    DEFAULT_MAX_LINE_LENGTH, DEFAULT_MAX_LOG_LINES, DEFAULT_MAX_NETWORK_INTERFACES,
+    DEFAULT_MAX_PROCESSES, DEFAULT_UPDATE_INTERVAL_MS,
+};
+
+const CONFIG_DIR_NAME: &str = "tui-dash";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+pub const MIN_UPDATE_INTERVAL_MS: u64 = 100;
+pub const MAX_UPDATE_INTERVAL_MS: u64 = 60_000;
+
+/// 配置加载错误
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read config file {path}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid config file {path}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("invalid config file {path}")]
+    Edit {
+        path: PathBuf,
+        source: toml_edit::TomlError,
+    },
+    #[error("failed to write config file {path}")]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid value for `{key}` in {path}: {message}")]
+    Invalid {
+        path: PathBuf,
+        key: &'static str,
+        message: String,
+    },
+}
+
+/// 应用程序配置
+///
+/// 从 `~/.config/tui-dash/config.toml` 加载，未出现的键使用默认值
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub general: GeneralConfig,
+    pub process: ProcessConfig,
+    pub network: NetworkConfig,
+    pub logs: LogsConfig,
+    pub docker: DockerConfig,
+    pub alerts: AlertsConfig,
+    pub masking: MaskingConfig,
+    /// 配置文件路径，界面中修改的置顶和监视规则写回该文件；没有用户配置目录时为 `None`
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneralConfig {
+    /// 数据刷新间隔（毫秒）
+    pub update_interval_ms: u64,
+    /// 历史图表保留的数据点数量
+    pub history_points: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessConfig {
+    /// 进程列表显示的最大进程数
+    pub max_processes: usize,
+    /// 进程表显示的列，界面中按 `o` 可以临时调整
+    pub columns: Vec<ProcessColumn>,
+    /// 置顶的进程：纯数字为 PID，其他为匹配进程名的正则表达式；界面中按 `p` 修改后写回
+    pub pinned: Vec<String>,
+    /// 监视的进程，格式同 `pinned`；界面中按 `w` 修改后写回
+    pub watched: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// 最多显示的网络接口数量
+    pub max_interfaces: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogsConfig {
+    /// 每个日志分类保留的最大行数
+    pub max_lines: usize,
+    /// 单行日志的最大长度，超出部分被截断
+    pub max_line_length: usize,
+    /// 日志刷新间隔（毫秒）
+    pub refresh_interval_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DockerConfig {
+    /// 容器列表刷新间隔（毫秒），每次刷新会为每个容器执行一次 `docker stats`
+    pub refresh_interval_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// 内存使用率告警阈值（百分比）
+    pub memory_percent: f64,
+    /// 交换分区使用率告警阈值（百分比）
+    pub swap_percent: f64,
+    /// 磁盘使用率告警阈值（百分比）
+    pub disk_percent: f64,
+    /// 温度告警阈值（摄氏度）
+    pub temperature_celsius: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaskingConfig {
+    /// 内置规则之外需要隐藏的内容（正则表达式），有捕获组时只隐藏第一个捕获组
+    pub patterns: Vec<String>,
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            update_interval_ms: DEFAULT_UPDATE_INTERVAL_MS,
+            history_points: DEFAULT_HISTORY_POINTS,
+        }
+    }
+}
+
+impl Default for ProcessConfig {
+    fn default() -> Self {
+        Self {
+            max_processes: DEFAULT_MAX_PROCESSES,
+            columns: ProcessColumn::DEFAULT.to_vec(),
+            pinned: Vec::new(),
+            watched: Vec::new(),
+        }
+    }
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            max_interfaces: DEFAULT_MAX_NETWORK_INTERFACES,
+        }
+    }
+}
+
+impl Default for LogsConfig {
+    fn default() -> Self {
+        Self {
+            max_lines: DEFAULT_MAX_LOG_LINES,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            refresh_interval_ms: DEFAULT_LOGS_REFRESH_INTERVAL_MS,
+        }
+    }
+}
+
+impl Default for DockerConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_ms: DEFAULT_DOCKER_REFRESH_INTERVAL_MS,
+        }
+    }
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            memory_percent: 90.0,
+            swap_percent: 80.0,
+            disk_percent: 90.0,
+            temperature_celsius: 70.0,
+        }
+    }
+}
+
+impl Config {
+    /// 默认配置文件路径：`<用户配置目录>/tui-dash/config.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+    }
+
+    /// 加载配置
+    ///
+    /// 显式指定的路径必须存在；默认路径下没有配置文件时使用默认配置
+    ///
+    /// # Errors
+    ///
+    /// 文件无法读取、包含未知键或取值非法时返回 [`ConfigError`]
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        match path {
+            Some(path) => Self::load_from(path),
+            None => match Self::default_path() {
+                Some(path) if path.exists() => Self::load_from(&path),
+                // 之后在界面中置顶或监视进程时再创建默认配置文件
+                path => Ok(Self {
+                    path,
+                    ..Self::default()
+                }),
+            },
+        }
+    }
+
+    fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let mut config = Self::parse(&content, path)?;
+        config.path = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// 把置顶和监视规则写回配置文件，保留文件中的其他内容和注释；文件不存在时创建
+    ///
+    /// # Errors
+    ///
+    /// 文件无法读写或不是合法的 TOML 时返回 [`ConfigError`]
+    pub fn save_process_patterns(
+        path: &Path,
+        pinned: &[String],
+        watched: &[String],
+    ) -> Result<(), ConfigError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(source) => {
+                return Err(ConfigError::Read {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let content = update_process_patterns(&content, pinned, watched).map_err(|e| match e {
+            PatternsError::Toml(source) => ConfigError::Edit {
+                path: path.to_path_buf(),
+                source,
+            },
+            PatternsError::NotATable => ConfigError::Invalid {
+                path: path.to_path_buf(),
+                key: "process",
+                message: "expected a table".to_string(),
+            },
+        })?;
+
+        let write_error = |source| ConfigError::Write {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(write_error)?;
+        }
+        fs::write(path, content).map_err(write_error)
+    }
+
+    /// 解析并校验配置内容，`path` 仅用于错误信息
+    pub fn parse(content: &str, path: &Path) -> Result<Self, ConfigError> {
+        let config: Config = toml::from_str(content).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        config.validate(path)?;
+        Ok(config)
+    }
+
+    fn validate(&self, path: &Path) -> Result<(), ConfigError> {
+        let invalid = |key: &'static str, message: String| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            key,
+            message,
+        };
+
+        check_range(
+            self.general.update_interval_ms,
+            MIN_UPDATE_INTERVAL_MS,
+            MAX_UPDATE_INTERVAL_MS,
+        )
+        .map_err(|m| invalid("general.update_interval_ms", m))?;
+        check_range(self.general.history_points, 2, 3600)
+            .map_err(|m| invalid("general.history_points", m))?;
+        check_range(self.process.max_processes, 1, 100_000)
+            .map_err(|m| invalid("process.max_processes", m))?;
+        if self.process.columns.is_empty() {
+            return Err(invalid(
+                "process.columns",
+                "at least one column must be listed".to_string(),
+            ));
+        }
+        for (key, patterns) in [
+            ("process.pinned", &self.process.pinned),
+            ("process.watched", &self.process.watched),
+        ] {
+            for pattern in patterns {
+                ProcessPattern::parse(pattern).map_err(|e| invalid(key, e.to_string()))?;
+            }
+        }
+        for pattern in &self.masking.patterns {
+            Regex::new(pattern).map_err(|e| invalid("masking.patterns", e.to_string()))?;
+        }
+        check_range(self.network.max_interfaces, 1, 1024)
+            .map_err(|m| invalid("network.max_interfaces", m))?;
+        check_range(self.logs.max_lines, 1, 10_000).map_err(|m| invalid("logs.max_lines", m))?;
+        check_range(self.logs.max_line_length, 16, 65_536)
+            .map_err(|m| invalid("logs.max_line_length", m))?;
+        check_range(self.logs.refresh_interval_ms, 500, 600_000)
+            .map_err(|m| invalid("logs.refresh_interval_ms", m))?;
+        check_range(self.docker.refresh_interval_ms, 1000, 600_000)
+            .map_err(|m| invalid("docker.refresh_interval_ms", m))?;
+
+        for (key, value) in [
+            ("alerts.memory_percent", self.alerts.memory_percent),
+            ("alerts.swap_percent", self.alerts.swap_percent),
+            ("alerts.disk_percent", self.alerts.disk_percent),
+        ] {
+            if !(0.0..=100.0).contains(&value) {
+                return Err(invalid(
+                    key,
+                    format!("{} is not a percentage between 0 and 100", value),
+                ));
+            }
+        }
+        if !(0.0..=200.0).contains(&self.alerts.temperature_celsius) {
+            return Err(invalid(
+                "alerts.temperature_celsius",
+                format!(
+                    "{} is outside the supported range 0-200",
+                    self.alerts.temperature_celsius
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// 修改配置文件内容的错误，由调用方附上文件路径
+#[derive(Debug)]
+enum PatternsError {
+    Toml(toml_edit::TomlError),
+    NotATable,
+}
+
+/// 在配置文件内容中替换 `process.pinned` 和 `process.watched`
+fn update_process_patterns(
+    content: &str,
+    pinned: &[String],
+    watched: &[String],
+) -> Result<String, PatternsError> {
+    let mut document: toml_edit::DocumentMut = content.parse().map_err(PatternsError::Toml)?;
+    let process = document
+        .entry("process")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or(PatternsError::NotATable)?;
+    for (key, patterns) in [("pinned", pinned), ("watched", watched)] {
+        let array: toml_edit::Array = patterns.iter().map(String::as_str).collect();
+        process.insert(key, toml_edit::value(array));
+    }
+    Ok(document.to_string())
+}
+
+fn check_range<T>(value: T, min: T, max: T) -> Result<(), String>
+where
+    T: PartialOrd + std::fmt::Display,
+{
+    if value < min || value > max {
+        Err(format!(
+            "{} is outside the allowed range {}-{}",
+            value, min, max
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Result<Config, ConfigError> {
+        Config::parse(content, Path::new("config.toml"))
+    }
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        assert_eq!(parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_partial_config_overrides_values() {
+        let config =
+            parse("[general]\nupdate_interval_ms = 500\n\n[process]\nmax_processes = 20\n")
+                .unwrap();
+        assert_eq!(config.general.update_interval_ms, 500);
+        assert_eq!(config.general.history_points, DEFAULT_HISTORY_POINTS);
+        assert_eq!(config.process.max_processes, 20);
+        assert_eq!(config.process.columns, ProcessColumn::DEFAULT);
+    }
+
+    #[test]
+    fn test_process_columns() {
+        let config = parse("[process]\ncolumns = [\"pid\", \"user\", \"disk_read\"]\n").unwrap();
+        assert_eq!(
+            config.process.columns,
+            vec![
+                ProcessColumn::Pid,
+                ProcessColumn::User,
+                ProcessColumn::DiskRead
+            ]
+        );
+        assert!(matches!(
+            parse("[process]\ncolumns = []\n").unwrap_err(),
+            ConfigError::Invalid {
+                key: "process.columns",
+                ..
+            }
+        ));
+        assert!(parse("[process]\ncolumns = [\"pid\", \"colour\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_process_patterns() {
+        let config = parse("[process]\npinned = [\"^nginx$\", \"42\"]\n").unwrap();
+        assert_eq!(config.process.pinned, vec!["^nginx$", "42"]);
+        assert!(config.process.watched.is_empty());
+        assert!(matches!(
+            parse("[process]\nwatched = [\"(\"]\n").unwrap_err(),
+            ConfigError::Invalid {
+                key: "process.watched",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_update_process_patterns_keeps_comments() {
+        let content = "# 采集间隔\n[general]\nupdate_interval_ms = 500 # 毫秒\n\n[process]\nmax_processes = 20\npinned = [\"old\"]\n";
+        let updated =
+            update_process_patterns(content, &["^nginx$".to_string()], &["42".to_string()])
+                .unwrap();
+        assert!(updated.starts_with("# 采集间隔\n[general]\nupdate_interval_ms = 500 # 毫秒\n"));
+        let config = parse(&updated).unwrap();
+        assert_eq!(config.general.update_interval_ms, 500);
+        assert_eq!(config.process.max_processes, 20);
+        assert_eq!(config.process.pinned, vec!["^nginx$"]);
+        assert_eq!(config.process.watched, vec!["42"]);
+
+        // 没有配置文件时新建 [process] 表
+        let updated = update_process_patterns("", &[], &["sshd".to_string()]).unwrap();
+        assert_eq!(parse(&updated).unwrap().process.watched, vec!["sshd"]);
+        assert!(update_process_patterns("process = 1\n", &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_masking_patterns() {
+        let config = parse("[masking]\npatterns = [\"internal-\\\\w+\"]\n").unwrap();
+        assert_eq!(config.masking.patterns, vec!["internal-\\w+"]);
+        assert!(matches!(
+            parse("[masking]\npatterns = [\"[\"]\n").unwrap_err(),
+            ConfigError::Invalid {
+                key: "masking.patterns",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_unknown_key_is_rejected() {
+        for (content, key) in [
+            ("[general]\nupdate_interval = 500\n", "update_interval"),
+            ("[colors]\n", "colors"),
+        ] {
+            match parse(content).unwrap_err() {
+                ConfigError::Parse { source, .. } => assert!(source.to_string().contains(key)),
+                other => panic!("unexpected error: {}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_value_is_rejected() {
+        let err = parse("[general]\nupdate_interval_ms = 10\n").unwrap_err();
+        match err {
+            ConfigError::Invalid { key, .. } => assert_eq!(key, "general.update_interval_ms"),
+            other => panic!("unexpected error: {}", other),
+        }
+
+        let err = parse("[alerts]\nmemory_percent = 120.0\n").unwrap_err();
+        assert!(err.to_string().contains("alerts.memory_percent"));
+    }
+
+    #[test]
+    fn test_wrong_type_is_rejected() {
+        let err = parse("[process]\nmax_processes = \"many\"\n").unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { .. }));
+    }
+}
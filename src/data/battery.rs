@@ -1,8 +1,13 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use super::{Collector, CollectorError, Sampler, SysRoot};
+use crate::config::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BatteryStatus {
     Charging,
     Discharging,
@@ -10,7 +15,7 @@ pub enum BatteryStatus {
     Unknown,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatteryData {
     pub present: bool,
     pub status: BatteryStatus,
@@ -21,10 +26,12 @@ pub struct BatteryData {
     pub voltage_now: Option<u64>,
     pub time_to_empty: Option<u64>,
     pub time_to_full: Option<u64>,
+    #[serde(skip)]
+    interval: Duration,
 }
 
 impl BatteryData {
-    pub fn new() -> Result<Self> {
+    pub fn new(config: &Config) -> Result<Self> {
         Ok(Self {
             present: false,
             status: BatteryStatus::Unknown,
@@ -35,21 +42,27 @@ impl BatteryData {
             voltage_now: None,
             time_to_empty: None,
             time_to_full: None,
+            interval: Duration::from_millis(config.general.update_interval_ms),
         })
     }
 
     #[cfg(target_os = "linux")]
-    pub fn update(&mut self) -> Result<()> {
-        let power_supply_path = Path::new("/sys/class/power_supply");
+    fn read_power_supply(&mut self, sysroot: &SysRoot) -> Result<(), CollectorError> {
+        let power_supply_path = sysroot.path("/sys/class/power_supply");
 
         if !power_supply_path.exists() {
             return Ok(());
         }
 
-        for entry in fs::read_dir(power_supply_path)? {
-            let entry = entry?;
-            let device_path = entry.path();
+        let mut devices = fs::read_dir(&power_supply_path)
+            .map_err(|e| CollectorError::read(&power_supply_path, e))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| CollectorError::read(&power_supply_path, e))?;
+        // 多块电池时总是显示编号最小的一块
+        devices.sort();
 
+        for device_path in devices {
             let type_path = device_path.join("type");
             if type_path.exists() {
                 if let Ok(device_type) = fs::read_to_string(&type_path) {
@@ -65,7 +78,8 @@ impl BatteryData {
     }
 
     #[cfg(target_os = "linux")]
-    fn read_battery_info(&mut self, path: &Path) -> Result<()> {
+    #[allow(clippy::bind_instead_of_map, clippy::manual_checked_ops)]
+    fn read_battery_info(&mut self, path: &Path) -> Result<(), CollectorError> {
         self.present = true;
 
         self.status = fs::read_to_string(path.join("status"))
@@ -101,17 +115,18 @@ impl BatteryData {
             .ok()
             .and_then(|s| s.trim().parse().ok());
 
+        // energy_* 单位为 µWh，power_now 单位为 µW，相除得到小时数，换算为秒
         if let Some(power) = self.power_now {
             if power > 0 {
                 match self.status {
                     BatteryStatus::Discharging => {
-                        let time = self.energy_now / power;
+                        let time = self.energy_now.saturating_mul(3600) / power;
                         self.time_to_empty = Some(time);
                         self.time_to_full = None;
                     }
                     BatteryStatus::Charging => {
                         let energy_needed = self.energy_full.saturating_sub(self.energy_now);
-                        let time = energy_needed / power;
+                        let time = energy_needed.saturating_mul(3600) / power;
                         self.time_to_empty = None;
                         self.time_to_full = Some(time);
                     }
@@ -120,19 +135,26 @@ impl BatteryData {
                         self.time_to_full = None;
                     }
                 }
+            } else {
+                self.time_to_empty = None;
+                self.time_to_full = None;
             }
+        } else {
+            self.time_to_empty = None;
+            self.time_to_full = None;
         }
 
         Ok(())
     }
 
     #[cfg(target_os = "windows")]
-    pub fn update(&mut self) -> Result<()> {
+    fn read_power_supply(&mut self, _sysroot: &SysRoot) -> Result<(), CollectorError> {
         use windows::core::*;
         use wmi::{COMLibrary, WMIConnection};
 
-        let com_con = COMLibrary::new()?;
-        let wmi_con = WMIConnection::new(com_con)?;
+        let com_con = COMLibrary::new().map_err(|e| CollectorError::Platform(e.to_string()))?;
+        let wmi_con =
+            WMIConnection::new(com_con).map_err(|e| CollectorError::Platform(e.to_string()))?;
 
         if let Ok(batteries) = wmi_con.query::<Win32_Battery>() {
             if let Some(battery) = batteries.first() {
@@ -202,7 +224,7 @@ impl BatteryData {
     }
 
     #[cfg(not(any(target_os = "linux", target_os = "windows")))]
-    pub fn update(&mut self) -> Result<()> {
+    fn read_power_supply(&mut self, _sysroot: &SysRoot) -> Result<(), CollectorError> {
         Ok(())
     }
 
@@ -235,9 +257,23 @@ impl BatteryData {
     }
 }
 
+impl Collector for BatteryData {
+    fn name(&self) -> &'static str {
+        "battery"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        self.read_power_supply(sampler.sysroot())
+    }
+}
+
 impl Default for BatteryData {
     fn default() -> Self {
-        Self::new().expect("Failed to create default BatteryData")
+        Self::new(&Config::default()).expect("Failed to create default BatteryData")
     }
 }
 
@@ -249,4 +285,47 @@ struct Win32_Battery {
     DesignCapacity: Option<u32>,
     DesignVoltage: Option<u32>,
     EstimatedRunTime: Option<u32>,
-}
\ No newline at end of file
+}
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::data::sysroot::fixture;
+
+    #[test]
+    fn test_discharging_battery_eta() {
+        let mut data = BatteryData::default();
+        data.read_power_supply(&fixture()).unwrap();
+
+        assert!(data.present);
+        assert_eq!(data.status, BatteryStatus::Discharging);
+        assert_eq!(data.capacity, 75);
+        // 37.5 Wh / 15 W = 2.5 h
+        assert_eq!(data.time_to_empty, Some(9000));
+        assert_eq!(data.time_to_full, None);
+        assert_eq!(data.format_time_remaining(), "2h 30m");
+        assert_eq!(data.format_power(), "15.00W");
+        assert_eq!(data.format_voltage(), "12.00V");
+    }
+
+    #[test]
+    fn test_charging_battery_eta() {
+        let mut data = BatteryData::default();
+        data.read_battery_info(&fixture().path("/sys/class/power_supply/BAT1"))
+            .unwrap();
+
+        assert_eq!(data.status, BatteryStatus::Charging);
+        // (50 Wh - 20 Wh) / 20 W = 1.5 h
+        assert_eq!(data.time_to_full, Some(5400));
+        assert_eq!(data.time_to_empty, None);
+        assert_eq!(data.format_time_remaining(), "1h 30m");
+    }
+
+    #[test]
+    fn test_no_power_supply() {
+        let mut data = BatteryData::default();
+        data.read_power_supply(&SysRoot::new("/nonexistent-sysroot"))
+            .unwrap();
+        assert!(!data.present);
+        assert_eq!(data.format_time_remaining(), "-");
+    }
+}
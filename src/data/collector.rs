@@ -0,0 +1,199 @@
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+use super::Sampler;
+
+/// 数据采集错误
+#[derive(Debug, Error)]
+pub enum CollectorError {
+    /// 读取 /proc、/sys 等文件失败
+    #[error("failed to read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    /// 外部命令无法执行或返回失败
+    #[error("`{command}` failed: {message}")]
+    Command {
+        command: &'static str,
+        message: String,
+    },
+    /// 平台接口（WMI 等）调用失败
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    #[error("{0}")]
+    Platform(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl CollectorError {
+    pub fn read(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self::Read {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+/// 数据采集模块的统一接口
+///
+/// 每个模块声明自己的名称和刷新间隔，由采集线程按各自的节奏调度
+pub trait Collector {
+    /// 模块名称，用于在界面中显示采集状态
+    fn name(&self) -> &'static str;
+
+    /// 两次采集之间的最小间隔
+    fn interval(&self) -> Duration;
+
+    /// 从采样器和其他数据源更新数据
+    ///
+    /// # Errors
+    ///
+    /// 数据源不可用或读取失败时返回 [`CollectorError`]
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError>;
+}
+
+/// 单个采集模块的运行状态
+#[derive(Debug, Clone)]
+pub struct CollectorStatus {
+    pub name: &'static str,
+    pub interval: Duration,
+    /// 最近一次采集失败的错误信息，成功后清除
+    pub last_error: Option<String>,
+    /// 连续失败次数
+    pub failures: u32,
+}
+
+/// 带调度信息的采集模块
+#[derive(Debug)]
+pub struct Scheduled<C> {
+    pub data: C,
+    next_due: Option<Instant>,
+    status: CollectorStatus,
+}
+
+impl<C: Collector> Scheduled<C> {
+    pub fn new(data: C) -> Self {
+        let status = CollectorStatus {
+            name: data.name(),
+            interval: data.interval(),
+            last_error: None,
+            failures: 0,
+        };
+        Self {
+            data,
+            next_due: None,
+            status,
+        }
+    }
+
+    /// 是否已到下一次采集时间
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_due.is_none_or(|due| now >= due)
+    }
+
+    pub fn next_due(&self) -> Option<Instant> {
+        self.next_due
+    }
+
+    /// 数据已经在别处加载过，从 `now` 开始等待一个完整的刷新间隔
+    pub fn defer(&mut self, now: Instant) {
+        self.next_due = Some(now + self.status.interval);
+    }
+
+    /// 下一次调度时立即采集
+    pub fn force(&mut self) {
+        self.next_due = None;
+    }
+
+    /// 到期时执行一次采集，返回是否执行了采集
+    pub fn poll(&mut self, sampler: &Sampler, now: Instant) -> bool {
+        if !self.is_due(now) {
+            return false;
+        }
+        self.run(sampler, now);
+        true
+    }
+
+    /// 无论是否到期都执行一次采集，并记录结果
+    pub fn run(&mut self, sampler: &Sampler, now: Instant) {
+        match self.data.update(sampler) {
+            Ok(()) => {
+                self.status.last_error = None;
+                self.status.failures = 0;
+            }
+            Err(e) => {
+                self.status.last_error = Some(e.to_string());
+                self.status.failures = self.status.failures.saturating_add(1);
+            }
+        }
+        self.next_due = Some(now + self.status.interval);
+    }
+
+    pub fn status(&self) -> &CollectorStatus {
+        &self.status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Flaky {
+        fail: bool,
+    }
+
+    impl Collector for Flaky {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_secs(5)
+        }
+
+        fn update(&mut self, _sampler: &Sampler) -> Result<(), CollectorError> {
+            if self.fail {
+                Err(CollectorError::Command {
+                    command: "flaky",
+                    message: "exit status 1".to_string(),
+                })
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_scheduled_respects_interval() {
+        let sampler = Sampler::new();
+        let mut scheduled = Scheduled::new(Flaky { fail: false });
+        let now = Instant::now();
+
+        assert!(scheduled.poll(&sampler, now));
+        assert!(!scheduled.poll(&sampler, now + Duration::from_secs(1)));
+        assert!(scheduled.poll(&sampler, now + Duration::from_secs(5)));
+
+        scheduled.force();
+        assert!(scheduled.is_due(now));
+    }
+
+    #[test]
+    fn test_scheduled_records_failures() {
+        let sampler = Sampler::new();
+        let mut scheduled = Scheduled::new(Flaky { fail: true });
+        let now = Instant::now();
+
+        scheduled.run(&sampler, now);
+        scheduled.run(&sampler, now);
+        assert_eq!(scheduled.status().failures, 2);
+        assert_eq!(
+            scheduled.status().last_error.as_deref(),
+            Some("`flaky` failed: exit status 1")
+        );
+
+        scheduled.data.fail = false;
+        scheduled.run(&sampler, now);
+        assert_eq!(scheduled.status().failures, 0);
+        assert!(scheduled.status().last_error.is_none());
+    }
+}
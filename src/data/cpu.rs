@@ -1,48 +1,224 @@
 use anyhow::Result;
-use sysinfo::System;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Debug)]
+use super::procfs::{self, CpuStat, CpuTimes};
+use super::{Collector, CollectorError, Sampler};
+use crate::config::Config;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuData {
     pub global_cpu_usage: f32,
     pub core_usages: Vec<f32>,
     pub cpu_frequency: u64,
     pub cpu_brand: String,
     pub cpu_cores: usize,
-    sys: System,
-    is_first_update: bool,
+    /// 上次采集以来各类 CPU 时间的占比，读不到 `/proc/stat` 时为 `None`
+    #[serde(default)]
+    pub breakdown: Option<CpuBreakdown>,
+    /// 每个核心的 CPU 时间占比，离线的核心各项均为 0
+    #[serde(default)]
+    pub core_breakdowns: Vec<CpuBreakdown>,
+    #[serde(skip)]
+    last_stat: Option<CpuStat>,
+    #[serde(skip)]
+    interval: Duration,
 }
 
 impl CpuData {
-    pub fn new() -> Result<Self> {
-        let sys = System::new_all();
-        let cpu_count = sys.cpus().len();
+    pub fn new(config: &Config, sampler: &Sampler) -> Result<Self> {
+        let cpu_count = sampler.system().cpus().len();
         Ok(Self {
             global_cpu_usage: 0.0,
             core_usages: Vec::new(),
             cpu_frequency: 0,
             cpu_brand: "Unknown".to_string(),
             cpu_cores: cpu_count,
-            sys,
-            is_first_update: true,
+            breakdown: None,
+            core_breakdowns: Vec::new(),
+            last_stat: sampler.proc_stat().and_then(procfs::parse_cpu_stat),
+            interval: Duration::from_millis(config.general.update_interval_ms),
         })
     }
 
-    pub fn update(&mut self) -> Result<()> {
-        if self.is_first_update {
-            self.sys.refresh_cpu();
-            self.sys.refresh_cpu();
-            self.is_first_update = false;
-        } else {
-            self.sys.refresh_cpu();
+    /// 根据 `/proc/stat` 与上次读取的差值更新各类 CPU 时间的占比
+    fn update_breakdown(&mut self, stat: Option<CpuStat>) {
+        match (&self.last_stat, &stat) {
+            (Some(last), Some(stat)) => {
+                self.breakdown = Some(CpuBreakdown::between(&last.total, &stat.total));
+                self.core_breakdowns = stat
+                    .cores
+                    .iter()
+                    .enumerate()
+                    .map(
+                        |(core, times)| match (last.cores.get(core).copied().flatten(), times) {
+                            (Some(last), Some(times)) => CpuBreakdown::between(&last, times),
+                            _ => CpuBreakdown::default(),
+                        },
+                    )
+                    .collect();
+            }
+            _ => {
+                self.breakdown = None;
+                self.core_breakdowns.clear();
+            }
+        }
+        self.last_stat = stat;
+    }
+}
+
+/// 一段时间内各类 CPU 时间的占比（%），由 `/proc/stat` 两次读取的差值计算
+///
+/// `user` 和 `nice` 不含虚拟机占用的时间，后者单独计入 `guest`，因此各项之和为 100
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuBreakdown {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    pub steal: f32,
+    pub guest: f32,
+}
+
+impl CpuBreakdown {
+    /// 各项的名称，与 `values` 的顺序一致，空闲时间在最后，便于堆叠显示
+    pub const NAMES: [&'static str; 9] = [
+        "user", "nice", "system", "iowait", "irq", "softirq", "steal", "guest", "idle",
+    ];
+
+    pub fn values(&self) -> [f32; 9] {
+        [
+            self.user,
+            self.nice,
+            self.system,
+            self.iowait,
+            self.irq,
+            self.softirq,
+            self.steal,
+            self.guest,
+            self.idle,
+        ]
+    }
+
+    /// 计算两次读取之间的占比；计数器回退或时间没有变化时各项为 0
+    pub fn between(last: &CpuTimes, current: &CpuTimes) -> Self {
+        let delta = |current: u64, last: u64| current.saturating_sub(last) as f32;
+        let guest = delta(current.guest, last.guest);
+        let guest_nice = delta(current.guest_nice, last.guest_nice);
+        let mut breakdown = Self {
+            user: (delta(current.user, last.user) - guest).max(0.0),
+            nice: (delta(current.nice, last.nice) - guest_nice).max(0.0),
+            system: delta(current.system, last.system),
+            idle: delta(current.idle, last.idle),
+            iowait: delta(current.iowait, last.iowait),
+            irq: delta(current.irq, last.irq),
+            softirq: delta(current.softirq, last.softirq),
+            steal: delta(current.steal, last.steal),
+            guest: guest + guest_nice,
+        };
+        let total: f32 = breakdown.values().iter().sum();
+        if total > 0.0 {
+            for value in [
+                &mut breakdown.user,
+                &mut breakdown.nice,
+                &mut breakdown.system,
+                &mut breakdown.idle,
+                &mut breakdown.iowait,
+                &mut breakdown.irq,
+                &mut breakdown.softirq,
+                &mut breakdown.steal,
+                &mut breakdown.guest,
+            ] {
+                *value = *value / total * 100.0;
+            }
         }
-        self.global_cpu_usage = self.sys.global_cpu_info().cpu_usage();
-        self.core_usages = self.sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        breakdown
+    }
+}
+
+impl Collector for CpuData {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
 
-        if let Some(cpu) = self.sys.cpus().first() {
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        let sys = sampler.system();
+        self.global_cpu_usage = sys.global_cpu_info().cpu_usage();
+        self.core_usages = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+
+        if let Some(cpu) = sys.cpus().first() {
             self.cpu_frequency = cpu.frequency();
             self.cpu_brand = cpu.brand().to_string();
         }
 
+        self.update_breakdown(sampler.proc_stat().and_then(procfs::parse_cpu_stat));
+
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_between() {
+        let last = CpuTimes {
+            user: 100,
+            idle: 1000,
+            ..CpuTimes::default()
+        };
+        let current = CpuTimes {
+            user: 150,
+            system: 10,
+            idle: 1010,
+            iowait: 10,
+            steal: 20,
+            // guest 已经计入 user
+            guest: 10,
+            ..CpuTimes::default()
+        };
+        let breakdown = CpuBreakdown::between(&last, &current);
+        assert_eq!(breakdown.user, 40.0);
+        assert_eq!(breakdown.guest, 10.0);
+        assert_eq!(breakdown.system, 10.0);
+        assert_eq!(breakdown.iowait, 10.0);
+        assert_eq!(breakdown.steal, 20.0);
+        assert_eq!(breakdown.idle, 10.0);
+
+        // 没有经过时间或计数器回退
+        assert_eq!(
+            CpuBreakdown::between(&current, &current),
+            CpuBreakdown::default()
+        );
+        assert_eq!(
+            CpuBreakdown::between(&current, &last)
+                .values()
+                .iter()
+                .sum::<f32>(),
+            0.0
+        );
+    }
+    #[test]
+    fn test_breakdown_from_sampled_proc_stat() {
+        use crate::data::sysroot::{fixture, fixture_next};
+
+        let config = Config::default();
+        let mut data = CpuData::new(&config, &Sampler::with_sysroot(fixture())).unwrap();
+        assert_eq!(data.breakdown, None);
+
+        data.update(&Sampler::with_sysroot(fixture_next())).unwrap();
+        let breakdown = data.breakdown.unwrap();
+        assert_eq!(breakdown.user, 40.0);
+        assert_eq!(breakdown.system, 10.0);
+        assert_eq!(breakdown.idle, 50.0);
+        assert_eq!(data.core_breakdowns.len(), 2);
+    }
+}
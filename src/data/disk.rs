@@ -1,160 +1,91 @@
+use super::{Collector, CollectorError, Sampler, SysRoot};
+use crate::config::Config;
 use crate::i18n;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::time::Duration;
 use sysinfo::{DiskKind, Disks};
 
-#[derive(Debug)]
+/// 单个挂载点的容量和读写速率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub mount_point: String,
+    /// 已用空间（字节）
+    pub used: u64,
+    /// 总空间（字节）
+    pub total: u64,
+    /// 读取速率（字节/秒）
+    pub read_speed: u64,
+    /// 写入速率（字节/秒）
+    pub write_speed: u64,
+}
+
+impl DiskInfo {
+    pub fn usage_percent(&self) -> f64 {
+        if self.total > 0 {
+            self.used as f64 / self.total as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiskData {
-    pub disks: Vec<(String, u64, u64, u64, u64)>,
-    disks_info: Disks,
+    pub disks: Vec<DiskInfo>,
+    #[serde(skip)]
     last_read_sectors: HashMap<String, u64>,
+    #[serde(skip)]
     last_write_sectors: HashMap<String, u64>,
+    #[serde(skip, default = "std::time::Instant::now")]
     last_update_time: std::time::Instant,
     pub has_warning: bool,
+    #[serde(skip)]
+    warning_percent: f64,
+    #[serde(skip)]
+    interval: Duration,
 
     #[cfg(target_os = "linux")]
+    #[serde(skip)]
     device_to_mount: HashMap<String, String>,
 }
 
 impl DiskData {
-    pub fn new() -> Result<Self> {
-        let disks_info = Disks::new_with_refreshed_list();
-        let mut disks = Vec::new();
-        let mut last_read_sectors = HashMap::new();
-        let mut last_write_sectors = HashMap::new();
-
-        #[cfg(target_os = "linux")]
-        let mut device_to_mount = Self::read_device_mount_mapping();
-
-        for disk in disks_info.list() {
-            if disk.kind() == DiskKind::HDD || disk.kind() == DiskKind::SSD {
-                let mount_point = disk
-                    .mount_point()
-                    .to_str()
-                    .unwrap_or(i18n::t("unknown"))
-                    .to_string();
-                let device_name = disk.name().to_string_lossy().to_string();
-
-                #[cfg(target_os = "linux")]
-                {
-                    device_to_mount
-                        .entry(device_name)
-                        .or_insert_with(|| mount_point.clone());
-                }
-
-                last_read_sectors.insert(mount_point.clone(), 0);
-                last_write_sectors.insert(mount_point.clone(), 0);
-            }
-        }
-
-        #[cfg(target_os = "linux")]
-        Self::update_disks(
-            &disks_info,
-            &mut disks,
-            &last_read_sectors,
-            &last_write_sectors,
-            &device_to_mount,
-            0.0,
-        )?;
-
-        #[cfg(target_os = "windows")]
-        Self::update_disks_windows(&disks_info, &mut disks)?;
-
-        Ok(Self {
-            disks_info,
-            disks,
-            last_read_sectors,
-            last_write_sectors,
+    pub fn new(config: &Config, sampler: &Sampler) -> Result<Self> {
+        let mut data = Self {
+            disks: Vec::new(),
+            last_read_sectors: HashMap::new(),
+            last_write_sectors: HashMap::new(),
             last_update_time: std::time::Instant::now(),
             has_warning: false,
+            warning_percent: config.alerts.disk_percent,
+            interval: Duration::from_millis(config.general.update_interval_ms),
 
             #[cfg(target_os = "linux")]
-            device_to_mount,
-        })
-    }
-
-    pub fn update(&mut self) -> Result<()> {
-        self.disks.clear();
-        self.disks_info.refresh();
-        self.has_warning = false;
-
-        let now = std::time::Instant::now();
+            device_to_mount: HashMap::new(),
+        };
 
+        // 第一次采样只记录扇区计数，没有上一次的计数，速率为 0
         #[cfg(target_os = "linux")]
-        {
-            let elapsed_secs = self.last_update_time.elapsed().as_secs_f64();
-
-            self.device_to_mount = Self::read_device_mount_mapping();
-
-            for disk in self.disks_info.list() {
-                if disk.kind() == DiskKind::HDD || disk.kind() == DiskKind::SSD {
-                    let mount_point = disk
-                        .mount_point()
-                        .to_str()
-                        .unwrap_or(i18n::t("unknown"))
-                        .to_string();
-                    let device_name = disk.name().to_string_lossy().to_string();
-                    self.device_to_mount
-                        .entry(device_name)
-                        .or_insert_with(|| mount_point.clone());
-                }
-            }
-
-            Self::update_disks(
-                &self.disks_info,
-                &mut self.disks,
-                &self.last_read_sectors,
-                &self.last_write_sectors,
-                &self.device_to_mount,
-                elapsed_secs,
-            )?;
-
-            if let Ok(disk_stats) = Self::read_disk_stats() {
-                for (device_name, (read_sectors, write_sectors)) in disk_stats {
-                    if let Some(mount_point) = self.device_to_mount.get(&device_name).cloned() {
-                        self.last_read_sectors
-                            .insert(mount_point.clone(), read_sectors);
-                        self.last_write_sectors
-                            .insert(mount_point.clone(), write_sectors);
-                    } else {
-                        self.last_read_sectors
-                            .insert(device_name.clone(), read_sectors);
-                        self.last_write_sectors
-                            .insert(device_name.clone(), write_sectors);
-                    }
-                }
-            }
-        }
+        data.sample(sampler.sysroot(), &partitions(sampler.disks()), 0.0)?;
 
         #[cfg(target_os = "windows")]
-        {
-            self.update_windows()?;
-        }
+        Self::update_disks_windows(sampler.disks(), &mut data.disks)?;
 
-        for (_mount_point, used, total, _, _) in &self.disks {
-            if *total > 0 {
-                let usage_percent = (*used as f64 / *total as f64) * 100.0;
-                if usage_percent > 90.0 {
-                    self.has_warning = true;
-                    break;
-                }
-            }
-        }
-
-        self.last_update_time = now;
-        Ok(())
+        Ok(data)
     }
 
     #[cfg(target_os = "linux")]
-    fn read_device_mount_mapping() -> HashMap<String, String> {
+    fn read_device_mount_mapping(sysroot: &SysRoot) -> HashMap<String, String> {
         let mut mapping = HashMap::new();
 
-        if let Ok(content) = fs::read_to_string("/proc/mounts") {
+        if let Ok(content) = fs::read_to_string(sysroot.path("/proc/mounts")) {
             for line in content.lines() {
                 let parts: Vec<&str> = line.split_whitespace().collect();
                 if parts.len() >= 2 {
-                    let device = parts.get(0).unwrap_or(&"").to_string();
+                    let device = parts.first().unwrap_or(&"").to_string();
                     let mount_point = parts.get(1).unwrap_or(&"").to_string();
                     if device.starts_with("/dev/") {
                         let device_name =
@@ -168,76 +99,93 @@ impl DiskData {
         mapping
     }
 
+    /// 按分区列表和 `/proc/diskstats` 更新容量和读写速率，并记录本次的扇区计数
     #[cfg(target_os = "linux")]
-    fn update_disks(
-        disks_info: &Disks,
-        disks: &mut Vec<(String, u64, u64, u64, u64)>,
-        last_read_sectors: &HashMap<String, u64>,
-        last_write_sectors: &HashMap<String, u64>,
-        _device_to_mount: &HashMap<String, String>,
+    fn sample(
+        &mut self,
+        sysroot: &SysRoot,
+        partitions: &[Partition],
         elapsed_secs: f64,
-    ) -> Result<()> {
-        let current_stats = Self::read_disk_stats()?;
-
-        const MAX_DISK_SPEED: u64 = 10 * 1024 * 1024 * 1024;
+    ) -> Result<(), CollectorError> {
+        self.device_to_mount = Self::read_device_mount_mapping(sysroot);
+        for partition in partitions {
+            self.device_to_mount
+                .entry(partition.device.clone())
+                .or_insert_with(|| partition.mount_point.clone());
+        }
 
-        for disk in disks_info.list() {
-            if disk.kind() == DiskKind::HDD || disk.kind() == DiskKind::SSD {
-                let mount_point = disk
-                    .mount_point()
-                    .to_str()
-                    .unwrap_or(i18n::t("unknown"))
-                    .to_string();
-                let total = disk.total_space();
-                let used = total.saturating_sub(disk.available_space());
-
-                let device_name = disk.name().to_string_lossy().to_string();
-
-                const MIN_ELAPSED_SECS: f64 = 0.1;
-                let min_elapsed_secs = elapsed_secs.max(MIN_ELAPSED_SECS);
-
-                let (read_speed, write_speed) =
-                    if let Some((current_read_sectors, current_write_sectors)) =
-                        current_stats.get(&device_name)
-                    {
-                        let last_read = last_read_sectors.get(&mount_point).unwrap_or(&0);
-                        let last_write = last_write_sectors.get(&mount_point).unwrap_or(&0);
-
-                        const SECTOR_SIZE: u64 = 512;
-
-                        let read_speed = if *current_read_sectors >= *last_read {
-                            let bytes_read =
-                                (*current_read_sectors - *last_read).saturating_mul(SECTOR_SIZE);
-                            ((bytes_read as f64) / min_elapsed_secs).min(MAX_DISK_SPEED as f64)
-                                as u64
-                        } else {
-                            0
-                        };
-
-                        let write_speed = if *current_write_sectors >= *last_write {
-                            let bytes_written =
-                                (*current_write_sectors - *last_write).saturating_mul(SECTOR_SIZE);
-                            ((bytes_written as f64) / min_elapsed_secs).min(MAX_DISK_SPEED as f64)
-                                as u64
-                        } else {
-                            0
-                        };
-
-                        (read_speed, write_speed)
-                    } else {
-                        (0, 0)
-                    };
-
-                disks.push((mount_point, used, total, read_speed, write_speed));
+        let disk_stats = Self::read_disk_stats(sysroot)?;
+        Self::update_disks(
+            &disk_stats,
+            partitions,
+            &mut self.disks,
+            &self.last_read_sectors,
+            &self.last_write_sectors,
+            &self.device_to_mount,
+            elapsed_secs,
+        );
+
+        for (device_name, (read_sectors, write_sectors)) in disk_stats {
+            if let Some(mount_point) = self.device_to_mount.get(&device_name).cloned() {
+                self.last_read_sectors
+                    .insert(mount_point.clone(), read_sectors);
+                self.last_write_sectors
+                    .insert(mount_point.clone(), write_sectors);
+            } else {
+                self.last_read_sectors
+                    .insert(device_name.clone(), read_sectors);
+                self.last_write_sectors
+                    .insert(device_name.clone(), write_sectors);
             }
         }
 
         Ok(())
     }
 
+    /// 上一次的扇区计数按设备对应的挂载点查找，同一设备的绑定挂载显示相同的速率
+    #[cfg(target_os = "linux")]
+    fn update_disks(
+        current_stats: &HashMap<String, (u64, u64)>,
+        partitions: &[Partition],
+        disks: &mut Vec<DiskInfo>,
+        last_read_sectors: &HashMap<String, u64>,
+        last_write_sectors: &HashMap<String, u64>,
+        device_to_mount: &HashMap<String, String>,
+        elapsed_secs: f64,
+    ) {
+        const MIN_ELAPSED_SECS: f64 = 0.1;
+        let min_elapsed_secs = elapsed_secs.max(MIN_ELAPSED_SECS);
+
+        for partition in partitions {
+            let key = device_to_mount
+                .get(&partition.device)
+                .unwrap_or(&partition.device);
+            let (read_speed, write_speed) = match (
+                current_stats.get(&partition.device),
+                last_read_sectors.get(key),
+                last_write_sectors.get(key),
+            ) {
+                (Some((current_read, current_write)), Some(last_read), Some(last_write)) => (
+                    sectors_to_speed(*current_read, *last_read, min_elapsed_secs),
+                    sectors_to_speed(*current_write, *last_write, min_elapsed_secs),
+                ),
+                _ => (0, 0),
+            };
+
+            disks.push(DiskInfo {
+                mount_point: partition.mount_point.clone(),
+                used: partition.used,
+                total: partition.total,
+                read_speed,
+                write_speed,
+            });
+        }
+    }
+
     #[cfg(target_os = "linux")]
-    fn read_disk_stats() -> Result<HashMap<String, (u64, u64)>> {
-        let content = fs::read_to_string("/proc/diskstats")?;
+    fn read_disk_stats(sysroot: &SysRoot) -> Result<HashMap<String, (u64, u64)>, CollectorError> {
+        let path = sysroot.path("/proc/diskstats");
+        let content = fs::read_to_string(&path).map_err(|e| CollectorError::read(&path, e))?;
         let mut stats = HashMap::new();
 
         for line in content.lines() {
@@ -261,10 +209,7 @@ impl DiskData {
     }
 
     #[cfg(target_os = "windows")]
-    fn update_disks_windows(
-        disks_info: &Disks,
-        disks: &mut Vec<(String, u64, u64, u64, u64)>,
-    ) -> Result<()> {
+    fn update_disks_windows(disks_info: &Disks, disks: &mut Vec<DiskInfo>) -> Result<()> {
         use std::collections::HashMap;
         use windows::core::*;
         use wmi::{COMLibrary, WMIConnection};
@@ -285,22 +230,197 @@ impl DiskData {
         }
 
         for (mount_point, (total, used)) in disk_map {
-            disks.push((mount_point, used, total, 0, 0));
+            disks.push(DiskInfo {
+                mount_point,
+                used,
+                total,
+                read_speed: 0,
+                write_speed: 0,
+            });
         }
 
         Ok(())
     }
+}
 
-    #[cfg(target_os = "windows")]
-    fn update_windows(&mut self) -> Result<()> {
-        Self::update_disks_windows(&self.disks_info, &mut self.disks)
+impl Collector for DiskData {
+    fn name(&self) -> &'static str {
+        "disk"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        let disks_info = sampler.disks();
+        self.disks.clear();
+        self.has_warning = false;
+
+        let now = std::time::Instant::now();
+
+        #[cfg(target_os = "linux")]
+        {
+            let elapsed_secs = self.last_update_time.elapsed().as_secs_f64();
+            self.sample(sampler.sysroot(), &partitions(disks_info), elapsed_secs)?;
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            Self::update_disks_windows(disks_info, &mut self.disks)?;
+        }
+
+        self.has_warning = self
+            .disks
+            .iter()
+            .any(|disk| disk.usage_percent() > self.warning_percent);
+
+        self.last_update_time = now;
+        Ok(())
     }
 }
 
+/// sysinfo 列出的本地磁盘分区
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone)]
+struct Partition {
+    /// `/proc/diskstats` 中的设备名，不带 `/dev/` 前缀，如 `sda1`
+    device: String,
+    mount_point: String,
+    total: u64,
+    used: u64,
+}
+
+#[cfg(target_os = "linux")]
+fn partitions(disks: &Disks) -> Vec<Partition> {
+    disks
+        .list()
+        .iter()
+        .filter(|disk| disk.kind() == DiskKind::HDD || disk.kind() == DiskKind::SSD)
+        .map(|disk| {
+            let name = disk.name().to_string_lossy();
+            let total = disk.total_space();
+            Partition {
+                device: name.strip_prefix("/dev/").unwrap_or(&name).to_string(),
+                mount_point: disk
+                    .mount_point()
+                    .to_str()
+                    .unwrap_or(i18n::t("unknown"))
+                    .to_string(),
+                total,
+                used: total.saturating_sub(disk.available_space()),
+            }
+        })
+        .collect()
+}
+
+/// `/proc/diskstats` 中的扇区大小固定为 512 字节，与设备的物理扇区大小无关
+#[cfg(target_os = "linux")]
+const SECTOR_SIZE: u64 = 512;
+
+/// 读写速率上限，用于过滤计数器回绕或设备重新挂载造成的异常值
+#[cfg(target_os = "linux")]
+const MAX_DISK_SPEED: u64 = 10 * 1024 * 1024 * 1024;
+
+/// 根据两次采样之间的扇区计数差计算每秒字节数
+///
+/// 计数器回退（设备重置）时返回 0
+#[cfg(target_os = "linux")]
+fn sectors_to_speed(current_sectors: u64, last_sectors: u64, elapsed_secs: f64) -> u64 {
+    if current_sectors < last_sectors {
+        return 0;
+    }
+    let bytes = (current_sectors - last_sectors).saturating_mul(SECTOR_SIZE);
+    ((bytes as f64) / elapsed_secs).min(MAX_DISK_SPEED as f64) as u64
+}
+
 #[cfg(target_os = "windows")]
 #[derive(serde::Deserialize)]
 struct Win32_LogicalDisk {
     DeviceID: Option<String>,
     Size: Option<u64>,
     FreeSpace: Option<u64>,
-}
\ No newline at end of file
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::data::sysroot::{fixture, fixture_next};
+
+    #[test]
+    fn test_read_disk_stats_from_fixture() {
+        let stats = DiskData::read_disk_stats(&fixture()).unwrap();
+        assert_eq!(stats.get("sda"), Some(&(204800, 409600)));
+        assert_eq!(stats.get("nvme0n1p1"), Some(&(1048000, 2097000)));
+        // 字段不足的行被忽略
+        assert!(!stats.contains_key("sdb"));
+    }
+
+    #[test]
+    fn test_read_device_mount_mapping_from_fixture() {
+        let mapping = DiskData::read_device_mount_mapping(&fixture());
+        assert_eq!(mapping.len(), 2);
+        assert_eq!(mapping.get("sda1").map(String::as_str), Some("/"));
+        assert_eq!(mapping.get("nvme0n1p1").map(String::as_str), Some("/home"));
+    }
+
+    #[test]
+    fn test_missing_diskstats_is_reported() {
+        match DiskData::read_disk_stats(&SysRoot::new("/nonexistent-sysroot")) {
+            Err(CollectorError::Read { path, .. }) => assert!(path.ends_with("proc/diskstats")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rates_between_two_samples() {
+        let mut data = DiskData {
+            disks: Vec::new(),
+            last_read_sectors: HashMap::new(),
+            last_write_sectors: HashMap::new(),
+            last_update_time: std::time::Instant::now(),
+            has_warning: false,
+            warning_percent: 90.0,
+            interval: Duration::from_secs(1),
+            device_to_mount: HashMap::new(),
+        };
+        let partition = |device: &str, mount_point: &str| Partition {
+            device: device.to_string(),
+            mount_point: mount_point.to_string(),
+            total: 1000,
+            used: 400,
+        };
+        let partitions = [partition("sda1", "/"), partition("nvme0n1p1", "/home")];
+        fn rates(data: &DiskData) -> Vec<(&str, u64, u64)> {
+            data.disks
+                .iter()
+                .map(|disk| (disk.mount_point.as_str(), disk.read_speed, disk.write_speed))
+                .collect()
+        }
+
+        // 第一次采样没有上一次的计数
+        data.sample(&fixture(), &partitions, 0.0).unwrap();
+        assert_eq!(rates(&data), vec![("/", 0, 0), ("/home", 0, 0)]);
+
+        // 两秒内 sda1 读了 2048 个扇区（1 MiB），写了 4096 个扇区（2 MiB）
+        data.disks.clear();
+        data.sample(&fixture_next(), &partitions, 2.0).unwrap();
+        assert_eq!(
+            rates(&data),
+            vec![("/", 512 * 1024, 1024 * 1024), ("/home", 0, 0)]
+        );
+        assert_eq!(data.disks[0].used, 400);
+    }
+
+    #[test]
+    fn test_sectors_to_speed() {
+        // 2048 个扇区 = 1 MiB
+        assert_eq!(sectors_to_speed(4096, 2048, 1.0), 1024 * 1024);
+        assert_eq!(sectors_to_speed(4096, 2048, 2.0), 512 * 1024);
+        assert_eq!(sectors_to_speed(2048, 2048, 1.0), 0);
+        // 计数器回退
+        assert_eq!(sectors_to_speed(100, 2048, 1.0), 0);
+        // 异常的大增量被限制在上限内
+        assert_eq!(sectors_to_speed(u64::MAX, 0, 1.0), MAX_DISK_SPEED);
+    }
+}
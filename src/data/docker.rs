@@ -1,7 +1,12 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::process::Command;
+use std::time::Duration;
 
-#[derive(Debug, Clone)]
+use super::{Collector, CollectorError, Sampler};
+use crate::config::Config;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContainerInfo {
     pub id: String,
     pub name: String,
@@ -17,7 +22,7 @@ pub struct ContainerInfo {
     pub created: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ContainerState {
     Running,
     Paused,
@@ -27,40 +32,19 @@ pub enum ContainerState {
     Unknown,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerData {
     pub containers: Vec<ContainerInfo>,
+    #[serde(skip)]
+    interval: Duration,
 }
 
 impl DockerData {
-    pub fn new() -> Result<Self> {
-        let mut data = Self {
+    pub fn new(config: &Config) -> Result<Self> {
+        Ok(Self {
             containers: Vec::new(),
-        };
-        let _ = data.update();
-        Ok(data)
-    }
-
-    pub fn update(&mut self) -> Result<()> {
-        self.containers.clear();
-
-        let output = Command::new("docker")
-            .args(["ps", "--format", "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.Status}}\t{{.CreatedAt}}"])
-            .output();
-
-        if let Ok(output) = output {
-            if output.status.success() {
-                let stdout = String::from_utf8(output.stdout)?;
-
-                for line in stdout.lines() {
-                    if let Some(container) = self.parse_container_line(line) {
-                        self.containers.push(container);
-                    }
-                }
-            }
-        }
-
-        Ok(())
+            interval: Duration::from_millis(config.docker.refresh_interval_ms),
+        })
     }
 
     fn parse_container_line(&self, line: &str) -> Option<ContainerInfo> {
@@ -97,7 +81,13 @@ impl DockerData {
 
     fn get_container_stats(&self, container_id: &str) -> (f32, f64, f64, f32, f64, f64) {
         let output = Command::new("docker")
-            .args(["stats", container_id, "--no-stream", "--format", "{{.CPUPerc}}\t{{.MemUsage}}"])
+            .args([
+                "stats",
+                container_id,
+                "--no-stream",
+                "--format",
+                "{{.CPUPerc}}\t{{.MemUsage}}",
+            ])
             .output();
 
         if let Ok(output) = output {
@@ -106,10 +96,7 @@ impl DockerData {
                 let parts: Vec<&str> = stdout.split('\t').collect();
 
                 if parts.len() >= 2 {
-                    let cpu_percent = parts[0]
-                        .trim_end_matches('%')
-                        .parse::<f32>()
-                        .unwrap_or(0.0);
+                    let cpu_percent = parts[0].trim_end_matches('%').parse::<f32>().unwrap_or(0.0);
 
                     let mem_usage_str = parts[1];
                     let mem_parts: Vec<&str> = mem_usage_str.split('/').collect();
@@ -122,7 +109,14 @@ impl DockerData {
                             0.0
                         };
 
-                        return (cpu_percent, usage_mb, limit_mb, memory_percent as f32, 0.0, 0.0);
+                        return (
+                            cpu_percent,
+                            usage_mb,
+                            limit_mb,
+                            memory_percent as f32,
+                            0.0,
+                            0.0,
+                        );
                     }
                 }
             }
@@ -152,7 +146,10 @@ impl DockerData {
 fn parse_size_mb(size_str: &str) -> f64 {
     let size_str = size_str.trim();
     let (num_str, unit) = size_str.split_at(
-        size_str.chars().position(|c| c.is_alphabetic()).unwrap_or(size_str.len()),
+        size_str
+            .chars()
+            .position(|c| c.is_alphabetic())
+            .unwrap_or(size_str.len()),
     );
 
     if let Ok(num) = num_str.trim().parse::<f64>() {
@@ -169,8 +166,57 @@ fn parse_size_mb(size_str: &str) -> f64 {
     }
 }
 
+impl Collector for DockerData {
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, _sampler: &Sampler) -> Result<(), CollectorError> {
+        const COMMAND: &str = "docker ps";
+
+        self.containers.clear();
+
+        let output = Command::new("docker")
+            .args([
+                "ps",
+                "--format",
+                "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.Status}}\t{{.CreatedAt}}",
+            ])
+            .output()
+            .map_err(|e| CollectorError::Command {
+                command: COMMAND,
+                message: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(CollectorError::Command {
+                command: COMMAND,
+                message: stderr
+                    .lines()
+                    .next()
+                    .map(|line| line.trim().to_string())
+                    .unwrap_or_else(|| output.status.to_string()),
+            });
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            if let Some(container) = self.parse_container_line(line) {
+                self.containers.push(container);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl Default for DockerData {
     fn default() -> Self {
-        Self::new().expect("Failed to create default DockerData")
+        Self::new(&Config::default()).expect("Failed to create default DockerData")
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,15 @@
+use chrono::{DateTime, Local};
 use std::collections::VecDeque;
 
+use super::{
+    CpuBreakdown, CpuData, LoadData, MemoryData, NetworkData, ProcessData, BYTES_PER_GB,
+    BYTES_PER_MB, PERCENTAGE_MULTIPLIER,
+};
+
+/// 固定容量的历史数据，每个点记录采集时间
 #[derive(Debug, Clone)]
 pub struct HistoryData<T> {
-    data: VecDeque<T>,
+    data: VecDeque<(DateTime<Local>, T)>,
     max_points: usize,
 }
 
@@ -14,15 +21,21 @@ impl<T: Clone> HistoryData<T> {
         }
     }
 
-    pub fn push(&mut self, value: T) {
+    pub fn push(&mut self, timestamp: DateTime<Local>, value: T) {
         if self.data.len() >= self.max_points {
             self.data.pop_front();
         }
-        self.data.push_back(value);
+        self.data.push_back((timestamp, value));
     }
 
+    /// 按时间顺序返回所有数值
     pub fn get_all(&self) -> Vec<T> {
-        self.data.iter().cloned().collect()
+        self.data.iter().map(|(_, value)| value.clone()).collect()
+    }
+
+    /// 按时间顺序遍历带时间戳的数据点
+    pub fn iter(&self) -> impl Iterator<Item = &(DateTime<Local>, T)> {
+        self.data.iter()
     }
 }
 
@@ -30,29 +43,48 @@ impl<T: Clone> HistoryData<T> {
 pub struct CpuHistory {
     pub global_usage: HistoryData<f32>,
     pub core_usage: Vec<HistoryData<f32>>,
+    /// 各类 CPU 时间的占比，只在能读取 `/proc/stat` 时记录
+    pub breakdown: HistoryData<CpuBreakdown>,
+    pub core_breakdown: Vec<HistoryData<CpuBreakdown>>,
 }
 
 impl CpuHistory {
     pub fn new(core_count: usize, max_points: usize) -> Self {
         let mut core_usage = Vec::with_capacity(core_count);
+        let mut core_breakdown = Vec::with_capacity(core_count);
         for _ in 0..core_count {
             core_usage.push(HistoryData::new(max_points));
+            core_breakdown.push(HistoryData::new(max_points));
         }
 
         Self {
             global_usage: HistoryData::new(max_points),
             core_usage,
+            breakdown: HistoryData::new(max_points),
+            core_breakdown,
         }
     }
 
-    pub fn update(&mut self, global_usage: f32, core_usages: &[f32]) {
-        self.global_usage.push(global_usage);
+    pub fn update(&mut self, timestamp: DateTime<Local>, global_usage: f32, core_usages: &[f32]) {
+        self.global_usage.push(timestamp, global_usage);
         for (i, &usage) in core_usages.iter().enumerate() {
             if i < self.core_usage.len() {
-                self.core_usage[i].push(usage);
+                self.core_usage[i].push(timestamp, usage);
             }
         }
     }
+
+    pub fn update_breakdown(
+        &mut self,
+        timestamp: DateTime<Local>,
+        breakdown: CpuBreakdown,
+        core_breakdowns: &[CpuBreakdown],
+    ) {
+        self.breakdown.push(timestamp, breakdown);
+        for (history, &breakdown) in self.core_breakdown.iter_mut().zip(core_breakdowns) {
+            history.push(timestamp, breakdown);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -71,10 +103,16 @@ impl MemoryHistory {
         }
     }
 
-    pub fn update(&mut self, usage_percent: f32, used_gb: f32, swap_percent: f32) {
-        self.usage_percent.push(usage_percent);
-        self.used_gb.push(used_gb);
-        self.swap_percent.push(swap_percent);
+    pub fn update(
+        &mut self,
+        timestamp: DateTime<Local>,
+        usage_percent: f32,
+        used_gb: f32,
+        swap_percent: f32,
+    ) {
+        self.usage_percent.push(timestamp, usage_percent);
+        self.used_gb.push(timestamp, used_gb);
+        self.swap_percent.push(timestamp, swap_percent);
     }
 }
 
@@ -92,9 +130,113 @@ impl NetworkHistory {
         }
     }
 
-    pub fn update(&mut self, receive_speed: f32, transmit_speed: f32) {
-        self.receive_speed.push(receive_speed);
-        self.transmit_speed.push(transmit_speed);
+    pub fn update(&mut self, timestamp: DateTime<Local>, receive_speed: f32, transmit_speed: f32) {
+        self.receive_speed.push(timestamp, receive_speed);
+        self.transmit_speed.push(timestamp, transmit_speed);
+    }
+}
+
+/// 一类资源的 PSI 历史，依次为 avg10、avg60、avg300
+#[derive(Debug, Clone)]
+pub struct PressureHistory {
+    pub some: [HistoryData<f32>; 3],
+    pub full: [HistoryData<f32>; 3],
+}
+
+impl PressureHistory {
+    pub fn new(max_points: usize) -> Self {
+        Self {
+            some: std::array::from_fn(|_| HistoryData::new(max_points)),
+            full: std::array::from_fn(|_| HistoryData::new(max_points)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadHistory {
+    /// 1、5、15 分钟平均负载
+    pub load_average: [HistoryData<f32>; 3],
+    pub uptime_secs: HistoryData<u64>,
+    pub running_tasks: HistoryData<f32>,
+    pub total_tasks: HistoryData<f32>,
+    pub context_switches_per_sec: HistoryData<f32>,
+    pub interrupts_per_sec: HistoryData<f32>,
+    /// 依次为 cpu、memory、io
+    pub pressure: [PressureHistory; 3],
+}
+
+impl LoadHistory {
+    pub fn new(max_points: usize) -> Self {
+        Self {
+            load_average: std::array::from_fn(|_| HistoryData::new(max_points)),
+            uptime_secs: HistoryData::new(max_points),
+            running_tasks: HistoryData::new(max_points),
+            total_tasks: HistoryData::new(max_points),
+            context_switches_per_sec: HistoryData::new(max_points),
+            interrupts_per_sec: HistoryData::new(max_points),
+            pressure: std::array::from_fn(|_| PressureHistory::new(max_points)),
+        }
+    }
+
+    /// 读不到的数值不记录，对应的曲线只包含有数据的点
+    pub fn update(&mut self, timestamp: DateTime<Local>, load: &LoadData) {
+        for (history, &value) in self.load_average.iter_mut().zip(&load.load_average) {
+            history.push(timestamp, value as f32);
+        }
+        self.uptime_secs.push(timestamp, load.uptime_secs);
+        for (history, value) in [
+            (
+                &mut self.running_tasks,
+                load.running_tasks.map(|n| n as f32),
+            ),
+            (&mut self.total_tasks, load.total_tasks.map(|n| n as f32)),
+            (
+                &mut self.context_switches_per_sec,
+                load.context_switches_per_sec.map(|rate| rate as f32),
+            ),
+            (
+                &mut self.interrupts_per_sec,
+                load.interrupts_per_sec.map(|rate| rate as f32),
+            ),
+        ] {
+            if let Some(value) = value {
+                history.push(timestamp, value);
+            }
+        }
+        for (history, pressure) in self.pressure.iter_mut().zip(&load.pressure) {
+            let Some(pressure) = pressure else {
+                continue;
+            };
+            for (history, &value) in history.some.iter_mut().zip(&pressure.some) {
+                history.push(timestamp, value);
+            }
+            for (history, &value) in history.full.iter_mut().zip(pressure.full.iter().flatten()) {
+                history.push(timestamp, value);
+            }
+        }
+    }
+}
+
+/// 详情面板中进程的 CPU 和内存历史，切换进程时重新开始
+#[derive(Debug, Clone)]
+pub struct ProcessHistory {
+    pub pid: Option<u32>,
+    pub cpu_usage: HistoryData<f32>,
+    pub memory_mb: HistoryData<f32>,
+}
+
+impl ProcessHistory {
+    pub fn new(max_points: usize) -> Self {
+        Self {
+            pid: None,
+            cpu_usage: HistoryData::new(max_points),
+            memory_mb: HistoryData::new(max_points),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: DateTime<Local>, cpu_usage: f32, memory_mb: f32) {
+        self.cpu_usage.push(timestamp, cpu_usage);
+        self.memory_mb.push(timestamp, memory_mb);
     }
 }
 
@@ -103,19 +245,80 @@ pub struct SystemHistory {
     pub cpu: CpuHistory,
     pub memory: MemoryHistory,
     pub network: NetworkHistory,
-    #[allow(dead_code)]
+    pub load: LoadHistory,
+    pub process: ProcessHistory,
     pub max_points: usize,
 }
 
 impl SystemHistory {
-    pub fn new(core_count: usize) -> Self {
-        let max_points = super::DEFAULT_HISTORY_POINTS;
-
+    pub fn new(core_count: usize, max_points: usize) -> Self {
         Self {
             cpu: CpuHistory::new(core_count, max_points),
             memory: MemoryHistory::new(max_points),
             network: NetworkHistory::new(max_points),
+            load: LoadHistory::new(max_points),
+            process: ProcessHistory::new(max_points),
             max_points,
         }
     }
-}
\ No newline at end of file
+
+    /// 记录一次 CPU 采集结果，包括各类 CPU 时间的占比
+    pub fn record_cpu(&mut self, timestamp: DateTime<Local>, cpu: &CpuData) {
+        self.cpu
+            .update(timestamp, cpu.global_cpu_usage, &cpu.core_usages);
+        if let Some(breakdown) = cpu.breakdown {
+            self.cpu
+                .update_breakdown(timestamp, breakdown, &cpu.core_breakdowns);
+        }
+    }
+
+    /// 记录一次内存采集结果，换算为使用率和已用 GB
+    pub fn record_memory(&mut self, timestamp: DateTime<Local>, memory: &MemoryData) {
+        let memory_percent = if memory.total_memory > 0 {
+            (memory.used_memory as f32 / memory.total_memory as f32) * PERCENTAGE_MULTIPLIER
+        } else {
+            0.0
+        };
+        let used_memory_gb = memory.used_memory as f32 / BYTES_PER_GB as f32;
+        let swap_percent = if memory.total_swap > 0 {
+            (memory.used_swap as f32 / memory.total_swap as f32) * PERCENTAGE_MULTIPLIER
+        } else {
+            0.0
+        };
+        self.memory
+            .update(timestamp, memory_percent, used_memory_gb, swap_percent);
+    }
+
+    /// 记录一次网络采集结果，所有接口的速率合计为 MB/s
+    pub fn record_network(&mut self, timestamp: DateTime<Local>, network: &NetworkData) {
+        let (total_rx_speed, total_tx_speed): (f32, f32) =
+            network
+                .interfaces
+                .iter()
+                .fold((0.0, 0.0), |(rx, tx), iface| {
+                    (
+                        rx + iface.rx_speed as f32 / BYTES_PER_MB as f32,
+                        tx + iface.tx_speed as f32 / BYTES_PER_MB as f32,
+                    )
+                });
+        self.network
+            .update(timestamp, total_rx_speed, total_tx_speed);
+    }
+
+    /// 记录一次平均负载、任务数和 PSI 的采集结果
+    pub fn record_load(&mut self, timestamp: DateTime<Local>, load: &LoadData) {
+        self.load.update(timestamp, load);
+    }
+
+    /// 记录详情面板中进程的 CPU 和内存，进程变化时清空之前的记录
+    pub fn record_process(&mut self, timestamp: DateTime<Local>, process: &ProcessData) {
+        if self.process.pid != process.detail_pid {
+            self.process = ProcessHistory::new(self.max_points);
+            self.process.pid = process.detail_pid;
+        }
+        if let Some(detail) = &process.detail {
+            self.process
+                .update(timestamp, detail.cpu_usage, detail.memory_mb);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+
+use super::procfs::{self, StatCounters};
+use super::{Collector, CollectorError, Sampler, SysRoot};
+use crate::config::Config;
+
+/// `/proc/pressure` 下的资源，与 `LoadData::pressure` 的顺序一致
+pub const PRESSURE_RESOURCES: [&str; 3] = ["cpu", "memory", "io"];
+
+/// 一类资源的压力停顿信息（PSI），依次为过去 10、60、300 秒内的平均占比（%）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    /// 至少有一个任务在等待该资源的时间占比
+    pub some: [f32; 3],
+    /// 所有非空闲任务同时在等待该资源的时间占比；5.13 以前的内核中 cpu 没有这一行
+    pub full: Option<[f32; 3]>,
+}
+
+/// CPU 页中的系统概况：平均负载、运行时间、任务数、上下文切换和中断速率以及 PSI
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoadData {
+    /// 1、5、15 分钟平均负载
+    pub load_average: [f64; 3],
+    /// 逻辑核心数，平均负载持续高于核心数说明 CPU 不够用
+    pub cpu_cores: usize,
+    pub uptime_secs: u64,
+    /// 可运行的任务数，读不到 `/proc/loadavg` 时为 `None`
+    pub running_tasks: Option<u32>,
+    /// 任务总数（含线程）
+    pub total_tasks: Option<u32>,
+    /// 上次采集以来每秒的上下文切换次数，首次采集或读不到 `/proc/stat` 时为 `None`
+    pub context_switches_per_sec: Option<f64>,
+    pub interrupts_per_sec: Option<f64>,
+    /// 依次为 cpu、memory、io，内核未启用 PSI 时为 `None`
+    pub pressure: [Option<Pressure>; 3],
+    #[serde(skip)]
+    last_counters: Option<(StatCounters, Instant)>,
+    #[serde(skip)]
+    interval: Duration,
+}
+
+/// `/proc/loadavg` 的内容
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LoadAvg {
+    load_average: [f64; 3],
+    running_tasks: u32,
+    total_tasks: u32,
+}
+
+impl LoadData {
+    pub fn new(config: &Config, sampler: &Sampler) -> Result<Self> {
+        Ok(Self {
+            cpu_cores: sampler.system().cpus().len(),
+            last_counters: sampler
+                .proc_stat()
+                .and_then(procfs::parse_stat_counters)
+                .map(|counters| (counters, Instant::now())),
+            interval: Duration::from_millis(config.general.update_interval_ms),
+            ..Self::default()
+        })
+    }
+
+    /// 平均负载与核心数之比，核心数未知时为 `None`
+    pub fn load_per_core(&self, index: usize) -> Option<f64> {
+        (self.cpu_cores > 0).then(|| self.load_average[index] / self.cpu_cores as f64)
+    }
+
+    /// 运行时间，超过一天时显示天数
+    pub fn format_uptime(&self) -> String {
+        let (days, hours, minutes) = (
+            self.uptime_secs / 86_400,
+            self.uptime_secs / 3600 % 24,
+            self.uptime_secs / 60 % 60,
+        );
+        if days > 0 {
+            format!("{}d {:02}:{:02}", days, hours, minutes)
+        } else {
+            format!("{:02}:{:02}", hours, minutes)
+        }
+    }
+
+    /// 根据 `/proc/stat` 中计数器的增量更新每秒的上下文切换和中断次数
+    fn update_rates(&mut self, counters: Option<StatCounters>, now: Instant) {
+        (self.context_switches_per_sec, self.interrupts_per_sec) =
+            match (self.last_counters, counters) {
+                (Some((last, time)), Some(current)) if now > time => {
+                    let elapsed = now.duration_since(time).as_secs_f64();
+                    let rate =
+                        |current: u64, last: u64| current.saturating_sub(last) as f64 / elapsed;
+                    (
+                        Some(rate(current.context_switches, last.context_switches)),
+                        Some(rate(current.interrupts, last.interrupts)),
+                    )
+                }
+                _ => (None, None),
+            };
+        self.last_counters = counters.map(|counters| (counters, now));
+    }
+}
+
+/// 解析 `/proc/loadavg`，如 `0.52 0.61 0.70 2/345 12345`
+fn parse_loadavg(content: &str) -> Option<LoadAvg> {
+    let fields: Vec<&str> = content.split_whitespace().collect();
+    let (running, total) = fields.get(3)?.split_once('/')?;
+    Some(LoadAvg {
+        load_average: [
+            fields.first()?.parse().ok()?,
+            fields.get(1)?.parse().ok()?,
+            fields.get(2)?.parse().ok()?,
+        ],
+        running_tasks: running.parse().ok()?,
+        total_tasks: total.parse().ok()?,
+    })
+}
+
+/// 解析 `/proc/pressure/<resource>`，每行如 `some avg10=0.12 avg60=0.05 avg300=0.01 total=123`
+fn parse_pressure(content: &str) -> Option<Pressure> {
+    let averages = |kind: &str| -> Option<[f32; 3]> {
+        let line = content.lines().find(|line| line.starts_with(kind))?;
+        let field = |name: &str| {
+            line.split_whitespace()
+                .find_map(|field| field.strip_prefix(name))
+                .and_then(|value| value.parse().ok())
+        };
+        Some([field("avg10=")?, field("avg60=")?, field("avg300=")?])
+    };
+    Some(Pressure {
+        some: averages("some ")?,
+        full: averages("full "),
+    })
+}
+
+/// 解析 `/proc/uptime`，如 `350735.47 234388.90`，第一个字段为开机以来的秒数
+fn parse_uptime(content: &str) -> Option<u64> {
+    let seconds: f64 = content.split_whitespace().next()?.parse().ok()?;
+    Some(seconds as u64)
+}
+
+fn read_uptime(sysroot: &SysRoot) -> Option<u64> {
+    let content = fs::read_to_string(sysroot.path("/proc/uptime")).ok()?;
+    parse_uptime(&content)
+}
+
+fn read_pressure(sysroot: &SysRoot, resource: &str) -> Option<Pressure> {
+    let content = fs::read_to_string(sysroot.path(format!("/proc/pressure/{}", resource))).ok()?;
+    parse_pressure(&content)
+}
+
+impl Collector for LoadData {
+    fn name(&self) -> &'static str {
+        "load"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        let sysroot = sampler.sysroot();
+        // 任务数只能从 `/proc/loadavg` 读取，其他平台的平均负载由 sysinfo 提供
+        match fs::read_to_string(sysroot.path("/proc/loadavg"))
+            .ok()
+            .and_then(|content| parse_loadavg(&content))
+        {
+            Some(loadavg) => {
+                self.load_average = loadavg.load_average;
+                self.running_tasks = Some(loadavg.running_tasks);
+                self.total_tasks = Some(loadavg.total_tasks);
+            }
+            None => {
+                let load = System::load_average();
+                self.load_average = [load.one, load.five, load.fifteen];
+                self.running_tasks = None;
+                self.total_tasks = None;
+            }
+        }
+        // 与其他数据一样从 sysroot 读取，其他平台由 sysinfo 提供
+        self.uptime_secs = read_uptime(sysroot).unwrap_or_else(System::uptime);
+
+        self.update_rates(
+            sampler.proc_stat().and_then(procfs::parse_stat_counters),
+            Instant::now(),
+        );
+
+        for (pressure, resource) in self.pressure.iter_mut().zip(PRESSURE_RESOURCES) {
+            *pressure = read_pressure(sysroot, resource);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_loadavg() {
+        assert_eq!(
+            parse_loadavg("0.52 0.61 0.70 2/345 12345\n"),
+            Some(LoadAvg {
+                load_average: [0.52, 0.61, 0.70],
+                running_tasks: 2,
+                total_tasks: 345,
+            })
+        );
+        assert_eq!(parse_loadavg("0.52 0.61 0.70\n"), None);
+    }
+
+    #[test]
+    fn test_read_uptime() {
+        assert_eq!(read_uptime(&crate::data::sysroot::fixture()), Some(350_735));
+        assert_eq!(parse_uptime("12.99 3.00\n"), Some(12));
+        assert_eq!(parse_uptime(""), None);
+        assert_eq!(read_uptime(&SysRoot::new("/nonexistent-sysroot")), None);
+    }
+
+    #[test]
+    fn test_parse_pressure() {
+        let memory = "some avg10=1.50 avg60=0.75 avg300=0.10 total=123456\n\
+                      full avg10=0.50 avg60=0.25 avg300=0.00 total=65432\n";
+        assert_eq!(
+            parse_pressure(memory),
+            Some(Pressure {
+                some: [1.5, 0.75, 0.1],
+                full: Some([0.5, 0.25, 0.0]),
+            })
+        );
+        // 较旧内核的 cpu 只有 some
+        let cpu = "some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n";
+        assert_eq!(parse_pressure(cpu).and_then(|pressure| pressure.full), None);
+        assert_eq!(parse_pressure(""), None);
+    }
+
+    #[test]
+    fn test_rates_and_uptime() {
+        let mut load = LoadData::default();
+        let start = Instant::now();
+        let counters = |context_switches, interrupts| StatCounters {
+            context_switches,
+            interrupts,
+        };
+        load.update_rates(Some(counters(1000, 500)), start);
+        assert_eq!(load.context_switches_per_sec, None);
+
+        load.update_rates(Some(counters(3000, 1500)), start + Duration::from_secs(2));
+        assert_eq!(load.context_switches_per_sec, Some(1000.0));
+        assert_eq!(load.interrupts_per_sec, Some(500.0));
+
+        load.update_rates(None, start + Duration::from_secs(3));
+        assert_eq!(load.interrupts_per_sec, None);
+
+        load.uptime_secs = 3 * 86_400 + 4 * 3600 + 12 * 60 + 30;
+        assert_eq!(load.format_uptime(), "3d 04:12");
+        load.uptime_secs = 59 * 60;
+        assert_eq!(load.format_uptime(), "00:59");
+    }
+    #[test]
+    fn test_rates_from_sampled_proc_stat() {
+        use crate::data::sysroot::{fixture, fixture_next};
+
+        let config = Config::default();
+        let mut load = LoadData::new(&config, &Sampler::with_sysroot(fixture())).unwrap();
+        load.update(&Sampler::with_sysroot(fixture_next())).unwrap();
+        assert!(load.context_switches_per_sec.unwrap() > 0.0);
+        assert!(load.interrupts_per_sec.unwrap() > 0.0);
+    }
+}
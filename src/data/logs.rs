@@ -1,20 +1,17 @@
+use super::{Collector, CollectorError, Sampler};
 use crate::app::{LogCategory, LogLevel};
+use crate::config::Config;
 use anyhow::Result;
 use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
 use std::process::Command;
-use std::time::{Duration, Instant};
-
-use super::{DEFAULT_MAX_LINE_LENGTH, MAX_LOG_LINES};
-
-const MAX_LINE_LENGTH: usize = DEFAULT_MAX_LINE_LENGTH;
+use std::time::Duration;
 
 const ALLOWED_JOURNALCTL_ARGS: &[&str] =
     &["--no-pager", "-n", "--output", "--system", "-k", "-p", "-b"];
 
-const ALLOWED_JOURNALCTL_LEVELS: &[&str] =
-    &["emerg", "alert", "crit", "err", "error", "warning", "warn", "notice", "info", "debug"];
+const ALLOWED_JOURNALCTL_LEVELS: &[&str] = &[
+    "emerg", "alert", "crit", "err", "error", "warning", "warn", "notice", "info", "debug",
+];
 
 /// 验证 journalctl 参数是否安全
 fn validate_journalctl_args(args: &[&str]) -> Result<()> {
@@ -40,7 +37,7 @@ fn validate_journalctl_args(args: &[&str]) -> Result<()> {
         }
 
         if let Ok(num) = arg.parse::<u32>() {
-            if num >= 1 && num <= 10000 {
+            if (1..=10000).contains(&num) {
                 i += 1;
                 continue;
             } else {
@@ -56,38 +53,35 @@ fn validate_journalctl_args(args: &[&str]) -> Result<()> {
     Ok(())
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LogsData {
     cached_logs: HashMap<LogCategory, Vec<String>>,
     cache_valid: bool,
-    last_update: Instant,
     update_interval: Duration,
+    max_lines: usize,
+    max_line_length: usize,
     pub log_level: LogLevel,
-    #[allow(dead_code)]
-    temp_files: HashMap<LogCategory, PathBuf>,
-    #[allow(dead_code)]
-    background_processes: Vec<std::process::Child>,
-    #[allow(dead_code)]
-    app_pid: u32,
     initial_load: bool,
 }
 
 impl LogsData {
-    pub fn new() -> Self {
-        let mut data = Self {
+    pub fn new(config: &Config) -> Self {
+        let mut data = Self::empty(config);
+        let _ = data.read_logs_initial();
+        data
+    }
+
+    /// 创建不读取任何日志的实例，用于回放等没有实时日志的场景
+    pub fn empty(config: &Config) -> Self {
+        Self {
             cached_logs: HashMap::new(),
             cache_valid: false,
-            last_update: Instant::now(),
-            update_interval: Duration::from_secs(3),
+            update_interval: Duration::from_millis(config.logs.refresh_interval_ms),
+            max_lines: config.logs.max_lines,
+            max_line_length: config.logs.max_line_length,
             log_level: LogLevel::All,
-            temp_files: HashMap::new(),
-            background_processes: Vec::new(),
-            app_pid: std::process::id(),
             initial_load: true,
-        };
-
-        let _ = data.read_logs_initial();
-        data
+        }
     }
 
     pub fn toggle_log_level(&mut self) {
@@ -123,14 +117,18 @@ impl LogsData {
 
         #[cfg(not(any(target_os = "linux", target_os = "windows")))]
         {
-            let categories = [LogCategory::System, LogCategory::Kernel, LogCategory::Error, LogCategory::Boot];
+            let categories = [
+                LogCategory::System,
+                LogCategory::Kernel,
+                LogCategory::Error,
+                LogCategory::Boot,
+            ];
             for category in categories {
                 self.cached_logs.insert(category, Vec::new());
             }
         }
 
         self.cache_valid = true;
-        self.last_update = Instant::now();
         Ok(())
     }
 
@@ -157,7 +155,12 @@ impl LogsData {
 
         #[cfg(not(any(target_os = "linux", target_os = "windows")))]
         {
-            let categories = [LogCategory::System, LogCategory::Kernel, LogCategory::Error, LogCategory::Boot];
+            let categories = [
+                LogCategory::System,
+                LogCategory::Kernel,
+                LogCategory::Error,
+                LogCategory::Boot,
+            ];
             for category in categories {
                 self.cached_logs.insert(category, Vec::new());
             }
@@ -167,17 +170,6 @@ impl LogsData {
         Ok(())
     }
 
-    pub fn update(&mut self) -> Result<()> {
-        if self.last_update.elapsed() < self.update_interval {
-            return Ok(());
-        }
-
-        self.read_logs_from_files()?;
-
-        self.last_update = Instant::now();
-        Ok(())
-    }
-
     pub fn get_logs_by_category(&self, category: &LogCategory) -> Vec<String> {
         match category {
             LogCategory::All => {
@@ -204,16 +196,23 @@ impl LogsData {
         Ok(())
     }
 
-    fn truncate_line(line: &str) -> String {
-        if line.len() > MAX_LINE_LENGTH {
-            format!("{}...[截断]", &line[..MAX_LINE_LENGTH])
+    fn truncate_line(&self, line: &str) -> String {
+        if line.len() > self.max_line_length {
+            let mut end = self.max_line_length;
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            format!("{}...[截断]", &line[..end])
         } else {
             line.to_string()
         }
     }
 
     #[cfg(target_os = "linux")]
-    fn get_journalctl_logs_by_category(&self, initial: bool) -> Result<HashMap<LogCategory, Vec<String>>> {
+    fn get_journalctl_logs_by_category(
+        &self,
+        initial: bool,
+    ) -> Result<HashMap<LogCategory, Vec<String>>> {
         let mut logs_map = HashMap::new();
         let count = if initial { "100" } else { "1000" };
 
@@ -270,8 +269,8 @@ impl LogsData {
                 use std::io::{BufRead, BufReader};
                 let reader = BufReader::new(stdout);
                 for log_line in reader.lines().map_while(Result::ok) {
-                    logs.push(log_line);
-                    if logs.len() >= MAX_LOG_LINES {
+                    logs.push(self.truncate_line(&log_line));
+                    if logs.len() >= self.max_lines {
                         break;
                     }
                 }
@@ -314,7 +313,7 @@ impl LogsData {
                 };
 
                 let log_line = format!("[{}] [{}] [{}] {}", time, type_str, source, message);
-                system_logs.push(Self::truncate_line(&log_line));
+                system_logs.push(self.truncate_line(&log_line));
             }
         }
         logs_map.insert(LogCategory::System, system_logs);
@@ -339,7 +338,7 @@ impl LogsData {
                 };
 
                 let log_line = format!("[{}] [{}] [{}] {}", time, type_str, source, message);
-                app_logs.push(Self::truncate_line(&log_line));
+                app_logs.push(self.truncate_line(&log_line));
             }
         }
         logs_map.insert(LogCategory::Error, app_logs);
@@ -366,7 +365,7 @@ impl LogsData {
                 };
 
                 let log_line = format!("[{}] [{}] [{}] {}", time, type_str, source, message);
-                security_logs.push(Self::truncate_line(&log_line));
+                security_logs.push(self.truncate_line(&log_line));
             }
         }
         logs_map.insert(LogCategory::Boot, security_logs);
@@ -375,6 +374,21 @@ impl LogsData {
     }
 }
 
+impl Collector for LogsData {
+    fn name(&self) -> &'static str {
+        "logs"
+    }
+
+    fn interval(&self) -> Duration {
+        self.update_interval
+    }
+
+    fn update(&mut self, _sampler: &Sampler) -> Result<(), CollectorError> {
+        self.read_logs_from_files()?;
+        Ok(())
+    }
+}
+
 #[cfg(target_os = "windows")]
 #[derive(serde::Deserialize)]
 struct Win32_NTLogEvent {
@@ -383,18 +397,3 @@ struct Win32_NTLogEvent {
     SourceName: Option<String>,
     Message: Option<String>,
 }
-
-impl Drop for LogsData {
-    fn drop(&mut self) {
-        for child in &mut self.background_processes {
-            let _ = child.kill();
-            let _ = child.wait();
-        }
-
-        for (_, file_path) in &self.temp_files {
-            if file_path.exists() {
-                let _ = fs::remove_file(file_path);
-            }
-        }
-    }
-}
\ No newline at end of file
@@ -1,51 +1,69 @@
 use anyhow::Result;
-use sysinfo::System;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-use super::{BYTES_PER_GB, BYTES_PER_MB};
+use super::{Collector, CollectorError, Sampler, BYTES_PER_GB, BYTES_PER_MB};
+use crate::config::Config;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryData {
     pub total_memory: u64,
     pub used_memory: u64,
     pub total_swap: u64,
     pub used_swap: u64,
     pub has_warning: bool,
-    sys: System,
+    #[serde(skip)]
+    memory_warning_percent: f64,
+    #[serde(skip)]
+    swap_warning_percent: f64,
+    #[serde(skip)]
+    interval: Duration,
 }
 
 impl MemoryData {
-    pub fn new() -> Result<Self> {
-        let sys = System::new_all();
+    pub fn new(config: &Config, sampler: &Sampler) -> Result<Self> {
         let mut data = Self {
             total_memory: 0,
             used_memory: 0,
             total_swap: 0,
             used_swap: 0,
             has_warning: false,
-            sys,
+            memory_warning_percent: config.alerts.memory_percent,
+            swap_warning_percent: config.alerts.swap_percent,
+            interval: Duration::from_millis(config.general.update_interval_ms),
         };
-        data.update()?;
+        data.update(sampler)?;
         Ok(data)
     }
+}
+
+impl Collector for MemoryData {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
 
-    pub fn update(&mut self) -> Result<()> {
-        self.sys.refresh_memory();
-        self.total_memory = self.sys.total_memory();
-        self.used_memory = self.sys.used_memory();
-        self.total_swap = self.sys.total_swap();
-        self.used_swap = self.sys.used_swap();
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        let sys = sampler.system();
+        self.total_memory = sys.total_memory();
+        self.used_memory = sys.used_memory();
+        self.total_swap = sys.total_swap();
+        self.used_swap = sys.used_swap();
 
         self.has_warning = false;
         if self.total_memory > 0 {
             let memory_percent = (self.used_memory as f64 / self.total_memory as f64) * 100.0;
-            if memory_percent > 90.0 {
+            if memory_percent > self.memory_warning_percent {
                 self.has_warning = true;
             }
         }
 
         if self.total_swap > 0 {
             let swap_percent = (self.used_swap as f64 / self.total_swap as f64) * 100.0;
-            if swap_percent > 80.0 {
+            if swap_percent > self.swap_warning_percent {
                 self.has_warning = true;
             }
         }
@@ -88,4 +106,4 @@ mod tests {
         let mb_value = bytes_to_mb(1073741824);
         assert_eq!(mb_value, gb_value * 1024.0);
     }
-}
\ No newline at end of file
+}
@@ -1,45 +1,58 @@
 pub mod battery;
+pub mod collector;
 pub mod cpu;
 pub mod disk;
 pub mod docker;
 pub mod history;
+pub mod load;
 pub mod logs;
+pub mod masking;
 pub mod memory;
 pub mod network;
 pub mod process;
+pub mod process_control;
+pub mod process_filter;
+pub mod process_watch;
+pub mod procfs;
+pub mod sampler;
+pub mod sysroot;
 pub mod temperature;
 
 pub use battery::BatteryData;
-pub use cpu::CpuData;
-pub use disk::DiskData;
-pub use docker::DockerData;
+pub use collector::{Collector, CollectorError, CollectorStatus, Scheduled};
+pub use cpu::{CpuBreakdown, CpuData};
+pub use disk::{DiskData, DiskInfo};
+pub use docker::{ContainerInfo, DockerData};
 pub use history::SystemHistory;
+pub use load::LoadData;
 pub use memory::bytes_to_gb;
 pub use memory::bytes_to_mb;
 pub use memory::MemoryData;
-pub use network::NetworkData;
-pub use process::ProcessData;
-pub use temperature::TemperatureData;
+pub use network::{InterfaceInfo, NetworkData};
+pub use process::{ProcessData, ProcessInfo};
+pub use sampler::{SampleNeeds, Sampler};
+pub use sysroot::SysRoot;
+pub use temperature::{TemperatureData, TemperatureSensor};
 
 pub const BYTES_PER_MB: u64 = 1024 * 1024;
 pub const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
 
 pub const DEFAULT_HISTORY_POINTS: usize = 60;
 
-#[allow(dead_code)]
 pub const DEFAULT_MAX_PROCESSES: usize = 100;
 
-#[allow(dead_code)]
 pub const DEFAULT_MAX_LOG_LINES: usize = 500;
 
-#[allow(dead_code)]
 pub const DEFAULT_MAX_LINE_LENGTH: usize = 4096;
 
+pub const DEFAULT_LOGS_REFRESH_INTERVAL_MS: u64 = 3000;
+
+pub const DEFAULT_DOCKER_REFRESH_INTERVAL_MS: u64 = 5000;
+
 pub const DEFAULT_UPDATE_INTERVAL_MS: u64 = 1000;
 pub const EVENT_POLL_INTERVAL_MS: u64 = 100;
 
 pub const PERCENTAGE_MULTIPLIER: f32 = 100.0;
 
 pub const MAX_SEARCH_QUERY_LENGTH: usize = 100;
-pub const MAX_NETWORK_INTERFACES: usize = 50;
-pub const MAX_LOG_LINES: usize = 500;
\ No newline at end of file
+pub const DEFAULT_MAX_NETWORK_INTERFACES: usize = 50;
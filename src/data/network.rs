@@ -1,59 +1,137 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use sysinfo::Networks;
 
-use super::MAX_NETWORK_INTERFACES;
+use super::{Collector, CollectorError, Sampler};
+use crate::config::Config;
 
-#[derive(Debug)]
+/// 单个网络接口的累计流量和速率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceInfo {
+    pub name: String,
+    /// 累计接收字节数
+    pub received: u64,
+    /// 累计发送字节数
+    pub transmitted: u64,
+    /// 接收速率（字节/秒）
+    pub rx_speed: u64,
+    /// 发送速率（字节/秒）
+    pub tx_speed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkData {
-    pub interfaces: Vec<(String, u64, u64, u64, u64)>,
-    networks: Networks,
+    pub interfaces: Vec<InterfaceInfo>,
+    #[serde(skip, default = "Instant::now")]
     last_update_time: Instant,
+    #[serde(skip)]
     last_received_bytes: HashMap<String, u64>,
+    #[serde(skip)]
     last_transmitted_bytes: HashMap<String, u64>,
+    #[serde(skip)]
+    max_interfaces: usize,
+    #[serde(skip)]
+    interval: Duration,
 }
 
 impl NetworkData {
-    pub fn new() -> Result<Self> {
-        let networks = Networks::new_with_refreshed_list();
+    pub fn new(config: &Config, sampler: &Sampler) -> Result<Self> {
+        let networks = sampler.networks();
         let mut interfaces = Vec::new();
-        let last_received_bytes = HashMap::new();
-        let last_transmitted_bytes = HashMap::new();
+        let last_received_bytes = networks
+            .iter()
+            .map(|(name, data)| (name.to_string(), data.total_received()))
+            .collect();
+        let last_transmitted_bytes = networks
+            .iter()
+            .map(|(name, data)| (name.to_string(), data.total_transmitted()))
+            .collect();
+        let max_interfaces = config.network.max_interfaces;
         Self::update_networks(
-            &networks,
+            networks,
             &mut interfaces,
             &HashMap::new(),
             &HashMap::new(),
             0.0,
+            max_interfaces,
         );
         Ok(Self {
-            networks,
             interfaces,
             last_update_time: Instant::now(),
             last_received_bytes,
             last_transmitted_bytes,
+            max_interfaces,
+            interval: Duration::from_millis(config.general.update_interval_ms),
         })
     }
 
-    pub fn update(&mut self) -> Result<()> {
+    fn update_networks(
+        networks: &Networks,
+        interfaces: &mut Vec<InterfaceInfo>,
+        last_received_bytes: &HashMap<String, u64>,
+        last_transmitted_bytes: &HashMap<String, u64>,
+        elapsed_secs: f64,
+        max_interfaces: usize,
+    ) {
+        for (iface_name, data) in networks.iter() {
+            if interfaces.len() >= max_interfaces {
+                break;
+            }
+
+            let received = data.total_received();
+            let transmitted = data.total_transmitted();
+
+            // 计数器是累计值，没有上一次采样的接口（新出现或首次采集）速率记为 0
+            let min_elapsed_secs = elapsed_secs.max(0.1);
+            let speed = |current: u64, last: Option<&u64>| {
+                last.map_or(0, |last| {
+                    (current.saturating_sub(*last) as f64 / min_elapsed_secs) as u64
+                })
+            };
+            let rx_speed = speed(received, last_received_bytes.get(iface_name));
+            let tx_speed = speed(transmitted, last_transmitted_bytes.get(iface_name));
+
+            interfaces.push(InterfaceInfo {
+                name: iface_name.to_string(),
+                received,
+                transmitted,
+                rx_speed,
+                tx_speed,
+            });
+        }
+    }
+}
+
+impl Collector for NetworkData {
+    fn name(&self) -> &'static str {
+        "network"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        let networks = sampler.networks();
         self.interfaces.clear();
-        self.networks.refresh();
 
         let now = Instant::now();
         let elapsed_secs = self.last_update_time.elapsed().as_secs_f64();
 
         Self::update_networks(
-            &self.networks,
+            networks,
             &mut self.interfaces,
             &self.last_received_bytes,
             &self.last_transmitted_bytes,
             elapsed_secs,
+            self.max_interfaces,
         );
 
-        for (iface_name, data) in self.networks.iter() {
-            let received = data.received();
-            let transmitted = data.transmitted();
+        for (iface_name, data) in networks.iter() {
+            let received = data.total_received();
+            let transmitted = data.total_transmitted();
 
             self.last_received_bytes
                 .insert(iface_name.to_string(), received);
@@ -64,37 +142,4 @@ impl NetworkData {
         self.last_update_time = now;
         Ok(())
     }
-
-    fn update_networks(
-        networks: &Networks,
-        interfaces: &mut Vec<(String, u64, u64, u64, u64)>,
-        last_received_bytes: &HashMap<String, u64>,
-        last_transmitted_bytes: &HashMap<String, u64>,
-        elapsed_secs: f64,
-    ) {
-        for (iface_name, data) in networks.iter() {
-            if interfaces.len() >= MAX_NETWORK_INTERFACES {
-                break;
-            }
-
-            let received = data.received();
-            let transmitted = data.transmitted();
-
-            let last_rx = last_received_bytes.get(iface_name).unwrap_or(&0);
-            let last_tx = last_transmitted_bytes.get(iface_name).unwrap_or(&0);
-
-            let min_elapsed_secs = elapsed_secs.max(0.1);
-            let rx_speed = ((received.saturating_sub(*last_rx)) as f64 / min_elapsed_secs) as u64;
-            let tx_speed =
-                ((transmitted.saturating_sub(*last_tx)) as f64 / min_elapsed_secs) as u64;
-
-            interfaces.push((
-                iface_name.to_string(),
-                received,
-                transmitted,
-                rx_speed,
-                tx_speed,
-            ));
-        }
-    }
-}
\ No newline at end of file
+}
@@ -1,111 +1,1043 @@
 use anyhow::Result;
-use sysinfo::System;
-
-
-fn mask_sensitive_info(command: &str) -> String {
-    let password_patterns = [
-        "--password=",
-        "-p=",
-        "--pass=",
-        "--passwd=",
-        "--token=",
-        "--api-key=",
-        "--apikey=",
-        "--secret=",
-        "--key=",
-        "-P ",
-        "--db-password=",
-        "--dbpassword=",
-    ];
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
-    let mut masked = command.to_string();
+use super::masking;
+use super::process_filter::{parse_patterns, ProcessFilter, ProcessPattern};
+use super::process_watch::{WatchEvent, WatchedProcess};
+use super::procfs::{self, TcpConnection, UserNames};
+use super::{Collector, CollectorError, Sampler};
+use crate::config::Config;
 
-    for pattern in &password_patterns {
-        if let Some(idx) = masked.find(pattern) {
-            let start = idx + pattern.len();
-            let end = masked[start..]
-                .find(' ')
-                .map(|i| start + i)
-                .unwrap_or(masked.len());
+/// 保留的监视事件数量，界面按序号提示新事件
+const MAX_WATCH_EVENTS: usize = 32;
 
-            let replacement = format!("{}***{}", &masked[..start + pattern.len()], &masked[end..]);
-            masked = replacement;
-        }
-    }
-
-    masked
+/// 序列化时只输出脱敏后的命令行，避免密码出现在导出的数据中
+fn serialize_masked<S: Serializer>(command: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&masking::mask(command))
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
     pub pid: u32,
+    /// 父进程 PID，内核线程和 init 进程为 `None`
+    #[serde(default)]
+    pub parent: Option<u32>,
     pub name: String,
     pub cpu_usage: f32,
     pub memory_mb: f32,
     pub memory_percent: f32,
     pub virtual_memory_mb: f64,
     pub status: String,
+    #[serde(serialize_with = "serialize_masked")]
     pub command: String,
     pub run_time: u64,
+    #[serde(default)]
+    pub nice: i32,
+    #[serde(default)]
+    pub priority: i32,
+    /// 允许运行的 CPU 编号，读取失败时为空
+    #[serde(default)]
+    pub affinity: Vec<usize>,
+    /// 用户名，`/etc/passwd` 中没有时为用户 ID，读取失败时为空
+    #[serde(default)]
+    pub user: String,
+    #[serde(default)]
+    pub threads: u32,
+    /// 启动时间，Unix 时间戳（秒）
+    #[serde(default)]
+    pub start_time: u64,
+    /// 磁盘读写速率（字节/秒）
+    #[serde(default)]
+    pub disk_read_speed: u64,
+    #[serde(default)]
+    pub disk_write_speed: u64,
+    /// 可执行文件路径，内核线程或没有权限读取时为空
+    #[serde(default)]
+    pub exe: String,
+    /// 打开的套接字数量，没有权限读取 `/proc/<pid>/fd` 或进程表用不到该列时为 0
+    #[serde(default)]
+    pub sockets: u32,
+    /// 匹配置顶规则，不受过滤条件影响并排在最前面
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl ProcessInfo {
+    pub fn display_command(&self) -> String {
+        masking::mask(&self.command)
+    }
+
+    /// 分组视图中的分组键，读不到可执行文件时按名称分组
+    pub fn group_key(&self, view: ProcessView) -> &str {
+        match view {
+            ProcessView::GroupByExe if !self.exe.is_empty() => &self.exe,
+            _ => &self.name,
+        }
+    }
 }
 
+#[cfg(test)]
 impl ProcessInfo {
+    /// 测试用的进程，各测试只覆盖关心的字段
+    pub fn test(pid: u32, name: &str) -> Self {
+        Self {
+            pid,
+            parent: None,
+            name: name.to_string(),
+            cpu_usage: 0.0,
+            memory_mb: 10.0,
+            memory_percent: 1.0,
+            virtual_memory_mb: 0.0,
+            status: "Sleep".to_string(),
+            command: name.to_string(),
+            run_time: 0,
+            nice: 0,
+            priority: 20,
+            affinity: Vec::new(),
+            user: "root".to_string(),
+            threads: 1,
+            start_time: 0,
+            disk_read_speed: 0,
+            disk_write_speed: 0,
+            exe: String::new(),
+            sockets: 0,
+            pinned: false,
+        }
+    }
+}
+
+/// 详情面板中显示的进程信息，只为打开详情的进程采集
+///
+/// 读取失败（进程属于其他用户、非 Linux 系统）的字段为 `None`
+#[derive(Debug, Clone, Default)]
+pub struct ProcessDetails {
+    pub pid: u32,
+    pub name: String,
+    /// 原始命令行，界面中默认显示脱敏后的内容
+    pub command: String,
+    pub cwd: Option<String>,
+    pub exe: Option<String>,
+    pub uid: Option<u32>,
+    pub user: Option<String>,
+    /// 启动时间，Unix 时间戳（秒）
+    pub start_time: u64,
+    pub parent: Option<u32>,
+    pub parent_name: Option<String>,
+    pub threads: Option<u32>,
+    pub open_fds: Option<usize>,
+    pub read_bytes: Option<u64>,
+    pub write_bytes: Option<u64>,
+    /// 已脱敏的环境变量，每项为 `KEY=value`
+    pub environ: Option<Vec<String>>,
+    /// TCP 连接，按状态和本地地址排列；没有权限读取 `/proc/<pid>/fd` 时为 `None`
+    pub connections: Option<Vec<TcpConnection>>,
+    pub cpu_usage: f32,
+    pub memory_mb: f32,
+}
+
+impl ProcessDetails {
+    fn collect(sampler: &Sampler, process: &sysinfo::Process, user_names: &UserNames) -> Self {
+        let sysroot = sampler.sysroot();
+        let pid = process.pid().as_u32();
+        let status = procfs::read_status(sysroot, pid).unwrap_or_default();
+        let io = procfs::read_io(sysroot, pid);
+        let parent = process.parent();
+
+        Self {
+            pid,
+            name: process.name().to_string(),
+            command: process.cmd().join(" "),
+            cwd: procfs::read_link(sysroot, pid, "cwd"),
+            exe: procfs::read_link(sysroot, pid, "exe"),
+            uid: status.uid,
+            user: status
+                .uid
+                .and_then(|uid| user_names.get(uid).map(str::to_string)),
+            start_time: process.start_time(),
+            parent: parent.map(|parent| parent.as_u32()),
+            parent_name: parent
+                .and_then(|parent| sampler.system().process(parent))
+                .map(|parent| parent.name().to_string()),
+            threads: status.threads,
+            open_fds: procfs::count_fds(sysroot, pid),
+            read_bytes: io.map(|io| io.read_bytes),
+            write_bytes: io.map(|io| io.write_bytes),
+            environ: procfs::read_environ(sysroot, pid).map(|environ| {
+                environ
+                    .iter()
+                    .map(|entry| masking::mask_env(entry))
+                    .collect()
+            }),
+            connections: procfs::read_socket_inodes(sysroot, pid).map(|inodes| {
+                let mut table = procfs::read_tcp_connections(sysroot);
+                let mut connections: Vec<TcpConnection> = inodes
+                    .iter()
+                    .filter_map(|inode| table.remove(inode))
+                    .collect();
+                connections.sort_by(|a, b| a.state.cmp(b.state).then(a.local.cmp(&b.local)));
+                connections
+            }),
+            cpu_usage: process.cpu_usage(),
+            memory_mb: process.memory() as f32 / super::BYTES_PER_MB as f32,
+        }
+    }
+
     pub fn display_command(&self) -> String {
-        mask_sensitive_info(&self.command)
+        masking::mask(&self.command)
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ProcessSortField {
     Pid,
     Name,
+    User,
+    #[default]
     Cpu,
     Memory,
+    VirtualMemory,
+    Priority,
+    Nice,
+    Threads,
+    RunTime,
+    StartTime,
+    DiskRead,
+    DiskWrite,
+    Sockets,
     Status,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl ProcessSortField {
+    /// 第一次按该字段排序时的方向：文本按字母升序，数值从大到小
+    fn default_order(self) -> ProcessSortOrder {
+        match self {
+            ProcessSortField::Name | ProcessSortField::User | ProcessSortField::Status => {
+                ProcessSortOrder::Ascending
+            }
+            _ => ProcessSortOrder::Descending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ProcessSortOrder {
     Ascending,
+    #[default]
     Descending,
 }
 
-#[derive(Debug)]
+/// 进程表的排序字段和方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessSort {
+    pub field: ProcessSortField,
+    pub order: ProcessSortOrder,
+}
+
+impl ProcessSort {
+    /// 再次选择当前字段时反转方向，选择其他字段时使用该字段的默认方向
+    pub fn toggle(self, field: ProcessSortField) -> Self {
+        if self.field == field {
+            let order = match self.order {
+                ProcessSortOrder::Ascending => ProcessSortOrder::Descending,
+                ProcessSortOrder::Descending => ProcessSortOrder::Ascending,
+            };
+            Self { field, order }
+        } else {
+            Self {
+                field,
+                order: field.default_order(),
+            }
+        }
+    }
+}
+
+/// 进程表中可以显示的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessColumn {
+    Pid,
+    Name,
+    User,
+    Cpu,
+    MemoryPercent,
+    MemoryMb,
+    VirtualMemory,
+    Priority,
+    Nice,
+    Affinity,
+    Threads,
+    RunTime,
+    StartTime,
+    DiskRead,
+    DiskWrite,
+    Sockets,
+    Status,
+    Command,
+}
+
+impl ProcessColumn {
+    /// 列选择器中的顺序，也是表格中的显示顺序
+    pub const ALL: [ProcessColumn; 18] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Name,
+        ProcessColumn::User,
+        ProcessColumn::Cpu,
+        ProcessColumn::MemoryPercent,
+        ProcessColumn::MemoryMb,
+        ProcessColumn::VirtualMemory,
+        ProcessColumn::Priority,
+        ProcessColumn::Nice,
+        ProcessColumn::Affinity,
+        ProcessColumn::Threads,
+        ProcessColumn::RunTime,
+        ProcessColumn::StartTime,
+        ProcessColumn::DiskRead,
+        ProcessColumn::DiskWrite,
+        ProcessColumn::Sockets,
+        ProcessColumn::Status,
+        ProcessColumn::Command,
+    ];
+
+    /// 未配置 `process.columns` 时显示的列
+    pub const DEFAULT: [ProcessColumn; 10] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Name,
+        ProcessColumn::Cpu,
+        ProcessColumn::MemoryPercent,
+        ProcessColumn::MemoryMb,
+        ProcessColumn::Priority,
+        ProcessColumn::Nice,
+        ProcessColumn::Affinity,
+        ProcessColumn::Status,
+        ProcessColumn::Command,
+    ];
+
+    /// 点击表头时使用的排序字段，CPU 列表和命令行不能排序
+    pub fn sort_field(self) -> Option<ProcessSortField> {
+        match self {
+            ProcessColumn::Pid => Some(ProcessSortField::Pid),
+            ProcessColumn::Name => Some(ProcessSortField::Name),
+            ProcessColumn::User => Some(ProcessSortField::User),
+            ProcessColumn::Cpu => Some(ProcessSortField::Cpu),
+            ProcessColumn::MemoryPercent | ProcessColumn::MemoryMb => {
+                Some(ProcessSortField::Memory)
+            }
+            ProcessColumn::VirtualMemory => Some(ProcessSortField::VirtualMemory),
+            ProcessColumn::Priority => Some(ProcessSortField::Priority),
+            ProcessColumn::Nice => Some(ProcessSortField::Nice),
+            ProcessColumn::Threads => Some(ProcessSortField::Threads),
+            ProcessColumn::RunTime => Some(ProcessSortField::RunTime),
+            ProcessColumn::StartTime => Some(ProcessSortField::StartTime),
+            ProcessColumn::DiskRead => Some(ProcessSortField::DiskRead),
+            ProcessColumn::DiskWrite => Some(ProcessSortField::DiskWrite),
+            ProcessColumn::Sockets => Some(ProcessSortField::Sockets),
+            ProcessColumn::Status => Some(ProcessSortField::Status),
+            ProcessColumn::Affinity | ProcessColumn::Command => None,
+        }
+    }
+}
+
+/// 进程表的显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessView {
+    /// 按排序字段平铺
+    #[default]
+    Flat,
+    /// 子进程缩进显示在父进程之下，同级进程按排序字段排列
+    Tree,
+    /// 同名进程合并为一行，显示进程数和 CPU、内存合计
+    GroupByName,
+    /// 同一可执行文件的进程合并为一行
+    GroupByExe,
+}
+
+impl ProcessView {
+    pub fn is_grouped(self) -> bool {
+        matches!(self, ProcessView::GroupByName | ProcessView::GroupByExe)
+    }
+}
+
+/// 进程表中的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessRow {
+    /// 在 [`ProcessData::processes`] 中的下标
+    pub index: usize,
+    /// 树形视图中名称前的连线，平铺视图中为空
+    pub prefix: String,
+    pub has_children: bool,
+    pub collapsed: bool,
+    /// 树形视图中为整个子树的合计，分组行为组内合计，其他情况为进程本身
+    pub cpu_usage: f32,
+    pub memory_mb: f32,
+    pub memory_percent: f32,
+    /// 分组视图中分组行的键（名称或可执行文件路径），其他行为 `None`
+    pub group: Option<String>,
+    /// 该行代表的进程数，只有分组行大于 1
+    pub count: usize,
+    /// 线程行，`index` 为在 [`ProcessData::threads`] 中的下标
+    pub thread: bool,
+}
+
+impl ProcessRow {
+    fn new(index: usize, process: &ProcessInfo) -> Self {
+        Self {
+            index,
+            prefix: String::new(),
+            has_children: false,
+            collapsed: false,
+            cpu_usage: process.cpu_usage,
+            memory_mb: process.memory_mb,
+            memory_percent: process.memory_percent,
+            group: None,
+            count: 1,
+            thread: false,
+        }
+    }
+}
+
+/// 按显示方式排列好的进程表
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessTable {
+    pub view: ProcessView,
+    pub rows: Vec<ProcessRow>,
+}
+
+/// 生成树形视图的行，子树合计包含折叠起来的子进程
+struct TreeBuilder<'a> {
+    processes: &'a [ProcessInfo],
+    collapsed: &'a HashSet<u32>,
+    children: Vec<Vec<usize>>,
+    totals: Vec<Option<(f32, f32, f32)>>,
+    visited: Vec<bool>,
+    rows: Vec<ProcessRow>,
+}
+
+impl<'a> TreeBuilder<'a> {
+    fn build(processes: &'a [ProcessInfo], collapsed: &'a HashSet<u32>) -> Vec<ProcessRow> {
+        let index_of: HashMap<u32, usize> = processes
+            .iter()
+            .enumerate()
+            .map(|(index, p)| (p.pid, index))
+            .collect();
+
+        // 按进程表的顺序挂到父进程下，同级进程因此保持排序
+        let mut children = vec![Vec::new(); processes.len()];
+        let mut roots = Vec::new();
+        for (index, process) in processes.iter().enumerate() {
+            match process.parent.and_then(|parent| index_of.get(&parent)) {
+                Some(&parent) if parent != index => children[parent].push(index),
+                _ => roots.push(index),
+            }
+        }
+
+        let mut builder = Self {
+            processes,
+            collapsed,
+            children,
+            totals: vec![None; processes.len()],
+            visited: vec![false; processes.len()],
+            rows: Vec::with_capacity(processes.len()),
+        };
+        for index in roots {
+            builder.visit(index, "", "");
+        }
+        // PID 复用可能形成环，环上的进程作为根显示
+        for index in 0..processes.len() {
+            if !builder.visited[index] {
+                builder.visit(index, "", "");
+            }
+        }
+        builder.rows
+    }
+
+    fn total(&mut self, index: usize) -> (f32, f32, f32) {
+        if let Some(total) = self.totals[index] {
+            return total;
+        }
+        let p = &self.processes[index];
+        // 先记录自身，遇到环时不会无限递归
+        self.totals[index] = Some((p.cpu_usage, p.memory_mb, p.memory_percent));
+        let mut total = (p.cpu_usage, p.memory_mb, p.memory_percent);
+        for child in self.children[index].clone() {
+            let (cpu, memory_mb, memory_percent) = self.total(child);
+            total.0 += cpu;
+            total.1 += memory_mb;
+            total.2 += memory_percent;
+        }
+        self.totals[index] = Some(total);
+        total
+    }
+
+    /// 折叠起来的子孙进程不再作为环上的进程单独显示
+    fn hide(&mut self, index: usize) {
+        for child in self.children[index].clone() {
+            if !self.visited[child] {
+                self.visited[child] = true;
+                self.hide(child);
+            }
+        }
+    }
+
+    fn visit(&mut self, index: usize, guide: &str, connector: &str) {
+        self.visited[index] = true;
+        let (cpu_usage, memory_mb, memory_percent) = self.total(index);
+        let has_children = !self.children[index].is_empty();
+        let collapsed = has_children && self.collapsed.contains(&self.processes[index].pid);
+        self.rows.push(ProcessRow {
+            index,
+            prefix: format!("{}{}", guide, connector),
+            has_children,
+            collapsed,
+            cpu_usage,
+            memory_mb,
+            memory_percent,
+            group: None,
+            count: 1,
+            thread: false,
+        });
+        if collapsed {
+            self.hide(index);
+            return;
+        }
+
+        let guide = match connector {
+            "├─ " => format!("{}│  ", guide),
+            "└─ " => format!("{}   ", guide),
+            _ => guide.to_string(),
+        };
+        let children = self.children[index].clone();
+        for (i, child) in children.iter().enumerate() {
+            if self.visited[*child] {
+                continue;
+            }
+            let connector = if i + 1 == children.len() {
+                "└─ "
+            } else {
+                "├─ "
+            };
+            self.visit(*child, &guide, connector);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessData {
     pub processes: Vec<ProcessInfo>,
-    pub filter: String,
-    pub sort_field: ProcessSortField,
-    pub sort_order: ProcessSortOrder,
+    #[serde(skip)]
+    pub filter: ProcessFilter,
+    #[serde(skip)]
+    pub sort: ProcessSort,
+    #[serde(skip)]
+    pub view: ProcessView,
+    #[serde(skip)]
     pub max_processes: usize,
-    system: System,
+    /// 需要采集详情的进程
+    #[serde(skip)]
+    pub detail_pid: Option<u32>,
+    /// `detail_pid` 的详情，进程已退出时为 `None`
+    #[serde(skip)]
+    pub detail: Option<ProcessDetails>,
+    /// 置顶规则
+    #[serde(skip)]
+    pub pins: Vec<ProcessPattern>,
+    /// 监视中的进程，每次刷新都在完整的进程列表上更新
+    #[serde(skip)]
+    pub watches: Vec<WatchedProcess>,
+    /// 最近的监视事件，最多保留 [`MAX_WATCH_EVENTS`] 条
+    #[serde(skip)]
+    pub watch_events: Vec<WatchEvent>,
+    #[serde(skip)]
+    next_watch_event: u64,
+    /// 最近一次处理的进程采样时间；调整过滤或排序时不是新的采样，不重复记录监视历史和线程 CPU 时间
+    #[serde(skip)]
+    watched_at: Option<Instant>,
+    #[serde(skip)]
+    history_points: usize,
+    /// 在进程下列出各个线程
+    #[serde(skip)]
+    pub show_threads: bool,
+    /// 线程行，每个多线程进程的线程按排序字段排列，`parent` 为所属进程
+    #[serde(skip)]
+    pub threads: Vec<ProcessInfo>,
+    /// 上一次采样时各线程的 CPU 时间（时钟滴答）和算出的 CPU 占用
+    #[serde(skip)]
+    thread_samples: HashMap<u32, (u64, f32)>,
+    #[serde(skip)]
+    user_names: UserNames,
+    /// 进程表显示的列，决定每次刷新需要从 `/proc` 补充读取哪些数据
+    #[serde(skip)]
+    pub columns: Vec<ProcessColumn>,
+    #[serde(skip)]
+    interval: Duration,
 }
 
 impl ProcessData {
-    pub fn new() -> Result<Self> {
-        let mut system = System::new_all();
-        system.refresh_processes();
-
+    pub fn new(config: &Config, sampler: &Sampler) -> Result<Self> {
         let mut data = Self {
             processes: Vec::new(),
-            filter: String::new(),
-            sort_field: ProcessSortField::Cpu,
-            sort_order: ProcessSortOrder::Descending,
-            max_processes: 100,
-            system,
+            filter: ProcessFilter::default(),
+            sort: ProcessSort::default(),
+            view: ProcessView::default(),
+            max_processes: config.process.max_processes,
+            detail_pid: None,
+            detail: None,
+            pins: Vec::new(),
+            watches: Vec::new(),
+            watch_events: Vec::new(),
+            next_watch_event: 1,
+            watched_at: None,
+            history_points: config.general.history_points,
+            show_threads: false,
+            threads: Vec::new(),
+            thread_samples: HashMap::new(),
+            user_names: UserNames::default(),
+            columns: config.process.columns.clone(),
+            interval: Duration::from_millis(config.general.update_interval_ms),
         };
+        data.set_pins(parse_patterns(&config.process.pinned));
+        data.set_watches(parse_patterns(&config.process.watched));
 
-        data.update()?;
+        data.update(sampler)?;
         Ok(data)
     }
 
-    pub fn update(&mut self) -> Result<()> {
-        self.system.refresh_processes();
+    /// 按排序字段比较两个进程，值相同时按 PID 升序
+    ///
+    /// 固定的次序使相同取值的进程在每次刷新后保持原来的相对位置，不会来回跳动
+    fn compare(
+        field: ProcessSortField,
+        order: ProcessSortOrder,
+        a: &ProcessInfo,
+        b: &ProcessInfo,
+    ) -> Ordering {
+        let ordering = match field {
+            ProcessSortField::Pid => a.pid.cmp(&b.pid),
+            ProcessSortField::Name => a.name.cmp(&b.name),
+            ProcessSortField::User => a.user.cmp(&b.user),
+            ProcessSortField::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            ProcessSortField::Memory => a.memory_mb.total_cmp(&b.memory_mb),
+            ProcessSortField::VirtualMemory => a.virtual_memory_mb.total_cmp(&b.virtual_memory_mb),
+            ProcessSortField::Priority => a.priority.cmp(&b.priority),
+            ProcessSortField::Nice => a.nice.cmp(&b.nice),
+            ProcessSortField::Threads => a.threads.cmp(&b.threads),
+            ProcessSortField::RunTime => a.run_time.cmp(&b.run_time),
+            ProcessSortField::StartTime => a.start_time.cmp(&b.start_time),
+            ProcessSortField::DiskRead => a.disk_read_speed.cmp(&b.disk_read_speed),
+            ProcessSortField::DiskWrite => a.disk_write_speed.cmp(&b.disk_write_speed),
+            ProcessSortField::Sockets => a.sockets.cmp(&b.sockets),
+            ProcessSortField::Status => a.status.cmp(&b.status),
+        };
+        let ordering = match order {
+            ProcessSortOrder::Ascending => ordering,
+            ProcessSortOrder::Descending => ordering.reverse(),
+        };
+        ordering.then_with(|| a.pid.cmp(&b.pid))
+    }
+
+    /// 按当前排序方式排列进程列表，置顶的进程排在最前面
+    pub fn sort_processes(&mut self) {
+        let ProcessSort { field, order } = self.sort;
+        self.processes.sort_unstable_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then_with(|| Self::compare(field, order, a, b))
+        });
+    }
+
+    /// 对采集到的完整进程列表依次过滤、排序，再截取前 `max_processes` 个
+    ///
+    /// 必须先排序后截取，否则留下的只是采集顺序中的任意一段，而不是占用最高的进程
+    fn arrange(&mut self) {
+        self.apply_filter();
+        self.sort_processes();
+        // 树形视图和分组视图需要完整的进程列表才能计算合计
+        if self.view == ProcessView::Flat {
+            self.processes.truncate(self.max_processes);
+        }
+    }
+
+    pub fn set_filter(&mut self, filter: ProcessFilter) {
+        self.filter = filter;
+    }
+
+    pub fn set_view(&mut self, view: ProcessView) {
+        self.view = view;
+    }
+
+    pub fn set_detail_pid(&mut self, pid: Option<u32>) {
+        self.detail_pid = pid;
+        self.detail = None;
+    }
+
+    pub fn set_show_threads(&mut self, show: bool) {
+        self.show_threads = show;
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<ProcessColumn>) {
+        self.columns = columns;
+    }
+
+    /// 是否用到某一列的数据：显示该列、按该列排序或过滤条件引用了该字段
+    fn uses(&self, column: ProcessColumn) -> bool {
+        self.columns.contains(&column)
+            || column.sort_field() == Some(self.sort.field)
+            || self.filter.uses(column)
+    }
+
+    pub fn set_pins(&mut self, pins: Vec<ProcessPattern>) {
+        self.pins = pins;
+    }
+
+    /// 更新监视规则，保留仍在列表中的规则已经记录的历史
+    pub fn set_watches(&mut self, patterns: Vec<ProcessPattern>) {
+        let mut previous = std::mem::take(&mut self.watches);
+        self.watches = patterns
+            .into_iter()
+            .map(
+                |pattern| match previous.iter().position(|w| w.pattern == pattern) {
+                    Some(index) => previous.swap_remove(index),
+                    None => WatchedProcess::new(pattern, self.history_points),
+                },
+            )
+            .collect();
+    }
+
+    /// 用完整的进程列表更新监视中的进程，状态变化记为事件
+    fn update_watches(&mut self, timestamp: DateTime<Local>) {
+        for watch in &mut self.watches {
+            if let Some(kind) = watch.update(&self.processes, timestamp) {
+                self.watch_events.push(WatchEvent {
+                    id: self.next_watch_event,
+                    name: watch.name.clone(),
+                    kind,
+                });
+                self.next_watch_event += 1;
+            }
+        }
+        let excess = self.watch_events.len().saturating_sub(MAX_WATCH_EVENTS);
+        self.watch_events.drain(..excess);
+    }
+
+    /// 按当前过滤条件筛选进程列表，置顶的进程总是保留
+    ///
+    /// 树形视图下同时保留匹配进程的所有祖先，使其仍挂在原来的位置
+    pub fn apply_filter(&mut self) {
+        for process in &mut self.processes {
+            process.pinned = self.pins.iter().any(|pin| pin.matches(process));
+        }
+        if self.filter.is_empty() {
+            return;
+        }
+        let mut keep: HashSet<u32> = self
+            .processes
+            .iter()
+            .filter(|p| p.pinned || self.filter.matches(p))
+            .map(|p| p.pid)
+            .collect();
+
+        if self.view == ProcessView::Tree {
+            let parents: HashMap<u32, u32> = self
+                .processes
+                .iter()
+                .filter_map(|p| p.parent.map(|parent| (p.pid, parent)))
+                .collect();
+            for pid in keep.clone() {
+                let mut current = pid;
+                while let Some(&parent) = parents.get(&current) {
+                    // 已在集合中的祖先，其上层也已经（或将会）加入
+                    if !keep.insert(parent) {
+                        break;
+                    }
+                    current = parent;
+                }
+            }
+        }
+
+        self.processes.retain(|p| keep.contains(&p.pid));
+    }
+
+    /// 按显示方式生成进程表
+    ///
+    /// `collapsed` 中的进程在树形视图中折叠其子树，`expanded_groups` 中的分组在分组视图中展开
+    pub fn table(
+        &self,
+        view: ProcessView,
+        collapsed: &HashSet<u32>,
+        expanded_groups: &HashSet<String>,
+    ) -> ProcessTable {
+        let rows = match view {
+            ProcessView::Flat => self
+                .processes
+                .iter()
+                .enumerate()
+                .map(|(index, p)| ProcessRow::new(index, p))
+                .collect(),
+            ProcessView::Tree => TreeBuilder::build(&self.processes, collapsed),
+            ProcessView::GroupByName | ProcessView::GroupByExe => {
+                self.group_rows(view, expanded_groups)
+            }
+        };
+        let rows = if self.threads.is_empty() {
+            rows
+        } else {
+            self.insert_thread_rows(rows)
+        };
+        ProcessTable { view, rows }
+    }
+
+    /// 行对应的进程，线程行为线程本身
+    pub fn row_process(&self, row: &ProcessRow) -> &ProcessInfo {
+        if row.thread {
+            &self.threads[row.index]
+        } else {
+            &self.processes[row.index]
+        }
+    }
+
+    /// 在每个进程行之后插入它的线程，分组行不展开线程
+    fn insert_thread_rows(&self, rows: Vec<ProcessRow>) -> Vec<ProcessRow> {
+        let mut threads: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (index, thread) in self.threads.iter().enumerate() {
+            if let Some(owner) = thread.parent {
+                threads.entry(owner).or_default().push(index);
+            }
+        }
+
+        let mut result = Vec::with_capacity(rows.len() + self.threads.len());
+        for row in rows {
+            let owned = match &row.group {
+                Some(_) => None,
+                None => threads.get(&self.processes[row.index].pid),
+            };
+            let Some(owned) = owned else {
+                result.push(row);
+                continue;
+            };
+            // 线程缩进到子进程连线的位置之后
+            let guide = match row.prefix.strip_suffix("├─ ") {
+                Some(guide) => format!("{}│  ", guide),
+                None => match row.prefix.strip_suffix("└─ ") {
+                    Some(guide) => format!("{}   ", guide),
+                    None => row.prefix.clone(),
+                },
+            };
+            result.push(row);
+            result.extend(owned.iter().map(|&index| ProcessRow {
+                prefix: format!("{}  ↳ ", guide),
+                thread: true,
+                ..ProcessRow::new(index, &self.threads[index])
+            }));
+        }
+        result
+    }
+
+    /// 读取列表中多线程进程的各个线程
+    ///
+    /// CPU 占用按两次进程采样之间的 CPU 时间计算；`sampled` 为 `false` 时不是新的采样，
+    /// 沿用上一次的结果
+    fn update_threads(&mut self, sampler: &Sampler, sampled: bool) {
+        self.threads.clear();
+        if !self.show_threads {
+            self.thread_samples.clear();
+            return;
+        }
+        let ticks_per_second = procfs::clock_ticks_per_second() as f32;
+        let elapsed_secs = sampler
+            .process_interval()
+            .map(|interval| interval.as_secs_f32().max(0.1));
+
+        let mut samples = HashMap::new();
+        for process in &self.processes {
+            if process.threads < 2 {
+                continue;
+            }
+            for tid in procfs::read_task_ids(sampler.sysroot(), process.pid) {
+                let Some(stat) = procfs::read_task_stat(sampler.sysroot(), process.pid, tid) else {
+                    continue;
+                };
+                let sample = match self.thread_samples.get(&tid) {
+                    Some(&previous) if !sampled => previous,
+                    Some(&(ticks, _)) => {
+                        let seconds =
+                            stat.cpu_ticks.saturating_sub(ticks) as f32 / ticks_per_second;
+                        let usage = elapsed_secs.map_or(0.0, |elapsed| {
+                            seconds / elapsed * super::PERCENTAGE_MULTIPLIER
+                        });
+                        (stat.cpu_ticks, usage)
+                    }
+                    None => (stat.cpu_ticks, 0.0),
+                };
+                samples.insert(tid, sample);
+                // 内存、磁盘和套接字等属于整个进程，线程行不显示
+                self.threads.push(ProcessInfo {
+                    pid: tid,
+                    parent: Some(process.pid),
+                    name: stat.name,
+                    cpu_usage: sample.1,
+                    memory_mb: 0.0,
+                    memory_percent: 0.0,
+                    virtual_memory_mb: 0.0,
+                    status: format!("{:?}", sysinfo::ProcessStatus::from(stat.state)),
+                    nice: stat.nice,
+                    priority: stat.priority,
+                    affinity: Vec::new(),
+                    threads: 1,
+                    disk_read_speed: 0,
+                    disk_write_speed: 0,
+                    sockets: 0,
+                    pinned: false,
+                    ..process.clone()
+                });
+            }
+        }
+        self.thread_samples = samples;
+
+        let ProcessSort { field, order } = self.sort;
+        self.threads
+            .sort_by(|a, b| Self::compare(field, order, a, b));
+    }
+
+    /// 分组视图的行：分组按合计值排序，展开的分组下列出组内进程
+    ///
+    /// 只有一个进程的分组直接显示为普通行
+    fn group_rows(&self, view: ProcessView, expanded: &HashSet<String>) -> Vec<ProcessRow> {
+        let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for (index, process) in self.processes.iter().enumerate() {
+            let key = process.group_key(view);
+            match positions.get(key) {
+                Some(&position) => groups[position].1.push(index),
+                None => {
+                    positions.insert(key, groups.len());
+                    groups.push((key, vec![index]));
+                }
+            }
+        }
+
+        // 用组内合计代替第一个进程的取值参与排序，其余字段沿用第一个（排序最靠前的）进程
+        let totals: Vec<ProcessInfo> = groups
+            .iter()
+            .map(|(key, members)| {
+                let mut total = self.processes[members[0]].clone();
+                total.name = key.to_string();
+                for &index in &members[1..] {
+                    let p = &self.processes[index];
+                    total.cpu_usage += p.cpu_usage;
+                    total.memory_mb += p.memory_mb;
+                    total.memory_percent += p.memory_percent;
+                    total.virtual_memory_mb += p.virtual_memory_mb;
+                    total.threads += p.threads;
+                    total.disk_read_speed += p.disk_read_speed;
+                    total.disk_write_speed += p.disk_write_speed;
+                    total.sockets += p.sockets;
+                }
+                total
+            })
+            .collect();
+        let mut order: Vec<usize> = (0..groups.len()).collect();
+        let ProcessSort {
+            field,
+            order: direction,
+        } = self.sort;
+        order.sort_by(|&a, &b| Self::compare(field, direction, &totals[a], &totals[b]));
+
+        let mut rows = Vec::with_capacity(self.processes.len());
+        for position in order {
+            let (key, members) = &groups[position];
+            if members.len() == 1 {
+                rows.push(ProcessRow::new(members[0], &self.processes[members[0]]));
+                continue;
+            }
+            let total = &totals[position];
+            let collapsed = !expanded.contains(*key);
+            rows.push(ProcessRow {
+                index: members[0],
+                prefix: String::new(),
+                has_children: true,
+                collapsed,
+                cpu_usage: total.cpu_usage,
+                memory_mb: total.memory_mb,
+                memory_percent: total.memory_percent,
+                group: Some(key.to_string()),
+                count: members.len(),
+                thread: false,
+            });
+            if !collapsed {
+                rows.extend(members.iter().map(|&index| ProcessRow {
+                    prefix: "   ".to_string(),
+                    ..ProcessRow::new(index, &self.processes[index])
+                }));
+            }
+        }
+        rows
+    }
+
+    pub fn set_sort(&mut self, sort: ProcessSort) {
+        self.sort = sort;
+    }
+
+    pub fn get_sort_indicator(&self, field: ProcessSortField) -> &'static str {
+        if self.sort.field != field {
+            return "";
+        }
+        match self.sort.order {
+            ProcessSortOrder::Ascending => " ↑",
+            ProcessSortOrder::Descending => " ↓",
+        }
+    }
+}
+
+impl Collector for ProcessData {
+    fn name(&self) -> &'static str {
+        "process"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        let system = sampler.system();
 
         self.processes.clear();
 
-        let total_memory = self.system.total_memory();
+        // 逐个进程读取 /proc 的开销较大，只读取用到的文件；列出线程时需要线程数
+        let read_stat = self.uses(ProcessColumn::Priority) || self.uses(ProcessColumn::Nice);
+        let read_status = self.show_threads
+            || [
+                ProcessColumn::User,
+                ProcessColumn::Affinity,
+                ProcessColumn::Threads,
+            ]
+            .into_iter()
+            .any(|column| self.uses(column));
+        // 统计套接字要读取 /proc/<pid>/fd 下的每个链接，是其中开销最大的
+        let count_sockets = self.uses(ProcessColumn::Sockets);
 
-        for (pid, process) in self.system.processes() {
+        if read_status || self.detail_pid.is_some() {
+            self.user_names.refresh(sampler.sysroot());
+        }
+        self.detail = self
+            .detail_pid
+            .and_then(|pid| system.process(sysinfo::Pid::from_u32(pid)))
+            .map(|process| ProcessDetails::collect(sampler, process, &self.user_names));
+
+        let total_memory = system.total_memory();
+        // sysinfo 给出的是两次刷新之间的读写量，第一次刷新时没有间隔，速率记为 0
+        let elapsed_secs = sampler
+            .process_interval()
+            .map(|interval| interval.as_secs_f64().max(0.1));
+        let speed = |bytes: u64| elapsed_secs.map_or(0, |secs| (bytes as f64 / secs) as u64);
+
+        for (pid, process) in system.processes() {
+            // sysinfo 把线程也列为进程，线程改为在所属进程下列出
+            if process.thread_kind() == Some(sysinfo::ThreadKind::Userland) {
+                continue;
+            }
             let memory_mb = process.memory() as f32 / super::BYTES_PER_MB as f32;
             let memory_percent = if total_memory > 0 {
                 (process.memory() as f32 / total_memory as f32) * super::PERCENTAGE_MULTIPLIER
@@ -115,8 +1047,19 @@ impl ProcessData {
 
             let virtual_memory_mb = process.virtual_memory() as f64 / super::BYTES_PER_MB as f64;
 
+            // sysinfo 不提供调度信息，从 /proc 补充
+            let stat = read_stat
+                .then(|| procfs::read_stat(sampler.sysroot(), pid.as_u32()))
+                .flatten();
+            let status = read_status
+                .then(|| procfs::read_status(sampler.sysroot(), pid.as_u32()))
+                .flatten()
+                .unwrap_or_default();
+            let disk_usage = process.disk_usage();
+
             let process_info = ProcessInfo {
                 pid: pid.as_u32(),
+                parent: process.parent().map(|parent| parent.as_u32()),
                 name: process.name().to_string(),
                 cpu_usage: process.cpu_usage(),
                 memory_mb,
@@ -125,108 +1068,397 @@ impl ProcessData {
                 status: format!("{:?}", process.status()),
                 command: process.cmd().join(" "),
                 run_time: process.run_time(),
+                nice: stat.as_ref().map_or(0, |stat| stat.nice),
+                priority: stat.as_ref().map_or(0, |stat| stat.priority),
+                affinity: status.cpus_allowed.unwrap_or_default(),
+                user: status.uid.map_or_else(String::new, |uid| {
+                    self.user_names
+                        .get(uid)
+                        .map_or_else(|| uid.to_string(), str::to_string)
+                }),
+                threads: status.threads.unwrap_or(1),
+                start_time: process.start_time(),
+                disk_read_speed: speed(disk_usage.read_bytes),
+                disk_write_speed: speed(disk_usage.written_bytes),
+                exe: process
+                    .exe()
+                    .map_or_else(String::new, |exe| exe.display().to_string()),
+                sockets: if count_sockets {
+                    procfs::read_socket_inodes(sampler.sysroot(), pid.as_u32())
+                        .map_or(0, |inodes| inodes.len() as u32)
+                } else {
+                    0
+                },
+                pinned: false,
             };
 
             self.processes.push(process_info);
         }
 
-        if !self.filter.is_empty() {
-            let filter_lower = self.filter.to_lowercase();
-            self.processes.retain(|p| {
-                p.name.to_lowercase().contains(&filter_lower)
-                    || p.command.to_lowercase().contains(&filter_lower)
-                    || p.pid.to_string().contains(&filter_lower)
-            });
+        let sampled = sampler.processes_refreshed_at() != self.watched_at;
+        if sampled {
+            self.watched_at = sampler.processes_refreshed_at();
+            self.update_watches(Local::now());
         }
+        self.arrange();
+        self.update_threads(sampler, sampled);
 
-        if self.processes.len() > self.max_processes {
-            self.processes.truncate(self.max_processes);
-        }
+        Ok(())
+    }
+}
 
-        self.sort_processes();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(())
+    fn process(pid: u32, parent: Option<u32>, name: &str, cpu_usage: f32) -> ProcessInfo {
+        ProcessInfo {
+            parent,
+            cpu_usage,
+            ..ProcessInfo::test(pid, name)
+        }
     }
 
-    fn sort_processes(&mut self) {
-        match self.sort_field {
-            ProcessSortField::Pid => {
-                if self.sort_order == ProcessSortOrder::Ascending {
-                    self.processes.sort_by(|a, b| a.pid.cmp(&b.pid));
-                } else {
-                    self.processes.sort_by(|a, b| b.pid.cmp(&a.pid));
-                }
-            }
-            ProcessSortField::Name => {
-                if self.sort_order == ProcessSortOrder::Ascending {
-                    self.processes.sort_by(|a, b| a.name.cmp(&b.name));
-                } else {
-                    self.processes.sort_by(|a, b| b.name.cmp(&a.name));
-                }
-            }
-            ProcessSortField::Cpu => {
-                if self.sort_order == ProcessSortOrder::Ascending {
-                    self.processes.sort_by(|a, b| {
-                        a.cpu_usage
-                            .partial_cmp(&b.cpu_usage)
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    });
-                } else {
-                    self.processes.sort_by(|a, b| {
-                        b.cpu_usage
-                            .partial_cmp(&a.cpu_usage)
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    });
-                }
-            }
-            ProcessSortField::Memory => {
-                if self.sort_order == ProcessSortOrder::Ascending {
-                    self.processes.sort_by(|a, b| {
-                        a.memory_mb
-                            .partial_cmp(&b.memory_mb)
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    });
-                } else {
-                    self.processes.sort_by(|a, b| {
-                        b.memory_mb
-                            .partial_cmp(&a.memory_mb)
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    });
-                }
-            }
-            ProcessSortField::Status => {
-                if self.sort_order == ProcessSortOrder::Ascending {
-                    self.processes.sort_by(|a, b| a.status.cmp(&b.status));
-                } else {
-                    self.processes.sort_by(|a, b| b.status.cmp(&a.status));
-                }
-            }
+    /// init ─┬─ supervisor ─┬─ worker-a
+    ///       │              └─ worker-b
+    ///       └─ sshd
+    fn data() -> ProcessData {
+        ProcessData {
+            processes: vec![
+                process(1, None, "init", 0.5),
+                process(10, Some(1), "supervisor", 1.0),
+                process(11, Some(10), "worker-a", 20.0),
+                process(12, Some(10), "worker-b", 30.0),
+                process(20, Some(1), "sshd", 0.0),
+            ],
+            filter: ProcessFilter::default(),
+            sort: ProcessSort::default(),
+            view: ProcessView::Tree,
+            max_processes: 100,
+            detail_pid: None,
+            detail: None,
+            pins: Vec::new(),
+            watches: Vec::new(),
+            watch_events: Vec::new(),
+            next_watch_event: 1,
+            watched_at: None,
+            history_points: 10,
+            show_threads: false,
+            threads: Vec::new(),
+            thread_samples: HashMap::new(),
+            user_names: UserNames::default(),
+            columns: ProcessColumn::DEFAULT.to_vec(),
+            interval: Duration::from_secs(1),
         }
     }
 
-    pub fn set_filter(&mut self, filter: String) {
-        self.filter = filter;
+    fn labels(table: &ProcessTable, data: &ProcessData) -> Vec<String> {
+        table
+            .rows
+            .iter()
+            .map(|row| format!("{}{}", row.prefix, data.row_process(row).name))
+            .collect()
     }
 
-    pub fn toggle_sort(&mut self, field: ProcessSortField) {
-        if self.sort_field == field {
-            self.sort_order = match self.sort_order {
-                ProcessSortOrder::Ascending => ProcessSortOrder::Descending,
-                ProcessSortOrder::Descending => ProcessSortOrder::Ascending,
-            };
-        } else {
-            self.sort_field = field;
-            self.sort_order = ProcessSortOrder::Descending;
-        }
+    #[test]
+    fn test_tree_rows_have_guides_and_totals() {
+        let data = data();
+        let table = data.table(ProcessView::Tree, &HashSet::new(), &HashSet::new());
+        assert_eq!(
+            labels(&table, &data),
+            vec![
+                "init",
+                "├─ supervisor",
+                "│  ├─ worker-a",
+                "│  └─ worker-b",
+                "└─ sshd",
+            ]
+        );
+        assert_eq!(table.rows[1].cpu_usage, 51.0);
+        assert_eq!(table.rows[1].memory_mb, 30.0);
+        assert_eq!(table.rows[0].cpu_usage, 51.5);
+        assert!(table.rows[1].has_children && !table.rows[4].has_children);
+
+        let flat = data.table(ProcessView::Flat, &HashSet::new(), &HashSet::new());
+        assert_eq!(flat.rows.len(), 5);
+        assert_eq!(flat.rows[1].cpu_usage, 1.0);
     }
 
-    pub fn get_sort_indicator(&self, field: ProcessSortField) -> &'static str {
-        if self.sort_field != field {
-            return "";
+    #[test]
+    fn test_collapsed_subtree_keeps_totals() {
+        let data = data();
+        let table = data.table(ProcessView::Tree, &HashSet::from([10]), &HashSet::new());
+        assert_eq!(
+            labels(&table, &data),
+            vec!["init", "├─ supervisor", "└─ sshd"]
+        );
+        assert!(table.rows[1].collapsed);
+        assert_eq!(table.rows[1].cpu_usage, 51.0);
+    }
+
+    #[test]
+    fn test_thread_rows_follow_their_process() {
+        let mut data = data();
+        data.threads = vec![
+            process(10, Some(10), "supervisor", 0.5),
+            process(13, Some(10), "io-thread", 2.0),
+        ];
+        let table = data.table(ProcessView::Tree, &HashSet::new(), &HashSet::new());
+        assert_eq!(
+            labels(&table, &data)[1..5],
+            [
+                "├─ supervisor",
+                "│    ↳ supervisor",
+                "│    ↳ io-thread",
+                "│  ├─ worker-a",
+            ]
+        );
+        let thread = &table.rows[3];
+        assert!(thread.thread && !table.rows[1].thread);
+        assert_eq!((data.row_process(thread).pid, thread.cpu_usage), (13, 2.0));
+        // 子树合计不包含线程，线程的 CPU 已经计入所属进程
+        assert_eq!(table.rows[1].cpu_usage, 51.0);
+
+        let flat = data.table(ProcessView::Flat, &HashSet::new(), &HashSet::new());
+        assert_eq!(
+            labels(&flat, &data)[..4],
+            ["init", "supervisor", "  ↳ supervisor", "  ↳ io-thread"]
+        );
+    }
+
+    #[test]
+    fn test_tree_filter_keeps_ancestors() {
+        let mut data = data();
+        data.set_filter(ProcessFilter::parse("worker-b").unwrap());
+        data.apply_filter();
+        let table = data.table(ProcessView::Tree, &HashSet::new(), &HashSet::new());
+        assert_eq!(
+            labels(&table, &data),
+            vec!["init", "└─ supervisor", "   └─ worker-b"]
+        );
+
+        let mut flat = self::data();
+        flat.set_view(ProcessView::Flat);
+        flat.set_filter(ProcessFilter::parse("worker-b").unwrap());
+        flat.apply_filter();
+        assert_eq!(flat.processes.len(), 1);
+    }
+
+    /// 以固定步长打乱的顺序生成进程，模拟 sysinfo 按哈希表顺序返回
+    fn shuffled(count: u32) -> Vec<ProcessInfo> {
+        (0..count)
+            .map(|i| {
+                let pid = (i * 7919) % count + 1;
+                let mut p = process(pid, None, &format!("p{}", pid), (pid % 97) as f32);
+                p.memory_mb = ((pid * 31) % 101) as f32;
+                p
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_top_consumers_survive_truncation() {
+        for field in [ProcessSortField::Cpu, ProcessSortField::Memory] {
+            let mut data = data();
+            data.view = ProcessView::Flat;
+            data.max_processes = 10;
+            data.sort.field = field;
+            data.processes = shuffled(500);
+
+            let mut expected = data.processes.clone();
+            expected
+                .sort_by(|a, b| ProcessData::compare(field, ProcessSortOrder::Descending, a, b));
+            expected.truncate(10);
+
+            data.arrange();
+            let pids: Vec<u32> = data.processes.iter().map(|p| p.pid).collect();
+            let expected: Vec<u32> = expected.iter().map(|p| p.pid).collect();
+            assert_eq!(pids, expected);
         }
-        match self.sort_order {
-            ProcessSortOrder::Ascending => " ↑",
-            ProcessSortOrder::Descending => " ↓",
+
+        // CPU 最高的是 pid % 97 == 96 的进程，CPU 相同时按 PID 升序
+        let mut data = data();
+        data.view = ProcessView::Flat;
+        data.max_processes = 5;
+        data.processes = shuffled(500);
+        data.arrange();
+        let pids: Vec<u32> = data.processes.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![96, 193, 290, 387, 484]);
+    }
+
+    #[test]
+    fn test_filter_runs_before_truncation() {
+        let mut data = data();
+        data.view = ProcessView::Flat;
+        data.max_processes = 2;
+        data.processes = shuffled(500);
+        data.set_filter(ProcessFilter::parse("p4").unwrap());
+        data.arrange();
+        // 匹配 "p4" 的进程中 CPU 最高的两个，CPU 更高的 96、290 等被过滤掉
+        let pids: Vec<u32> = data.processes.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![484, 483]);
+    }
+
+    #[test]
+    fn test_ties_are_ordered_by_pid() {
+        let mut first = data();
+        first.processes = vec![
+            process(30, None, "c", 5.0),
+            process(10, None, "a", 5.0),
+            process(20, None, "b", 9.0),
+        ];
+        let mut second = first.clone();
+        second.processes.reverse();
+
+        first.arrange();
+        second.arrange();
+        let pids = |data: &ProcessData| data.processes.iter().map(|p| p.pid).collect::<Vec<_>>();
+        assert_eq!(pids(&first), vec![20, 10, 30]);
+        assert_eq!(pids(&second), pids(&first));
+
+        first.sort.order = ProcessSortOrder::Ascending;
+        first.arrange();
+        assert_eq!(pids(&first), vec![10, 30, 20]);
+    }
+
+    #[test]
+    fn test_sort_toggle_and_new_fields() {
+        let sort = ProcessSort::default();
+        let by_user = sort.toggle(ProcessSortField::User);
+        assert_eq!(by_user.order, ProcessSortOrder::Ascending);
+        assert_eq!(
+            by_user.toggle(ProcessSortField::User).order,
+            ProcessSortOrder::Descending
+        );
+        assert_eq!(
+            by_user.toggle(ProcessSortField::DiskRead),
+            ProcessSort {
+                field: ProcessSortField::DiskRead,
+                order: ProcessSortOrder::Descending
+            }
+        );
+
+        let mut data = data();
+        data.view = ProcessView::Flat;
+        for (i, p) in data.processes.iter_mut().enumerate() {
+            p.threads = [4, 1, 8, 2, 1][i];
+            p.disk_write_speed = [0, 10, 0, 30, 20][i];
+            p.sockets = [0, 3, 0, 0, 12][i];
         }
+        let pids = |data: &ProcessData| data.processes.iter().map(|p| p.pid).collect::<Vec<_>>();
+
+        data.set_sort(sort.toggle(ProcessSortField::Threads));
+        data.arrange();
+        assert_eq!(pids(&data), vec![11, 1, 12, 10, 20]);
+
+        data.set_sort(sort.toggle(ProcessSortField::DiskWrite));
+        data.arrange();
+        assert_eq!(pids(&data), vec![12, 20, 10, 1, 11]);
+
+        data.set_sort(sort.toggle(ProcessSortField::Sockets));
+        data.arrange();
+        assert_eq!(pids(&data), vec![20, 10, 1, 11, 12]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_uses_columns_sort_and_filter() {
+        let mut data = data();
+        data.set_columns(vec![ProcessColumn::Pid, ProcessColumn::Name]);
+        assert!(data.uses(ProcessColumn::Name));
+        assert!(!data.uses(ProcessColumn::Threads));
+        assert!(!data.uses(ProcessColumn::User));
+
+        data.set_sort(ProcessSort::default().toggle(ProcessSortField::Threads));
+        assert!(data.uses(ProcessColumn::Threads));
+
+        data.set_filter(ProcessFilter::parse("user:postgres").unwrap());
+        assert!(data.uses(ProcessColumn::User));
+        assert!(!data.uses(ProcessColumn::Nice));
+        assert!(!data.uses(ProcessColumn::Sockets));
+
+        data.set_filter(ProcessFilter::parse("sockets>0").unwrap());
+        assert!(data.uses(ProcessColumn::Sockets));
+    }
+
+    #[test]
+    fn test_parent_cycle_is_shown_as_roots() {
+        let mut data = data();
+        data.processes = vec![process(2, Some(3), "a", 1.0), process(3, Some(2), "b", 1.0)];
+        let table = data.table(ProcessView::Tree, &HashSet::new(), &HashSet::new());
+        assert_eq!(table.rows.len(), 2);
+    }
+
+    #[test]
+    fn test_group_rows_sum_members() {
+        let mut data = data();
+        data.processes = vec![
+            process(1, None, "php-fpm", 20.0),
+            process(2, Some(1), "php-fpm", 30.0),
+            process(3, Some(1), "php-fpm", 5.0),
+            process(4, None, "postgres", 40.0),
+            process(5, Some(4), "postgres", 1.0),
+            process(6, None, "sshd", 0.5),
+        ];
+        data.processes[3].exe = "/usr/lib/postgresql/16/bin/postgres".to_string();
+        data.processes[4].exe = "/usr/lib/postgresql/15/bin/postgres".to_string();
+        data.sort_processes();
+        let rows = |table: &ProcessTable| {
+            table
+                .rows
+                .iter()
+                .map(|row| (data.processes[row.index].pid, row.count, row.cpu_usage))
+                .collect::<Vec<_>>()
+        };
+
+        let table = data.table(ProcessView::GroupByName, &HashSet::new(), &HashSet::new());
+        assert_eq!(rows(&table), vec![(2, 3, 55.0), (4, 2, 41.0), (6, 1, 0.5)]);
+        assert_eq!(table.rows[0].group.as_deref(), Some("php-fpm"));
+        assert_eq!(table.rows[0].memory_mb, 30.0);
+        assert!(table.rows[0].collapsed && table.rows[2].group.is_none());
+
+        let expanded = HashSet::from(["php-fpm".to_string()]);
+        let table = data.table(ProcessView::GroupByName, &HashSet::new(), &expanded);
+        assert_eq!(
+            rows(&table),
+            vec![
+                (2, 3, 55.0),
+                (2, 1, 30.0),
+                (1, 1, 20.0),
+                (3, 1, 5.0),
+                (4, 2, 41.0),
+                (6, 1, 0.5)
+            ]
+        );
+        assert_eq!(table.rows[1].prefix, "   ");
+
+        // 可执行文件不同的同名进程分开显示
+        let table = data.table(ProcessView::GroupByExe, &HashSet::new(), &HashSet::new());
+        assert_eq!(
+            rows(&table),
+            vec![(2, 3, 55.0), (4, 1, 40.0), (5, 1, 1.0), (6, 1, 0.5)]
+        );
+    }
+
+    #[test]
+    fn test_collected_process_has_exe() {
+        let mut sampler = Sampler::new();
+        sampler.refresh(crate::data::SampleNeeds {
+            processes: true,
+            ..Default::default()
+        });
+        let mut data = ProcessData::new(&Config::default(), &sampler).unwrap();
+        data.max_processes = usize::MAX;
+        data.update(&sampler).unwrap();
+
+        // 测试进程自身一定在列表中，exe 是当前的测试程序
+        let current = data
+            .processes
+            .iter()
+            .find(|p| p.pid == std::process::id())
+            .expect("test process is listed");
+        assert_eq!(
+            current.exe,
+            std::env::current_exe().unwrap().display().to_string()
+        );
+    }
+}
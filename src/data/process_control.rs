@@ -0,0 +1,224 @@
+use std::io;
+use thiserror::Error;
+
+/// nice 值的范围
+pub const NICE_MIN: i32 = -20;
+pub const NICE_MAX: i32 = 19;
+
+/// 进程操作失败的原因
+#[derive(Debug, Error)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub enum ProcessControlError {
+    #[error("permission denied for PID {pid} (try running as root)")]
+    PermissionDenied { pid: u32 },
+    #[error("process {pid} no longer exists")]
+    NoSuchProcess { pid: u32 },
+    #[error("PID {pid}: {source}")]
+    Os { pid: u32, source: io::Error },
+    #[error("at least one CPU must be selected")]
+    EmptyAffinity,
+    #[error("{0} is not supported on this platform")]
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    Unsupported(&'static str),
+}
+
+impl ProcessControlError {
+    #[cfg(target_os = "linux")]
+    fn from_os(pid: u32, error: io::Error) -> Self {
+        match error.raw_os_error() {
+            // 非 root 用户降低 nice 值时返回 EACCES
+            Some(libc::EPERM | libc::EACCES) => Self::PermissionDenied { pid },
+            Some(libc::ESRCH) => Self::NoSuchProcess { pid },
+            _ => Self::Os { pid, source: error },
+        }
+    }
+}
+
+/// 可以从进程页发送的信号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    Term,
+    Kill,
+    Stop,
+    Cont,
+    Hup,
+    Int,
+    Quit,
+    Usr1,
+    Usr2,
+}
+
+impl Signal {
+    /// 信号选择器中列出的信号，按常用程度排列
+    pub const ALL: [Signal; 9] = [
+        Signal::Term,
+        Signal::Kill,
+        Signal::Stop,
+        Signal::Cont,
+        Signal::Hup,
+        Signal::Int,
+        Signal::Quit,
+        Signal::Usr1,
+        Signal::Usr2,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Signal::Term => "SIGTERM",
+            Signal::Kill => "SIGKILL",
+            Signal::Stop => "SIGSTOP",
+            Signal::Cont => "SIGCONT",
+            Signal::Hup => "SIGHUP",
+            Signal::Int => "SIGINT",
+            Signal::Quit => "SIGQUIT",
+            Signal::Usr1 => "SIGUSR1",
+            Signal::Usr2 => "SIGUSR2",
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn number(self) -> libc::c_int {
+        match self {
+            Signal::Term => libc::SIGTERM,
+            Signal::Kill => libc::SIGKILL,
+            Signal::Stop => libc::SIGSTOP,
+            Signal::Cont => libc::SIGCONT,
+            Signal::Hup => libc::SIGHUP,
+            Signal::Int => libc::SIGINT,
+            Signal::Quit => libc::SIGQUIT,
+            Signal::Usr1 => libc::SIGUSR1,
+            Signal::Usr2 => libc::SIGUSR2,
+        }
+    }
+}
+
+/// 向进程发送信号
+///
+/// # Errors
+///
+/// 没有权限、进程已退出或平台不支持时返回 [`ProcessControlError`]
+#[cfg(target_os = "linux")]
+pub fn send_signal(pid: u32, signal: Signal) -> Result<(), ProcessControlError> {
+    let target =
+        libc::pid_t::try_from(pid).map_err(|_| ProcessControlError::NoSuchProcess { pid })?;
+    // SAFETY: kill 只读取参数，不涉及内存访问
+    if unsafe { libc::kill(target, signal.number()) } == 0 {
+        Ok(())
+    } else {
+        Err(ProcessControlError::from_os(
+            pid,
+            io::Error::last_os_error(),
+        ))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn send_signal(_pid: u32, _signal: Signal) -> Result<(), ProcessControlError> {
+    Err(ProcessControlError::Unsupported("sending signals"))
+}
+
+/// 修改进程的 nice 值，超出 [`NICE_MIN`]..=[`NICE_MAX`] 的值会被截断
+///
+/// # Errors
+///
+/// 没有权限（非 root 用户只能提高 nice 值）、进程已退出或平台不支持时返回错误
+#[cfg(target_os = "linux")]
+pub fn set_nice(pid: u32, nice: i32) -> Result<(), ProcessControlError> {
+    let nice = nice.clamp(NICE_MIN, NICE_MAX);
+    // SAFETY: setpriority 只读取参数，不涉及内存访问
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) } == 0 {
+        Ok(())
+    } else {
+        Err(ProcessControlError::from_os(
+            pid,
+            io::Error::last_os_error(),
+        ))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_nice(_pid: u32, _nice: i32) -> Result<(), ProcessControlError> {
+    Err(ProcessControlError::Unsupported("changing the nice value"))
+}
+
+/// 将进程限制在指定的 CPU 上运行
+///
+/// # Errors
+///
+/// CPU 列表为空、没有权限、进程已退出或平台不支持时返回错误
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: u32, cpus: &[usize]) -> Result<(), ProcessControlError> {
+    if cpus.is_empty() {
+        return Err(ProcessControlError::EmptyAffinity);
+    }
+    let target =
+        libc::pid_t::try_from(pid).map_err(|_| ProcessControlError::NoSuchProcess { pid })?;
+
+    // SAFETY: cpu_set_t 是纯位图，全零即空集合
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        // SAFETY: CPU_SET 会忽略超出 CPU_SETSIZE 的编号
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+    // SAFETY: set 在调用期间有效，长度与类型一致
+    let result =
+        unsafe { libc::sched_setaffinity(target, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(ProcessControlError::from_os(
+            pid,
+            io::Error::last_os_error(),
+        ))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity(_pid: u32, _cpus: &[usize]) -> Result<(), ProcessControlError> {
+    Err(ProcessControlError::Unsupported(
+        "changing the CPU affinity",
+    ))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_errors_are_classified() {
+        // 超出 pid_max 的 PID 不可能存在，kill 返回 ESRCH
+        let missing = send_signal(i32::MAX as u32, Signal::Cont);
+        assert!(matches!(
+            missing,
+            Err(ProcessControlError::NoSuchProcess { .. })
+        ));
+
+        assert!(matches!(
+            ProcessControlError::from_os(1, io::Error::from_raw_os_error(libc::EPERM)),
+            ProcessControlError::PermissionDenied { pid: 1 }
+        ));
+    }
+
+    #[test]
+    fn test_set_nice_and_affinity_of_self() {
+        let pid = std::process::id();
+        let stat = crate::data::procfs::read_stat(&crate::data::SysRoot::default(), pid).unwrap();
+        // 保持原值不变，任何用户都有权限
+        assert!(set_nice(pid, stat.nice).is_ok());
+
+        let status =
+            crate::data::procfs::read_status(&crate::data::SysRoot::default(), pid).unwrap();
+        let cpus = status.cpus_allowed.unwrap();
+        assert!(set_affinity(pid, &cpus).is_ok());
+        assert!(matches!(
+            set_affinity(pid, &[]),
+            Err(ProcessControlError::EmptyAffinity)
+        ));
+    }
+
+    #[test]
+    fn test_send_signal_to_self() {
+        // SIGCONT 对正在运行的进程没有影响
+        assert!(send_signal(std::process::id(), Signal::Cont).is_ok());
+    }
+}
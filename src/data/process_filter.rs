@@ -0,0 +1,819 @@
+use regex::{Regex, RegexBuilder};
+use thiserror::Error;
+
+use super::process::{ProcessColumn, ProcessInfo};
+
+/// 过滤表达式解析失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FilterError {
+    #[error("unknown field '{0}'")]
+    UnknownField(String),
+    #[error("'{field}' does not support '{op}'")]
+    UnsupportedOperator { field: String, op: &'static str },
+    #[error("missing value after '{0}'")]
+    MissingValue(String),
+    #[error("invalid number '{value}' for '{field}'")]
+    InvalidNumber { field: String, value: String },
+    #[error("invalid regex '{pattern}': {message}")]
+    InvalidRegex { pattern: String, message: String },
+    #[error("unclosed quote")]
+    UnclosedQuote,
+    #[error("missing ')'")]
+    MissingParen,
+    #[error("unexpected '{0}'")]
+    Unexpected(String),
+    #[error("expected a condition after '{0}'")]
+    ExpectedCondition(String),
+}
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    /// `:`，文本字段为包含，数值字段同 `=`
+    Has,
+    /// `~`，正则匹配
+    Match,
+    /// `!~`
+    NotMatch,
+}
+
+impl Op {
+    /// 按长度从长到短排列，保证 `>=` 先于 `>` 匹配
+    const ALL: [(&'static str, Op); 9] = [
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        ("!=", Op::Ne),
+        ("!~", Op::NotMatch),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+        ("=", Op::Eq),
+        (":", Op::Has),
+        ("~", Op::Match),
+    ];
+
+    fn symbol(self) -> &'static str {
+        Op::ALL
+            .iter()
+            .find(|(_, op)| *op == self)
+            .map_or("", |(symbol, _)| symbol)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberField {
+    Cpu,
+    MemoryMb,
+    MemoryPercent,
+    VirtualMemoryMb,
+    Pid,
+    Ppid,
+    Nice,
+    Priority,
+    Threads,
+    Sockets,
+    /// 运行时长（秒）
+    Time,
+    /// 磁盘读写速率（MB/s）
+    Read,
+    Write,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextField {
+    Name,
+    Command,
+    User,
+    State,
+}
+
+/// 数值的单位，决定如何解析 `1G`、`2h` 这样的后缀
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    /// 不带单位的数值
+    Plain,
+    /// 以 MB 为基准，可带 K/M/G/T 后缀
+    Megabytes,
+    /// 以秒为基准，可带 s/m/h/d 后缀
+    Seconds,
+}
+
+impl NumberField {
+    fn unit(self) -> Unit {
+        match self {
+            NumberField::MemoryMb
+            | NumberField::VirtualMemoryMb
+            | NumberField::Read
+            | NumberField::Write => Unit::Megabytes,
+            NumberField::Time => Unit::Seconds,
+            _ => Unit::Plain,
+        }
+    }
+
+    fn value(self, process: &ProcessInfo) -> f64 {
+        let mb = |bytes: u64| bytes as f64 / super::BYTES_PER_MB as f64;
+        match self {
+            NumberField::Cpu => process.cpu_usage as f64,
+            NumberField::MemoryMb => process.memory_mb as f64,
+            NumberField::MemoryPercent => process.memory_percent as f64,
+            NumberField::VirtualMemoryMb => process.virtual_memory_mb,
+            NumberField::Pid => process.pid as f64,
+            // 没有父进程的视为 0
+            NumberField::Ppid => process.parent.unwrap_or(0) as f64,
+            NumberField::Nice => process.nice as f64,
+            NumberField::Priority => process.priority as f64,
+            NumberField::Threads => process.threads as f64,
+            NumberField::Sockets => process.sockets as f64,
+            NumberField::Time => process.run_time as f64,
+            NumberField::Read => mb(process.disk_read_speed),
+            NumberField::Write => mb(process.disk_write_speed),
+        }
+    }
+
+    /// 对应的进程表列，父进程 PID 没有单独的列
+    fn column(self) -> Option<ProcessColumn> {
+        match self {
+            NumberField::Cpu => Some(ProcessColumn::Cpu),
+            NumberField::MemoryMb => Some(ProcessColumn::MemoryMb),
+            NumberField::MemoryPercent => Some(ProcessColumn::MemoryPercent),
+            NumberField::VirtualMemoryMb => Some(ProcessColumn::VirtualMemory),
+            NumberField::Pid => Some(ProcessColumn::Pid),
+            NumberField::Ppid => None,
+            NumberField::Nice => Some(ProcessColumn::Nice),
+            NumberField::Priority => Some(ProcessColumn::Priority),
+            NumberField::Threads => Some(ProcessColumn::Threads),
+            NumberField::Sockets => Some(ProcessColumn::Sockets),
+            NumberField::Time => Some(ProcessColumn::RunTime),
+            NumberField::Read => Some(ProcessColumn::DiskRead),
+            NumberField::Write => Some(ProcessColumn::DiskWrite),
+        }
+    }
+}
+
+impl TextField {
+    fn value(self, process: &ProcessInfo) -> &str {
+        match self {
+            TextField::Name => &process.name,
+            TextField::Command => &process.command,
+            TextField::User => &process.user,
+            TextField::State => &process.status,
+        }
+    }
+
+    fn column(self) -> ProcessColumn {
+        match self {
+            TextField::Name => ProcessColumn::Name,
+            TextField::Command => ProcessColumn::Command,
+            TextField::User => ProcessColumn::User,
+            TextField::State => ProcessColumn::Status,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    /// 不带字段名的词：名称、命令行或 PID 包含该词（不区分大小写）
+    Keyword(String),
+    Number {
+        field: NumberField,
+        op: Op,
+        value: f64,
+    },
+    /// 包含或相等，值已转为小写
+    Text {
+        field: TextField,
+        op: Op,
+        value: String,
+    },
+    Regex {
+        field: TextField,
+        regex: Regex,
+        negate: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+    Condition(Condition),
+}
+
+/// 进程过滤条件
+///
+/// 由空格分隔的条件组成，相邻条件默认为 AND，可以用 `AND`、`OR`、`NOT`（或 `&`、`|`、`!`）
+/// 和括号组合，例如 `cpu>50 mem>1G user:postgres name~^java state:zombie`。
+/// 不带字段名的词保持原来的行为，按名称、命令行和 PID 做子串匹配
+#[derive(Debug, Clone, Default)]
+pub struct ProcessFilter {
+    query: String,
+    expr: Option<Expr>,
+}
+
+impl ProcessFilter {
+    /// 解析过滤表达式，空白字符串得到匹配所有进程的过滤条件
+    ///
+    /// # Errors
+    ///
+    /// 表达式有语法错误、未知字段或非法的数值、正则时返回 [`FilterError`]
+    pub fn parse(query: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(query)?;
+        let expr = if tokens.is_empty() {
+            None
+        } else {
+            let mut parser = Parser { tokens, pos: 0 };
+            let expr = parser.or()?;
+            if let Some(token) = parser.peek() {
+                return Err(FilterError::Unexpected(token.text().to_string()));
+            }
+            Some(expr)
+        };
+        Ok(Self {
+            query: query.trim().to_string(),
+            expr,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expr.is_none()
+    }
+
+    /// 原始的过滤表达式
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        self.expr
+            .as_ref()
+            .is_none_or(|expr| evaluate(expr, process))
+    }
+
+    /// 过滤条件是否引用了某一列的字段，不带字段名的词只用到名称、命令行和 PID
+    pub fn uses(&self, column: ProcessColumn) -> bool {
+        self.expr
+            .as_ref()
+            .is_some_and(|expr| references(expr, column))
+    }
+}
+
+/// 置顶和监视规则：纯数字为 PID，其他为匹配进程名的正则表达式
+#[derive(Debug, Clone)]
+pub struct ProcessPattern {
+    source: String,
+    kind: PatternKind,
+}
+
+#[derive(Debug, Clone)]
+enum PatternKind {
+    Pid(u32),
+    Name(Regex),
+}
+
+impl ProcessPattern {
+    /// # Errors
+    ///
+    /// 正则表达式非法时返回 [`FilterError::InvalidRegex`]
+    pub fn parse(source: &str) -> Result<Self, FilterError> {
+        let kind = match source.parse() {
+            Ok(pid) => PatternKind::Pid(pid),
+            Err(_) => {
+                PatternKind::Name(Regex::new(source).map_err(|e| FilterError::InvalidRegex {
+                    pattern: source.to_string(),
+                    message: regex_message(&e),
+                })?)
+            }
+        };
+        Ok(Self {
+            source: source.to_string(),
+            kind,
+        })
+    }
+
+    /// 完整匹配进程名的规则，用于在界面中按名称置顶或监视
+    pub fn for_name(name: &str) -> Self {
+        let source = format!("^{}$", regex::escape(name));
+        let regex = Regex::new(&source).expect("escaped name is a valid regex");
+        Self {
+            source,
+            kind: PatternKind::Name(regex),
+        }
+    }
+
+    /// 规则原文，也是写入配置文件的内容
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        match &self.kind {
+            PatternKind::Pid(pid) => process.pid == *pid,
+            PatternKind::Name(regex) => regex.is_match(&process.name),
+        }
+    }
+}
+
+/// 解析配置中的规则列表，跳过非法的规则（加载配置时已经校验过）
+pub fn parse_patterns(sources: &[String]) -> Vec<ProcessPattern> {
+    sources
+        .iter()
+        .filter_map(|source| ProcessPattern::parse(source).ok())
+        .collect()
+}
+
+impl PartialEq for ProcessPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+fn evaluate(expr: &Expr, process: &ProcessInfo) -> bool {
+    match expr {
+        Expr::And(exprs) => exprs.iter().all(|expr| evaluate(expr, process)),
+        Expr::Or(exprs) => exprs.iter().any(|expr| evaluate(expr, process)),
+        Expr::Not(expr) => !evaluate(expr, process),
+        Expr::Condition(condition) => condition.matches(process),
+    }
+}
+
+fn references(expr: &Expr, column: ProcessColumn) -> bool {
+    match expr {
+        Expr::And(exprs) | Expr::Or(exprs) => exprs.iter().any(|expr| references(expr, column)),
+        Expr::Not(expr) => references(expr, column),
+        Expr::Condition(condition) => condition.column() == Some(column),
+    }
+}
+
+impl Condition {
+    /// 条件引用的列，不带字段名的词返回 `None`
+    fn column(&self) -> Option<ProcessColumn> {
+        match self {
+            Condition::Keyword(_) => None,
+            Condition::Number { field, .. } => field.column(),
+            Condition::Text { field, .. } | Condition::Regex { field, .. } => Some(field.column()),
+        }
+    }
+
+    fn matches(&self, process: &ProcessInfo) -> bool {
+        match self {
+            Condition::Keyword(text) => {
+                process.name.to_lowercase().contains(text)
+                    || process.command.to_lowercase().contains(text)
+                    || process.pid.to_string().contains(text)
+            }
+            Condition::Number { field, op, value } => {
+                let actual = field.value(process);
+                match op {
+                    Op::Gt => actual > *value,
+                    Op::Ge => actual >= *value,
+                    Op::Lt => actual < *value,
+                    Op::Le => actual <= *value,
+                    Op::Eq | Op::Has => actual == *value,
+                    Op::Ne => actual != *value,
+                    Op::Match | Op::NotMatch => false,
+                }
+            }
+            Condition::Text { field, op, value } => {
+                let actual = field.value(process).to_lowercase();
+                match op {
+                    Op::Eq => actual == *value,
+                    Op::Ne => actual != *value,
+                    _ => actual.contains(value.as_str()),
+                }
+            }
+            Condition::Regex {
+                field,
+                regex,
+                negate,
+            } => regex.is_match(field.value(process)) != *negate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl Token {
+    fn text(&self) -> &str {
+        match self {
+            Token::Word(word) => word,
+            Token::And => "AND",
+            Token::Or => "OR",
+            Token::Not => "NOT",
+            Token::Open => "(",
+            Token::Close => ")",
+        }
+    }
+}
+
+/// 按空白和括号切分，双引号内的内容保持原样
+fn tokenize(query: &str) -> Result<Vec<Token>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            // 紧贴在条件前的 `!` 表示取反，`!=`、`!~` 只会出现在词的中间
+            '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            _ => {
+                let mut word = String::new();
+                let mut quoted = false;
+                // 词内部成对的括号属于词本身，例如 `name~^(java|go)$`
+                let mut depth = 0usize;
+                while let Some(&c) = chars.peek() {
+                    if !quoted && (c.is_whitespace() || (c == ')' && depth == 0)) {
+                        break;
+                    }
+                    chars.next();
+                    match c {
+                        '"' => quoted = !quoted,
+                        '(' if !quoted => {
+                            depth += 1;
+                            word.push(c);
+                        }
+                        ')' if !quoted => {
+                            depth -= 1;
+                            word.push(c);
+                        }
+                        _ => word.push(c),
+                    }
+                }
+                if quoted {
+                    return Err(FilterError::UnclosedQuote);
+                }
+                tokens.push(match word.as_str() {
+                    "AND" | "&" | "&&" => Token::And,
+                    "OR" | "|" | "||" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// 递归下降解析，优先级从低到高为 OR、AND、NOT
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr, FilterError> {
+        let mut exprs = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            exprs.push(self.operand("OR", Self::and)?);
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::Or(exprs)
+        })
+    }
+
+    fn and(&mut self) -> Result<Expr, FilterError> {
+        let mut exprs = vec![self.unary()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                    exprs.push(self.operand("AND", Self::unary)?);
+                }
+                // 相邻的条件默认为 AND
+                Some(Token::Word(_) | Token::Not | Token::Open) => exprs.push(self.unary()?),
+                _ => break,
+            }
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            Expr::And(exprs)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, FilterError> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.operand("NOT", Self::unary)?))),
+            Some(Token::Open) => {
+                let expr = self.operand("(", Self::or)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err(FilterError::MissingParen),
+                }
+            }
+            Some(Token::Word(word)) => parse_condition(&word).map(Expr::Condition),
+            Some(token) => Err(FilterError::Unexpected(token.text().to_string())),
+            None => Err(FilterError::ExpectedCondition(String::new())),
+        }
+    }
+
+    /// 解析运算符之后的部分，缺失时报告是哪个运算符后面缺少条件
+    fn operand(
+        &mut self,
+        after: &str,
+        parse: fn(&mut Self) -> Result<Expr, FilterError>,
+    ) -> Result<Expr, FilterError> {
+        match self.peek() {
+            None | Some(Token::Close | Token::And | Token::Or) => {
+                Err(FilterError::ExpectedCondition(after.to_string()))
+            }
+            Some(_) => parse(self),
+        }
+    }
+}
+
+fn parse_condition(word: &str) -> Result<Condition, FilterError> {
+    // 运算符出现在词首时（例如 `:foo`）不是字段条件
+    let operator = word.char_indices().skip(1).find_map(|(i, _)| {
+        Op::ALL
+            .iter()
+            .find(|(symbol, _)| word[i..].starts_with(symbol))
+            .map(|&(symbol, op)| (i, symbol, op))
+    });
+    let Some((index, symbol, op)) = operator else {
+        return Ok(Condition::Keyword(word.to_lowercase()));
+    };
+
+    let name = word[..index].to_lowercase();
+    let value = &word[index + symbol.len()..];
+    if value.is_empty() {
+        return Err(FilterError::MissingValue(word.to_string()));
+    }
+
+    if let Some(field) = text_field(&name) {
+        return match op {
+            Op::Match | Op::NotMatch => RegexBuilder::new(value)
+                .case_insensitive(true)
+                .build()
+                .map(|regex| Condition::Regex {
+                    field,
+                    regex,
+                    negate: op == Op::NotMatch,
+                })
+                .map_err(|e| FilterError::InvalidRegex {
+                    pattern: value.to_string(),
+                    message: regex_message(&e),
+                }),
+            Op::Eq | Op::Ne | Op::Has => Ok(Condition::Text {
+                field,
+                op,
+                value: value.to_lowercase(),
+            }),
+            _ => Err(FilterError::UnsupportedOperator {
+                field: name,
+                op: op.symbol(),
+            }),
+        };
+    }
+
+    let Some(field) = number_field(&name) else {
+        return Err(FilterError::UnknownField(name));
+    };
+    if matches!(op, Op::Match | Op::NotMatch) {
+        return Err(FilterError::UnsupportedOperator {
+            field: name,
+            op: op.symbol(),
+        });
+    }
+    // `mem>5%` 按内存占比比较
+    let (field, value) = match (field, value.strip_suffix('%')) {
+        (NumberField::MemoryMb, Some(percent)) => (NumberField::MemoryPercent, percent),
+        (NumberField::Cpu, Some(percent)) => (NumberField::Cpu, percent),
+        _ => (field, value),
+    };
+    let value = parse_number(value, field.unit()).ok_or_else(|| FilterError::InvalidNumber {
+        field: name,
+        value: value.to_string(),
+    })?;
+    Ok(Condition::Number { field, op, value })
+}
+
+fn text_field(name: &str) -> Option<TextField> {
+    match name {
+        "name" => Some(TextField::Name),
+        "cmd" | "command" => Some(TextField::Command),
+        "user" => Some(TextField::User),
+        "state" | "status" => Some(TextField::State),
+        _ => None,
+    }
+}
+
+fn number_field(name: &str) -> Option<NumberField> {
+    match name {
+        "cpu" => Some(NumberField::Cpu),
+        "mem" | "memory" => Some(NumberField::MemoryMb),
+        "virt" => Some(NumberField::VirtualMemoryMb),
+        "pid" => Some(NumberField::Pid),
+        "ppid" => Some(NumberField::Ppid),
+        "nice" | "ni" => Some(NumberField::Nice),
+        "pri" | "priority" => Some(NumberField::Priority),
+        "threads" => Some(NumberField::Threads),
+        "sockets" => Some(NumberField::Sockets),
+        "time" => Some(NumberField::Time),
+        "read" => Some(NumberField::Read),
+        "write" => Some(NumberField::Write),
+        _ => None,
+    }
+}
+
+/// 解析数值及单位后缀，容量以 1024 为进制
+fn parse_number(value: &str, unit: Unit) -> Option<f64> {
+    let lower = value.to_lowercase();
+    let (number, multiplier) = match unit {
+        Unit::Plain => (lower.as_str(), 1.0),
+        Unit::Megabytes => {
+            let number = lower.strip_suffix('b').unwrap_or(&lower);
+            match number.char_indices().last() {
+                Some((i, 'k')) => (&number[..i], 1.0 / 1024.0),
+                Some((i, 'm')) => (&number[..i], 1.0),
+                Some((i, 'g')) => (&number[..i], 1024.0),
+                Some((i, 't')) => (&number[..i], 1024.0 * 1024.0),
+                _ => (number, 1.0),
+            }
+        }
+        Unit::Seconds => match lower.char_indices().last() {
+            Some((i, 's')) => (&lower[..i], 1.0),
+            Some((i, 'm')) => (&lower[..i], 60.0),
+            Some((i, 'h')) => (&lower[..i], 3600.0),
+            Some((i, 'd')) => (&lower[..i], 86_400.0),
+            _ => (lower.as_str(), 1.0),
+        },
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .map(|n| n * multiplier)
+}
+
+/// regex 的错误信息是多行的，只保留最后一行说明
+fn regex_message(error: &regex::Error) -> String {
+    error
+        .to_string()
+        .lines()
+        .rfind(|line| !line.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .trim_start_matches("error: ")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, user: &str, cpu_usage: f32, memory_mb: f32) -> ProcessInfo {
+        ProcessInfo {
+            parent: Some(1),
+            cpu_usage,
+            memory_mb,
+            memory_percent: memory_mb / 100.0,
+            virtual_memory_mb: memory_mb as f64 * 2.0,
+            command: format!("/usr/bin/{} --serve", name),
+            run_time: 7200,
+            user: user.to_string(),
+            threads: 4,
+            ..ProcessInfo::test(pid, name)
+        }
+    }
+
+    fn processes() -> Vec<ProcessInfo> {
+        let mut zombie = process(40, "defunct", "root", 0.0, 0.0);
+        zombie.status = "Zombie".to_string();
+        vec![
+            process(10, "java", "app", 75.0, 2048.0),
+            process(20, "postgres", "postgres", 60.0, 512.0),
+            process(30, "javac", "app", 5.0, 1536.0),
+            zombie,
+        ]
+    }
+
+    fn matching(query: &str) -> Vec<u32> {
+        let filter = ProcessFilter::parse(query).unwrap();
+        processes()
+            .iter()
+            .filter(|p| filter.matches(p))
+            .map(|p| p.pid)
+            .collect()
+    }
+
+    #[test]
+    fn test_fields_and_units() {
+        assert_eq!(matching(""), vec![10, 20, 30, 40]);
+        assert_eq!(matching("cpu>50"), vec![10, 20]);
+        assert_eq!(matching("mem>1G"), vec![10, 30]);
+        assert_eq!(matching("mem>=2048M"), vec![10]);
+        assert_eq!(matching("mem>15%"), vec![10, 30]);
+        assert_eq!(matching("user:postgres"), vec![20]);
+        assert_eq!(matching("user=APP"), vec![10, 30]);
+        assert_eq!(matching("state:zombie"), vec![40]);
+        assert_eq!(matching("time>=2h threads=4 pid!=30"), vec![10, 20, 40]);
+        assert_eq!(matching("virt>3G"), vec![10]);
+    }
+
+    #[test]
+    fn test_regex_and_plain_words() {
+        assert_eq!(matching("name~^java$"), vec![10]);
+        assert_eq!(matching("name~^JAVA"), vec![10, 30]);
+        assert_eq!(matching("name!~^java"), vec![20, 40]);
+        assert_eq!(matching("(name~^(java|postgres)$ cpu>70)"), vec![10]);
+        assert_eq!(matching("cmd~\"bin/post\""), vec![20]);
+        // 不带字段名时与原来的子串搜索一致
+        assert_eq!(matching("JAV"), vec![10, 30]);
+        assert_eq!(matching("20"), vec![20]);
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        assert_eq!(matching("cpu>50 mem>1G user:app name~^java"), vec![10]);
+        assert_eq!(matching("state:zombie OR user:postgres"), vec![20, 40]);
+        assert_eq!(matching("user:app AND NOT cpu>50"), vec![30]);
+        assert_eq!(matching("!user:app"), vec![20, 40]);
+        // AND 优先于 OR
+        assert_eq!(matching("user:postgres | user:app cpu<10"), vec![20, 30]);
+        assert_eq!(matching("(user:postgres | user:app) cpu>50"), vec![10, 20]);
+    }
+
+    #[test]
+    fn test_uses() {
+        let filter = ProcessFilter::parse("java | !(user:app sockets>2)").unwrap();
+        assert!(filter.uses(ProcessColumn::User));
+        assert!(filter.uses(ProcessColumn::Sockets));
+        assert!(!filter.uses(ProcessColumn::Name));
+        assert!(!filter.uses(ProcessColumn::Nice));
+        assert!(!ProcessFilter::default().uses(ProcessColumn::User));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |query: &str| ProcessFilter::parse(query).unwrap_err();
+        assert_eq!(
+            error("colour:red"),
+            FilterError::UnknownField("colour".into())
+        );
+        assert_eq!(
+            error("cpu>lots"),
+            FilterError::InvalidNumber {
+                field: "cpu".into(),
+                value: "lots".into()
+            }
+        );
+        assert_eq!(
+            error("user>5"),
+            FilterError::UnsupportedOperator {
+                field: "user".into(),
+                op: ">"
+            }
+        );
+        assert_eq!(error("cpu>"), FilterError::MissingValue("cpu>".into()));
+        assert_eq!(error("(cpu>5"), FilterError::MissingParen);
+        assert_eq!(error("cpu>5)"), FilterError::Unexpected(")".into()));
+        assert_eq!(
+            error("cpu>5 OR"),
+            FilterError::ExpectedCondition("OR".into())
+        );
+        assert_eq!(error("name:\"java"), FilterError::UnclosedQuote);
+        assert!(matches!(
+            error("name~(java"),
+            FilterError::InvalidRegex { .. }
+        ));
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Local};
+
+use super::history::ProcessHistory;
+use super::process::ProcessInfo;
+use super::process_filter::ProcessPattern;
+
+/// 监视中的进程的状态变化
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEventKind {
+    /// 进程退出，且没有其他匹配的进程
+    Exited { pid: u32 },
+    /// 出现了新的匹配进程，`pid` 为新进程的 PID
+    Restarted { pid: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchEvent {
+    /// 递增的序号，界面据此判断哪些事件还没有提示过
+    pub id: u64,
+    pub name: String,
+    pub kind: WatchEventKind,
+}
+
+/// 一条监视规则及其跟踪的进程
+///
+/// 规则匹配多个进程时跟踪最早启动的一个；跟踪的进程退出后改为跟踪其他匹配的进程，
+/// 并视为重新启动
+#[derive(Debug, Clone)]
+pub struct WatchedProcess {
+    pub pattern: ProcessPattern,
+    /// 当前跟踪的进程，尚未出现或已经退出时为 `None`
+    pub pid: Option<u32>,
+    /// 最近一次跟踪到的进程名，尚未出现时为规则原文
+    pub name: String,
+    /// 跟踪的进程的启动时间，用于识别 PID 被复用
+    start_time: u64,
+    /// 曾经跟踪到过进程，之后再出现的匹配进程视为重新启动
+    seen: bool,
+    /// 退出后为最后一次跟踪到的 PID
+    last_pid: Option<u32>,
+    pub history: ProcessHistory,
+}
+
+impl WatchedProcess {
+    pub fn new(pattern: ProcessPattern, max_points: usize) -> Self {
+        Self {
+            name: pattern.source().to_string(),
+            pattern,
+            pid: None,
+            start_time: 0,
+            seen: false,
+            last_pid: None,
+            history: ProcessHistory::new(max_points),
+        }
+    }
+
+    /// 按最新的完整进程列表更新跟踪的进程并记录历史，返回状态变化
+    ///
+    /// 进程不存在期间历史记为 0，使曲线上能看出中断的时段
+    pub fn update(
+        &mut self,
+        processes: &[ProcessInfo],
+        timestamp: DateTime<Local>,
+    ) -> Option<WatchEventKind> {
+        let current = self.pid.and_then(|pid| {
+            processes
+                .iter()
+                .find(|p| p.pid == pid && p.start_time == self.start_time)
+        });
+        let (process, event) = match current {
+            Some(process) => (Some(process), None),
+            None => {
+                let next = processes
+                    .iter()
+                    .filter(|p| self.pattern.matches(p))
+                    .min_by_key(|p| (p.start_time, p.pid));
+                let event = match (self.pid, next) {
+                    (Some(pid), None) => Some(WatchEventKind::Exited { pid }),
+                    (Some(_), Some(next)) => Some(WatchEventKind::Restarted { pid: next.pid }),
+                    (None, Some(next)) if self.seen => {
+                        Some(WatchEventKind::Restarted { pid: next.pid })
+                    }
+                    (None, _) => None,
+                };
+                (next, event)
+            }
+        };
+
+        match process {
+            Some(process) => {
+                self.pid = Some(process.pid);
+                self.name = process.name.clone();
+                self.start_time = process.start_time;
+                self.seen = true;
+                self.last_pid = None;
+                self.history
+                    .update(timestamp, process.cpu_usage, process.memory_mb);
+            }
+            None => {
+                if self.pid.is_some() {
+                    self.last_pid = self.pid.take();
+                }
+                self.history.update(timestamp, 0.0, 0.0);
+            }
+        }
+        event
+    }
+
+    /// 已经退出的进程最后一次跟踪到的 PID
+    pub fn last_pid(&self) -> Option<u32> {
+        self.last_pid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, name: &str, start_time: u64) -> ProcessInfo {
+        ProcessInfo {
+            cpu_usage: pid as f32,
+            memory_mb: 1.0,
+            memory_percent: 0.1,
+            start_time,
+            ..ProcessInfo::test(pid, name)
+        }
+    }
+
+    #[test]
+    fn test_exit_and_restart_are_reported() {
+        let now = Local::now();
+        let mut watch = WatchedProcess::new(ProcessPattern::parse("^nginx$").unwrap(), 10);
+
+        assert_eq!(watch.update(&[process(1, "init", 0)], now), None);
+        assert_eq!(watch.pid, None);
+
+        // 最早启动的匹配进程被跟踪，工作进程退出不影响
+        let running = [process(20, "nginx", 100), process(21, "nginx", 101)];
+        assert_eq!(watch.update(&running, now), None);
+        assert_eq!(watch.pid, Some(20));
+        assert_eq!(watch.update(&running[..1], now), None);
+
+        assert_eq!(
+            watch.update(&[process(1, "init", 0)], now),
+            Some(WatchEventKind::Exited { pid: 20 })
+        );
+        assert_eq!((watch.pid, watch.last_pid()), (None, Some(20)));
+        assert_eq!(watch.update(&[], now), None);
+
+        assert_eq!(
+            watch.update(&[process(30, "nginx", 200)], now),
+            Some(WatchEventKind::Restarted { pid: 30 })
+        );
+        assert_eq!(
+            watch.history.cpu_usage.get_all(),
+            vec![0.0, 20.0, 20.0, 0.0, 0.0, 30.0]
+        );
+    }
+
+    #[test]
+    fn test_pid_reuse_is_a_restart() {
+        let now = Local::now();
+        let mut watch = WatchedProcess::new(ProcessPattern::parse("42").unwrap(), 10);
+        assert_eq!(watch.update(&[process(42, "worker", 100)], now), None);
+        assert_eq!(
+            watch.update(&[process(42, "worker", 500)], now),
+            Some(WatchEventKind::Restarted { pid: 42 })
+        );
+        assert_eq!(watch.name, "worker");
+    }
+}
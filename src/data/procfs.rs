@@ -0,0 +1,578 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::SystemTime;
+
+use super::SysRoot;
+
+/// `/proc/<pid>/stat` 和 `/proc/<pid>/task/<tid>/stat` 中用到的字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PidStat {
+    /// 进程名或线程名，最长 15 个字符
+    pub name: String,
+    /// 状态字母，如 `R`、`S`、`D`
+    pub state: char,
+    /// 用户态和内核态 CPU 时间之和，单位为时钟滴答
+    pub cpu_ticks: u64,
+    /// 内核调度优先级，普通进程为 20 + nice
+    pub priority: i32,
+    pub nice: i32,
+}
+
+/// 解析 `/proc/<pid>/stat`
+///
+/// 进程名（第 2 个字段）可能包含空格和括号，因此从最后一个 `)` 之后开始按空格切分
+pub fn parse_stat(content: &str) -> Option<PidStat> {
+    let close = content.rfind(')')?;
+    let name = content.get(content.find('(')? + 1..close)?;
+    let rest = &content[close + 1..];
+    // 第 3 个字段（state）起，utime、stime 是第 14、15 个字段，priority 和 nice 是第 18、19 个字段
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(PidStat {
+        name: name.to_string(),
+        state: fields.first()?.chars().next()?,
+        cpu_ticks: utime + stime,
+        priority: fields.get(15)?.parse().ok()?,
+        nice: fields.get(16)?.parse().ok()?,
+    })
+}
+
+pub fn read_stat(sysroot: &SysRoot, pid: u32) -> Option<PidStat> {
+    let content = fs::read_to_string(sysroot.path(format!("/proc/{}/stat", pid))).ok()?;
+    parse_stat(&content)
+}
+
+/// 进程的所有线程 ID，按 TID 升序
+pub fn read_task_ids(sysroot: &SysRoot, pid: u32) -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(sysroot.path(format!("/proc/{}/task", pid))) else {
+        return Vec::new();
+    };
+    let mut tids: Vec<u32> = entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .collect();
+    tids.sort_unstable();
+    tids
+}
+
+pub fn read_task_stat(sysroot: &SysRoot, pid: u32, tid: u32) -> Option<PidStat> {
+    let content =
+        fs::read_to_string(sysroot.path(format!("/proc/{}/task/{}/stat", pid, tid))).ok()?;
+    parse_stat(&content)
+}
+
+/// 每秒的时钟滴答数，用于把 `cpu_ticks` 换算成秒
+pub fn clock_ticks_per_second() -> u64 {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: sysconf 只读取系统配置
+        let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        if ticks > 0 {
+            return ticks as u64;
+        }
+    }
+    100
+}
+
+/// `/proc/<pid>/status` 中用到的字段
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PidStatus {
+    /// 实际用户 ID
+    pub uid: Option<u32>,
+    pub threads: Option<u32>,
+    /// 允许运行的 CPU 编号
+    pub cpus_allowed: Option<Vec<usize>>,
+}
+
+pub fn parse_status(content: &str) -> PidStatus {
+    let mut status = PidStatus::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "Uid" => {
+                status.uid = value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|uid| uid.parse().ok());
+            }
+            "Threads" => status.threads = value.parse().ok(),
+            "Cpus_allowed_list" => status.cpus_allowed = parse_cpu_list(value),
+            _ => {}
+        }
+    }
+    status
+}
+
+pub fn read_status(sysroot: &SysRoot, pid: u32) -> Option<PidStatus> {
+    let content = fs::read_to_string(sysroot.path(format!("/proc/{}/status", pid))).ok()?;
+    Some(parse_status(&content))
+}
+
+/// 读取 `/proc/<pid>/cwd`、`/proc/<pid>/exe` 等符号链接的目标
+pub fn read_link(sysroot: &SysRoot, pid: u32, name: &str) -> Option<String> {
+    fs::read_link(sysroot.path(format!("/proc/{}/{}", pid, name)))
+        .ok()
+        .map(|target| target.to_string_lossy().into_owned())
+}
+
+/// 读取环境变量，每项为 `KEY=value`；其他用户的进程需要 root 权限
+pub fn read_environ(sysroot: &SysRoot, pid: u32) -> Option<Vec<String>> {
+    let content = fs::read(sysroot.path(format!("/proc/{}/environ", pid))).ok()?;
+    Some(
+        content
+            .split(|&b| b == 0)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| String::from_utf8_lossy(entry).into_owned())
+            .collect(),
+    )
+}
+
+/// 打开的文件描述符数量
+pub fn count_fds(sysroot: &SysRoot, pid: u32) -> Option<usize> {
+    fs::read_dir(sysroot.path(format!("/proc/{}/fd", pid)))
+        .ok()
+        .map(|entries| entries.count())
+}
+
+/// 解析 `socket:[12345]` 形式的文件描述符链接，返回套接字的 inode
+pub fn parse_socket_link(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// 进程打开的套接字的 inode；其他用户的进程需要 root 权限
+pub fn read_socket_inodes(sysroot: &SysRoot, pid: u32) -> Option<Vec<u64>> {
+    let entries = fs::read_dir(sysroot.path(format!("/proc/{}/fd", pid))).ok()?;
+    Some(
+        entries
+            .filter_map(|entry| fs::read_link(entry.ok()?.path()).ok())
+            .filter_map(|target| parse_socket_link(&target.to_string_lossy()))
+            .collect(),
+    )
+}
+
+/// `/proc/net/tcp`、`/proc/net/tcp6` 中的一条连接
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcpConnection {
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    /// 连接状态，如 `ESTABLISHED`、`LISTEN`
+    pub state: &'static str,
+    pub inode: u64,
+}
+
+/// 解析 `/proc/net/tcp` 或 `/proc/net/tcp6`，跳过表头和无法识别的行
+pub fn parse_net_tcp(content: &str) -> Vec<TcpConnection> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            Some(TcpConnection {
+                local: parse_socket_addr(fields.get(1)?)?,
+                remote: parse_socket_addr(fields.get(2)?)?,
+                state: tcp_state(u8::from_str_radix(fields.get(3)?, 16).ok()?),
+                inode: fields.get(9)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// 读取所有 IPv4 和 IPv6 的 TCP 连接，以 inode 为键
+pub fn read_tcp_connections(sysroot: &SysRoot) -> HashMap<u64, TcpConnection> {
+    ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(sysroot.path(path)).ok())
+        .flat_map(|content| parse_net_tcp(&content))
+        // TIME_WAIT 等已不属于任何进程的连接 inode 为 0
+        .filter(|connection| connection.inode != 0)
+        .map(|connection| (connection.inode, connection))
+        .collect()
+}
+
+/// 解析 `0100007F:1F90` 形式的地址
+///
+/// 内核把网络字节序的 IP 按 32 位整数逐字输出，还原为内存中的字节即为地址
+fn parse_socket_addr(field: &str) -> Option<SocketAddr> {
+    let (ip, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut words = Vec::with_capacity(4);
+    for i in (0..ip.len()).step_by(8) {
+        let word = u32::from_str_radix(ip.get(i..i + 8)?, 16).ok()?;
+        words.extend_from_slice(&word.to_ne_bytes());
+    }
+    let ip = match words.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(words[0], words[1], words[2], words[3])),
+        16 => {
+            let octets: [u8; 16] = words.try_into().ok()?;
+            let ip = Ipv6Addr::from(octets);
+            // IPv4 映射地址按 IPv4 显示
+            ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4)
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+fn tcp_state(code: u8) -> &'static str {
+    match code {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// `/proc/<pid>/io` 中实际读写块设备的字节数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PidIo {
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+pub fn parse_io(content: &str) -> Option<PidIo> {
+    let field = |name: &str| {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|value| value.trim().parse().ok())
+    };
+    Some(PidIo {
+        read_bytes: field("read_bytes:")?,
+        write_bytes: field("write_bytes:")?,
+    })
+}
+
+pub fn read_io(sysroot: &SysRoot, pid: u32) -> Option<PidIo> {
+    let content = fs::read_to_string(sysroot.path(format!("/proc/{}/io", pid))).ok()?;
+    parse_io(&content)
+}
+
+/// `/etc/passwd` 中用户 ID 到用户名的映射
+///
+/// 只在文件的修改时间变化时重新读取，避免每次刷新进程列表都解析一遍
+#[derive(Debug, Clone, Default)]
+pub struct UserNames {
+    names: HashMap<u32, String>,
+    modified: Option<SystemTime>,
+}
+
+impl UserNames {
+    pub fn refresh(&mut self, sysroot: &SysRoot) {
+        let path = sysroot.path("/etc/passwd");
+        let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+        if modified.is_some() && modified == self.modified {
+            return;
+        }
+        self.names = fs::read_to_string(&path)
+            .map(|content| parse_passwd(&content))
+            .unwrap_or_default();
+        self.modified = modified;
+    }
+
+    pub fn get(&self, uid: u32) -> Option<&str> {
+        self.names.get(&uid).map(String::as_str)
+    }
+}
+
+pub fn parse_passwd(content: &str) -> HashMap<u32, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let uid = fields.nth(1)?.parse().ok()?;
+            Some((uid, name.to_string()))
+        })
+        .collect()
+}
+
+/// `/proc/stat` 中一行 `cpu` 的累计时间，单位为时钟滴答
+///
+/// 内核把 `guest` 计入 `user`、把 `guest_nice` 计入 `nice`；较旧的内核缺少的字段为 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    pub guest: u64,
+    pub guest_nice: u64,
+}
+
+/// `/proc/stat` 中的 CPU 时间：汇总行和各个核心
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuStat {
+    pub total: CpuTimes,
+    /// 以核心编号为下标，离线的核心不在 `/proc/stat` 中，对应位置为 `None`
+    pub cores: Vec<Option<CpuTimes>>,
+}
+
+/// 解析 `/proc/stat` 中的 `cpu` 和 `cpuN` 行，没有汇总行时返回 `None`
+pub fn parse_cpu_stat(content: &str) -> Option<CpuStat> {
+    let mut total = None;
+    let mut cores = Vec::new();
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(index) = fields.next().and_then(|label| label.strip_prefix("cpu")) else {
+            continue;
+        };
+        let mut values = [0u64; 10];
+        for (value, field) in values.iter_mut().zip(fields) {
+            *value = field.parse().ok()?;
+        }
+        let [user, nice, system, idle, iowait, irq, softirq, steal, guest, guest_nice] = values;
+        let times = CpuTimes {
+            user,
+            nice,
+            system,
+            idle,
+            iowait,
+            irq,
+            softirq,
+            steal,
+            guest,
+            guest_nice,
+        };
+        if index.is_empty() {
+            total = Some(times);
+        } else if let Ok(core) = index.parse::<usize>() {
+            if cores.len() <= core {
+                cores.resize(core + 1, None);
+            }
+            cores[core] = Some(times);
+        }
+    }
+    Some(CpuStat {
+        total: total?,
+        cores,
+    })
+}
+
+/// `/proc/stat` 中开机以来的累计计数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatCounters {
+    /// 上下文切换次数（`ctxt`）
+    pub context_switches: u64,
+    /// 中断次数（`intr` 行的第一个数为总数，之后是各个中断号）
+    pub interrupts: u64,
+}
+
+pub fn parse_stat_counters(content: &str) -> Option<StatCounters> {
+    let counter = |name: &str| {
+        content.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            (fields.next() == Some(name)).then(|| fields.next()?.parse().ok())?
+        })
+    };
+    Some(StatCounters {
+        context_switches: counter("ctxt")?,
+        interrupts: counter("intr")?,
+    })
+}
+
+/// 解析内核的 CPU 列表格式，如 `0-3,6,8-9`
+pub fn parse_cpu_list(list: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in list.split(',').filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end): (usize, usize) = (start.parse().ok()?, end.parse().ok()?);
+                cpus.extend(start..=end);
+            }
+            None => cpus.push(part.parse().ok()?),
+        }
+    }
+    Some(cpus)
+}
+
+/// 将 CPU 列表格式化为内核格式，连续的编号合并为区间
+pub fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut sorted = cpus.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut parts = Vec::new();
+    let mut iter = sorted.into_iter().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end = iter.next().unwrap_or(end);
+        }
+        if start == end {
+            parts.push(start.to_string());
+        } else {
+            parts.push(format!("{}-{}", start, end));
+        }
+    }
+    parts.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat() {
+        let stat = "1234 (tmux: server (1)) S 1 1234 1234 0 -1 4194560 2210 0 0 0 \
+                    52 31 0 0 25 5 1 0 2870 11194368 1011 18446744073709551615";
+        assert_eq!(
+            parse_stat(stat),
+            Some(PidStat {
+                name: "tmux: server (1)".to_string(),
+                state: 'S',
+                cpu_ticks: 83,
+                priority: 25,
+                nice: 5
+            })
+        );
+        assert_eq!(parse_stat("1234 (truncated) S 1"), None);
+        // `(` 在最后一个 `)` 之后或缺少 `(` 的内容不能导致越界
+        assert_eq!(parse_stat("1234 ) S 1 ("), None);
+        assert_eq!(parse_stat("1234 name) S 1"), None);
+    }
+
+    #[test]
+    fn test_parse_status_io_and_passwd() {
+        let status = "Name:\tbash\nUid:\t1000\t1000\t1000\t1000\nThreads:\t3\n\
+                      Cpus_allowed_list:\t0-1\n";
+        assert_eq!(
+            parse_status(status),
+            PidStatus {
+                uid: Some(1000),
+                threads: Some(3),
+                cpus_allowed: Some(vec![0, 1]),
+            }
+        );
+
+        let io = "rchar: 100\nwchar: 50\nread_bytes: 4096\nwrite_bytes: 8192\n";
+        assert_eq!(
+            parse_io(io),
+            Some(PidIo {
+                read_bytes: 4096,
+                write_bytes: 8192
+            })
+        );
+
+        let users = parse_passwd("root:x:0:0:root:/root:/bin/bash\nbroken\n");
+        assert_eq!(users.get(&0).map(String::as_str), Some("root"));
+        assert_eq!(users.len(), 1);
+    }
+
+    #[test]
+    fn test_user_names_from_fixture() {
+        let mut users = UserNames::default();
+        users.refresh(&crate::data::sysroot::fixture());
+        assert_eq!(users.get(110), Some("postgres"));
+        assert!(users.modified.is_some());
+
+        // 修改时间没有变化时保留已读取的内容
+        users.names.insert(1000, "cached".to_string());
+        users.refresh(&crate::data::sysroot::fixture());
+        assert_eq!(users.get(1000), Some("cached"));
+
+        users.refresh(&SysRoot::new("/nonexistent-sysroot"));
+        assert_eq!(users.get(0), None);
+    }
+
+    #[test]
+    fn test_parse_socket_link() {
+        assert_eq!(parse_socket_link("socket:[937]"), Some(937));
+        assert_eq!(parse_socket_link("pipe:[937]"), None);
+        assert_eq!(parse_socket_link("/dev/null"), None);
+    }
+
+    /// 样例取自小端序主机上的 `/proc/net/tcp{,6}`
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_parse_net_tcp() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+                   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 937 1 0 100 0 0 10 0\n\
+                   1: 0F02000A:C350 2A02000A:01BB 01 00000000:00000000 00:00000000 00000000  1000        0 1024 1 0 20 4 30 10 -1\n\
+                   2: 0F02000A:C352 2A02000A:01BB 06 00000000:00000000 03:00000F8B 00000000     0        0 0 3 0\n\
+                   3: broken\n";
+        let connections = parse_net_tcp(tcp);
+        assert_eq!(connections.len(), 3);
+        assert_eq!(
+            connections[0],
+            TcpConnection {
+                local: "127.0.0.1:8080".parse().unwrap(),
+                remote: "0.0.0.0:0".parse().unwrap(),
+                state: "LISTEN",
+                inode: 937,
+            }
+        );
+        assert_eq!(connections[1].local.to_string(), "10.0.2.15:50000");
+        assert_eq!(connections[1].remote.to_string(), "10.0.2.42:443");
+        assert_eq!(connections[1].state, "ESTABLISHED");
+        assert_eq!(connections[2].state, "TIME_WAIT");
+
+        let tcp6 = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+                    0: 00000000000000000000000001000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 662 1 0 100 0 0 10 0\n\
+                    1: B80D0120000000000000000001000000:01BB 0000000000000000FFFF00000100007F:D431 01 00000000:00000000 00:00000000 00000000     0        0 700 1 0 20 4 30 10 -1\n";
+        let connections = parse_net_tcp(tcp6);
+        assert_eq!(connections[0].local.to_string(), "[::1]:22");
+        assert_eq!(connections[1].local.to_string(), "[2001:db8::1]:443");
+        // IPv4 映射地址按 IPv4 显示
+        assert_eq!(connections[1].remote.to_string(), "127.0.0.1:54321");
+    }
+
+    #[test]
+    fn test_parse_cpu_stat_and_counters() {
+        let content = "cpu  400 10 200 3000 50 5 15 20 30 0\n\
+                       cpu0 200 5 100 1500 25 3 8 10 15 0\n\
+                       cpu2 200 5 100 1500 25 2 7 10 15 0\n\
+                       intr 12345 0 0\n\
+                       ctxt 67890\n";
+        let stat = parse_cpu_stat(content).unwrap();
+        assert_eq!(stat.total.user, 400);
+        assert_eq!(stat.total.steal, 20);
+        assert_eq!(stat.total.guest, 30);
+        // cpu1 离线
+        assert_eq!(stat.cores.len(), 3);
+        assert_eq!(stat.cores[0].map(|times| times.irq), Some(3));
+        assert_eq!(stat.cores[1], None);
+        assert_eq!(stat.cores[2].map(|times| times.softirq), Some(7));
+        assert_eq!(
+            parse_stat_counters(content),
+            Some(StatCounters {
+                context_switches: 67890,
+                interrupts: 12345
+            })
+        );
+
+        // 较旧的内核只有前 4 个字段
+        let old = parse_cpu_stat("cpu 1 2 3 4\n").unwrap();
+        assert_eq!(old.total.idle, 4);
+        assert_eq!(old.total.iowait, 0);
+        assert_eq!(parse_cpu_stat("intr 1\n"), None);
+    }
+
+    #[test]
+    fn test_cpu_list_round_trip() {
+        assert_eq!(parse_cpu_list("0-3,6,8-9"), Some(vec![0, 1, 2, 3, 6, 8, 9]));
+        assert_eq!(parse_cpu_list(""), Some(vec![]));
+        assert_eq!(parse_cpu_list("0-x"), None);
+        assert_eq!(format_cpu_list(&[9, 0, 1, 2, 3, 6, 8]), "0-3,6,8-9");
+        assert_eq!(format_cpu_list(&[]), "");
+    }
+}
@@ -0,0 +1,146 @@
+use std::fs;
+use std::time::{Duration, Instant};
+use sysinfo::{
+    CpuRefreshKind, Disks, MemoryRefreshKind, Networks, ProcessRefreshKind, RefreshKind, System,
+    UpdateKind,
+};
+
+use super::SysRoot;
+
+/// 一次采集周期中需要刷新的数据种类
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SampleNeeds {
+    pub cpu: bool,
+    pub memory: bool,
+    pub processes: bool,
+    pub disks: bool,
+    pub networks: bool,
+    /// `/proc/stat`，CPU 时间占比和负载页的计数都从这里解析
+    pub proc_stat: bool,
+}
+
+/// 所有数据模块共享的系统采样器
+///
+/// 只持有一个 `System` 实例，每个周期按 [`SampleNeeds`] 构造精确的 `RefreshKind`，
+/// 避免重复扫描进程表。数据模块只读取采样结果，不再自行刷新
+pub struct Sampler {
+    system: System,
+    disks: Disks,
+    networks: Networks,
+    sysroot: SysRoot,
+    /// 上一次刷新进程的时间
+    processes_refreshed_at: Option<Instant>,
+    /// 最近两次刷新进程的间隔
+    process_interval: Option<Duration>,
+    /// 最近一次读取的 `/proc/stat` 内容，每个周期只读一次供多个模块解析
+    proc_stat: Option<String>,
+}
+
+impl Sampler {
+    /// 创建采样器，`/proc` 和 `/sys` 的根目录从 `TUI_DASH_SYSROOT` 读取
+    pub fn new() -> Self {
+        Self::with_sysroot(SysRoot::from_env())
+    }
+
+    pub fn with_sysroot(sysroot: SysRoot) -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::new()
+                .with_cpu(CpuRefreshKind::everything())
+                .with_memory(MemoryRefreshKind::everything()),
+        );
+
+        let proc_stat = Self::read_proc_stat(&sysroot);
+        Self {
+            system,
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+            sysroot,
+            processes_refreshed_at: None,
+            process_interval: None,
+            proc_stat,
+        }
+    }
+
+    /// 按需刷新系统数据
+    pub fn refresh(&mut self, needs: SampleNeeds) {
+        let mut kind = RefreshKind::new();
+        if needs.cpu {
+            kind = kind.with_cpu(CpuRefreshKind::new().with_cpu_usage().with_frequency());
+        }
+        if needs.memory || needs.processes {
+            // 进程内存占比依赖系统总内存
+            kind = kind.with_memory(MemoryRefreshKind::everything());
+        }
+        if needs.processes {
+            kind = kind.with_processes(Self::process_refresh_kind());
+        }
+        self.system.refresh_specifics(kind);
+
+        if needs.processes {
+            let now = Instant::now();
+            self.process_interval = self.processes_refreshed_at.map(|last| now - last);
+            self.processes_refreshed_at = Some(now);
+        }
+
+        if needs.disks {
+            self.disks.refresh();
+        }
+        if needs.networks {
+            self.networks.refresh();
+        }
+        if needs.proc_stat {
+            self.proc_stat = Self::read_proc_stat(&self.sysroot);
+        }
+    }
+
+    fn read_proc_stat(sysroot: &SysRoot) -> Option<String> {
+        fs::read_to_string(sysroot.path("/proc/stat")).ok()
+    }
+
+    fn process_refresh_kind() -> ProcessRefreshKind {
+        ProcessRefreshKind::new()
+            .with_cpu()
+            .with_memory()
+            .with_disk_usage()
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            // 按可执行文件分组需要 exe，sysinfo 默认不读取
+            .with_exe(UpdateKind::OnlyIfNotSet)
+    }
+
+    pub fn system(&self) -> &System {
+        &self.system
+    }
+
+    pub fn disks(&self) -> &Disks {
+        &self.disks
+    }
+
+    pub fn networks(&self) -> &Networks {
+        &self.networks
+    }
+
+    pub fn sysroot(&self) -> &SysRoot {
+        &self.sysroot
+    }
+
+    /// 最近一次读取的 `/proc/stat` 内容，不可读时（非 Linux）为 `None`
+    pub fn proc_stat(&self) -> Option<&str> {
+        self.proc_stat.as_deref()
+    }
+
+    /// 上一次刷新进程的时间，尚未刷新过时为 `None`
+    pub fn processes_refreshed_at(&self) -> Option<Instant> {
+        self.processes_refreshed_at
+    }
+
+    /// 最近两次刷新进程的间隔，用于把 sysinfo 的磁盘读写增量换算为速率
+    pub fn process_interval(&self) -> Option<Duration> {
+        self.process_interval
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
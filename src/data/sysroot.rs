@@ -0,0 +1,72 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// 指定文件系统根目录的环境变量
+pub const SYSROOT_ENV: &str = "TUI_DASH_SYSROOT";
+
+/// `/proc` 和 `/sys` 读取所使用的文件系统根目录
+///
+/// 默认为 `/`。设置 `TUI_DASH_SYSROOT` 后，磁盘、挂载点、温度和电池信息从该目录下的
+/// 同名路径读取，便于使用固定的测试数据或读取容器中挂载的宿主机 `/proc`、`/sys`。
+/// 通过 sysinfo 采集的数据（CPU、内存、进程列表、网络）不受影响，
+/// 进程的 nice 值和 CPU 亲和性等补充信息仍从该目录下的 `/proc/<pid>` 读取
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysRoot {
+    root: PathBuf,
+}
+
+impl SysRoot {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// 从 `TUI_DASH_SYSROOT` 读取根目录，未设置或为空时使用 `/`
+    pub fn from_env() -> Self {
+        env::var_os(SYSROOT_ENV)
+            .filter(|root| !root.is_empty())
+            .map(Self::new)
+            .unwrap_or_default()
+    }
+
+    /// 将绝对路径（如 `/proc/diskstats`）映射到根目录下
+    pub fn path(&self, absolute: impl AsRef<Path>) -> PathBuf {
+        let absolute = absolute.as_ref();
+        self.root
+            .join(absolute.strip_prefix("/").unwrap_or(absolute))
+    }
+}
+
+impl Default for SysRoot {
+    fn default() -> Self {
+        Self::new("/")
+    }
+}
+
+/// 测试用的 `/proc`、`/sys` 固定数据，位于 `tests/fixtures/sysroot`
+#[cfg(test)]
+pub fn fixture() -> SysRoot {
+    SysRoot::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sysroot"))
+}
+
+/// 在 [`fixture`] 之后的第二次采样，位于 `tests/fixtures/sysroot-next`，用于测试速率计算
+#[cfg(test)]
+pub fn fixture_next() -> SysRoot {
+    SysRoot::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sysroot-next"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_is_joined_under_root() {
+        assert_eq!(
+            SysRoot::default().path("/proc/diskstats"),
+            PathBuf::from("/proc/diskstats")
+        );
+        assert_eq!(
+            SysRoot::new("/tmp/fixture").path("/sys/class/thermal"),
+            PathBuf::from("/tmp/fixture/sys/class/thermal")
+        );
+    }
+}
@@ -1,8 +1,12 @@
+use super::{Collector, CollectorError, Sampler, SysRoot};
+use crate::config::Config;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemperatureSensor {
     pub name: String,
     pub current_temp: f64,
@@ -10,61 +14,53 @@ pub struct TemperatureSensor {
     pub critical_temp: Option<f64>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemperatureData {
     pub sensors: Vec<TemperatureSensor>,
     pub has_warning: bool,
+    #[serde(skip)]
+    warning_celsius: f64,
+    #[serde(skip)]
+    interval: Duration,
 }
 
 impl TemperatureData {
-    pub fn new() -> Result<Self> {
+    pub fn new(config: &Config) -> Result<Self> {
         Ok(Self {
             sensors: Vec::new(),
             has_warning: false,
+            warning_celsius: config.alerts.temperature_celsius,
+            interval: Duration::from_millis(config.general.update_interval_ms),
         })
     }
 
     #[cfg(target_os = "linux")]
-    pub fn update(&mut self) -> Result<()> {
+    fn read_sensors(&mut self, sysroot: &SysRoot) -> Result<(), CollectorError> {
         self.sensors.clear();
         self.has_warning = false;
 
-        let thermal_path = Path::new("/sys/class/thermal");
+        let thermal_path = sysroot.path("/sys/class/thermal");
 
         if thermal_path.exists() {
-            for entry in fs::read_dir(thermal_path)? {
-                let entry = entry?;
-                let zone_path = entry.path();
-
+            let mut zones = fs::read_dir(&thermal_path)
+                .map_err(|e| CollectorError::read(&thermal_path, e))?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| CollectorError::read(&thermal_path, e))?;
+            zones.sort();
+
+            for zone_path in zones {
                 if zone_path.join("temp").exists() {
                     if let Some(name) = zone_path.file_name().and_then(|n| n.to_str()) {
-                        let temp_path = zone_path.join("temp");
-                        let trip_path = zone_path.join("trip_point_0_temp");
-
-                        let current_temp = fs::read_to_string(&temp_path)
-                            .ok()
-                            .and_then(|s| s.trim().parse::<i64>().ok())
-                            .map(|t| t as f64 / 1000.0)
-                            .unwrap_or(0.0);
-
-                        let max_temp = fs::read_to_string(&trip_path)
-                            .ok()
-                            .and_then(|s| s.trim().parse::<i64>().ok())
-                            .filter(|&t| t > 0)
-                            .map(|t| t as f64 / 1000.0)
+                        let current_temp =
+                            read_millidegrees(&zone_path.join("temp")).unwrap_or(0.0);
+
+                        let max_temp = read_millidegrees(&zone_path.join("trip_point_0_temp"))
+                            .filter(|&t| t > 0.0)
                             .unwrap_or(100.0);
 
-                        let critical_temp = zone_path
-                            .join("trip_point_1_temp")
-                            .exists()
-                            .then(|| {
-                                fs::read_to_string(zone_path.join("trip_point_1_temp"))
-                                    .ok()
-                                    .and_then(|s| s.trim().parse::<i64>().ok())
-                                    .filter(|&t| t > 0)
-                                    .map(|t| t as f64 / 1000.0)
-                            })
-                            .flatten();
+                        let critical_temp = read_millidegrees(&zone_path.join("trip_point_1_temp"))
+                            .filter(|&t| t > 0.0);
 
                         let sensor = TemperatureSensor {
                             name: name.to_string(),
@@ -73,7 +69,7 @@ impl TemperatureData {
                             critical_temp,
                         };
 
-                        if sensor.current_temp >= 70.0 {
+                        if sensor.current_temp >= self.warning_celsius {
                             self.has_warning = true;
                         }
 
@@ -87,15 +83,16 @@ impl TemperatureData {
     }
 
     #[cfg(target_os = "windows")]
-    pub fn update(&mut self) -> Result<()> {
+    fn read_sensors(&mut self, _sysroot: &SysRoot) -> Result<(), CollectorError> {
         use windows::core::*;
         use wmi::{COMLibrary, WMIConnection};
 
         self.sensors.clear();
         self.has_warning = false;
 
-        let com_con = COMLibrary::new()?;
-        let wmi_con = WMIConnection::new(com_con)?;
+        let com_con = COMLibrary::new().map_err(|e| CollectorError::Platform(e.to_string()))?;
+        let wmi_con =
+            WMIConnection::new(com_con).map_err(|e| CollectorError::Platform(e.to_string()))?;
 
         if let Ok(thermal_zones) = wmi_con.query::<MSAcpi_ThermalZoneTemperature>() {
             for (index, zone) in thermal_zones.iter().enumerate() {
@@ -115,7 +112,7 @@ impl TemperatureData {
                             critical_temp,
                         };
 
-                        if sensor.current_temp >= 70.0 {
+                        if sensor.current_temp >= self.warning_celsius {
                             self.has_warning = true;
                         }
 
@@ -138,14 +135,37 @@ impl TemperatureData {
     }
 
     #[cfg(not(any(target_os = "linux", target_os = "windows")))]
-    pub fn update(&mut self) -> Result<()> {
+    fn read_sensors(&mut self, _sysroot: &SysRoot) -> Result<(), CollectorError> {
         Ok(())
     }
 }
 
+/// 读取以千分之一摄氏度为单位的温度文件
+#[cfg(target_os = "linux")]
+fn read_millidegrees(path: &Path) -> Option<f64> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .map(|t| t as f64 / 1000.0)
+}
+
+impl Collector for TemperatureData {
+    fn name(&self) -> &'static str {
+        "temperature"
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn update(&mut self, sampler: &Sampler) -> Result<(), CollectorError> {
+        self.read_sensors(sampler.sysroot())
+    }
+}
+
 impl Default for TemperatureData {
     fn default() -> Self {
-        Self::new().expect("Failed to create default TemperatureData")
+        Self::new(&Config::default()).expect("Failed to create default TemperatureData")
     }
 }
 
@@ -153,4 +173,52 @@ impl Default for TemperatureData {
 #[derive(serde::Deserialize)]
 struct MSAcpi_ThermalZoneTemperature {
     CurrentTemperature: Option<u32>,
-}
\ No newline at end of file
+}
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::data::sysroot::fixture;
+
+    #[test]
+    fn test_read_sensors_from_fixture() {
+        let mut data = TemperatureData::default();
+        data.read_sensors(&fixture()).unwrap();
+
+        let names: Vec<&str> = data.sensors.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["thermal_zone0", "thermal_zone1", "thermal_zone2"]);
+
+        let zone0 = &data.sensors[0];
+        assert_eq!(zone0.current_temp, 45.0);
+        assert_eq!(zone0.max_temp, 90.0);
+        assert_eq!(zone0.critical_temp, Some(105.0));
+    }
+
+    #[test]
+    fn test_missing_or_invalid_trip_points_use_defaults() {
+        let mut data = TemperatureData::default();
+        data.read_sensors(&fixture()).unwrap();
+
+        // 没有 trip point
+        let zone1 = &data.sensors[1];
+        assert_eq!(zone1.max_temp, 100.0);
+        assert_eq!(zone1.critical_temp, None);
+
+        // trip point 为 0 或负数
+        let zone2 = &data.sensors[2];
+        assert_eq!(zone2.max_temp, 100.0);
+        assert_eq!(zone2.critical_temp, None);
+    }
+
+    #[test]
+    fn test_warning_threshold() {
+        let mut data = TemperatureData::default();
+        data.read_sensors(&fixture()).unwrap();
+        assert!(data.has_warning);
+
+        let mut config = Config::default();
+        config.alerts.temperature_celsius = 80.0;
+        let mut data = TemperatureData::new(&config).unwrap();
+        data.read_sensors(&fixture()).unwrap();
+        assert!(!data.has_warning);
+    }
+}
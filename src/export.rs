@@ -0,0 +1,231 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::ExportFormat;
+use crate::data::{history::HistoryData, SystemHistory};
+
+/// 一条历史曲线及其在导出文件中的名称
+struct Series<'a> {
+    /// CSV 列名
+    column: String,
+    /// Influx measurement
+    measurement: &'static str,
+    /// Influx tag
+    tag: Option<(&'static str, String)>,
+    /// Influx field
+    field: &'static str,
+    data: &'a HistoryData<f32>,
+}
+
+fn series(history: &SystemHistory) -> Vec<Series<'_>> {
+    let mut series = vec![Series {
+        column: "cpu_total_percent".to_string(),
+        measurement: "cpu",
+        tag: Some(("core", "total".to_string())),
+        field: "usage_percent",
+        data: &history.cpu.global_usage,
+    }];
+    for (core, data) in history.cpu.core_usage.iter().enumerate() {
+        series.push(Series {
+            column: format!("cpu{}_percent", core),
+            measurement: "cpu",
+            tag: Some(("core", core.to_string())),
+            field: "usage_percent",
+            data,
+        });
+    }
+
+    let memory = &history.memory;
+    let network = &history.network;
+    for (column, measurement, field, data) in [
+        (
+            "memory_used_percent",
+            "memory",
+            "used_percent",
+            &memory.usage_percent,
+        ),
+        ("memory_used_gb", "memory", "used_gb", &memory.used_gb),
+        (
+            "swap_used_percent",
+            "memory",
+            "swap_used_percent",
+            &memory.swap_percent,
+        ),
+        (
+            "network_rx_mb_per_sec",
+            "network",
+            "rx_mb_per_sec",
+            &network.receive_speed,
+        ),
+        (
+            "network_tx_mb_per_sec",
+            "network",
+            "tx_mb_per_sec",
+            &network.transmit_speed,
+        ),
+    ] {
+        series.push(Series {
+            column: column.to_string(),
+            measurement,
+            tag: None,
+            field,
+            data,
+        });
+    }
+    series
+}
+
+/// 以指定格式写出历史数据
+///
+/// CSV 每个采集时间一行、每条曲线一列，缺失的点留空；
+/// Influx 行协议每个数据点一行，时间戳为纳秒
+pub fn write_history(
+    history: &SystemHistory,
+    format: ExportFormat,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let series = series(history);
+    match format {
+        ExportFormat::Csv => write_csv(&series, out),
+        ExportFormat::Influx => write_influx(&series, out),
+    }
+}
+
+fn write_csv(series: &[Series], out: &mut impl Write) -> io::Result<()> {
+    let mut rows: BTreeMap<DateTime<Local>, Vec<Option<f32>>> = BTreeMap::new();
+    for (column, s) in series.iter().enumerate() {
+        for (timestamp, value) in s.data.iter() {
+            rows.entry(*timestamp)
+                .or_insert_with(|| vec![None; series.len()])[column] = Some(*value);
+        }
+    }
+
+    write!(out, "timestamp")?;
+    for s in series {
+        write!(out, ",{}", s.column)?;
+    }
+    writeln!(out)?;
+
+    for (timestamp, values) in rows {
+        write!(
+            out,
+            "{}",
+            timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, false)
+        )?;
+        for value in values {
+            match value {
+                Some(value) => write!(out, ",{}", value)?,
+                None => write!(out, ",")?,
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn write_influx(series: &[Series], out: &mut impl Write) -> io::Result<()> {
+    let mut lines: Vec<(DateTime<Local>, String)> = Vec::new();
+    for s in series {
+        let key = match &s.tag {
+            Some((tag, value)) => format!("{},{}={}", s.measurement, tag, value),
+            None => s.measurement.to_string(),
+        };
+        for (timestamp, value) in s.data.iter() {
+            lines.push((*timestamp, format!("{} {}={}", key, s.field, value)));
+        }
+    }
+    // 按时间排序，便于分批写入 InfluxDB
+    lines.sort_by_key(|(timestamp, _)| *timestamp);
+
+    for (timestamp, line) in lines {
+        writeln!(
+            out,
+            "{} {}",
+            line,
+            timestamp.timestamp_nanos_opt().unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}
+
+/// 写出到文件
+///
+/// # Errors
+///
+/// 文件无法创建或写入时返回错误
+pub fn export_to_file(history: &SystemHistory, format: ExportFormat, path: &Path) -> Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    write_history(history, format, &mut out)
+        .and_then(|()| out.flush())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// 界面中导出时使用的文件名，包含当前时间
+pub fn default_file_name(format: ExportFormat) -> PathBuf {
+    let extension = match format {
+        ExportFormat::Csv => "csv",
+        ExportFormat::Influx => "lp",
+    };
+    PathBuf::from(format!(
+        "tui-dash-history-{}.{}",
+        Local::now().format("%Y%m%d-%H%M%S"),
+        extension
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn history() -> SystemHistory {
+        let mut history = SystemHistory::new(2, 10);
+        let t0 = Local.timestamp_opt(1_700_000_000, 0).unwrap();
+        let t1 = Local.timestamp_opt(1_700_000_001, 0).unwrap();
+        history.cpu.update(t0, 10.0, &[5.0, 15.0]);
+        history.cpu.update(t1, 20.0, &[10.0, 30.0]);
+        history.memory.update(t0, 50.0, 4.0, 0.0);
+        history.network.update(t1, 1.5, 0.25);
+        history
+    }
+
+    fn export(format: ExportFormat) -> String {
+        let mut out = Vec::new();
+        write_history(&history(), format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let csv = export(ExportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "timestamp,cpu_total_percent,cpu0_percent,cpu1_percent,memory_used_percent,\
+             memory_used_gb,swap_used_percent,network_rx_mb_per_sec,network_tx_mb_per_sec"
+        );
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(",10,5,15,50,4,0,,"));
+        assert!(lines[2].ends_with(",20,10,30,,,,1.5,0.25"));
+    }
+
+    #[test]
+    fn test_write_influx() {
+        let influx = export(ExportFormat::Influx);
+        let lines: Vec<&str> = influx.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(
+            lines[0],
+            "cpu,core=total usage_percent=10 1700000000000000000"
+        );
+        assert!(lines.contains(&"cpu,core=1 usage_percent=30 1700000001000000000"));
+        assert!(lines.contains(&"memory used_gb=4 1700000000000000000"));
+        assert!(lines.contains(&"network tx_mb_per_sec=0.25 1700000001000000000"));
+    }
+}
@@ -0,0 +1,560 @@
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Write as _};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::collector::Snapshot;
+use crate::data::load::PRESSURE_RESOURCES;
+use crate::data::{CpuBreakdown, DiskInfo, InterfaceInfo};
+
+/// Prometheus 文本格式的 Content-Type
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 单个连接的读写超时，避免慢客户端阻塞后续抓取
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prometheus 指标导出服务
+///
+/// 在独立线程中监听 HTTP 请求，`GET /metrics` 返回最新快照生成的指标，
+/// 数据来自采集线程发布的快照，不额外触发采集
+pub struct MetricsServer {
+    addr: SocketAddr,
+}
+
+impl MetricsServer {
+    /// 绑定地址并在后台线程中开始处理请求
+    ///
+    /// # Errors
+    ///
+    /// 地址无法绑定时返回错误
+    pub fn spawn(addr: SocketAddr, snapshots: watch::Receiver<Arc<Snapshot>>) -> Result<Self> {
+        let listener =
+            TcpListener::bind(addr).with_context(|| format!("failed to bind {}", addr))?;
+        let addr = listener.local_addr()?;
+
+        std::thread::Builder::new()
+            .name("tui-dash-exporter".to_string())
+            .spawn(move || serve(listener, snapshots))
+            .context("failed to spawn exporter thread")?;
+
+        Ok(Self { addr })
+    }
+
+    /// 实际监听的地址（绑定端口 0 时为系统分配的端口）
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+fn serve(listener: TcpListener, snapshots: watch::Receiver<Arc<Snapshot>>) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        // 单个连接出错（客户端断开、超时）不影响后续请求
+        let _ = handle_connection(stream, &snapshots);
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    snapshots: &watch::Receiver<Arc<Snapshot>>,
+) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // 读完请求头，客户端才会开始等待响应
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header != "\r\n" && header != "\n" {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => {
+            let snapshot = snapshots.borrow().clone();
+            ("200 OK", CONTENT_TYPE, render(&snapshot))
+        }
+        ("GET", _) => ("404 Not Found", "text/plain", "see /metrics\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "only GET is supported\n".to_string(),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// 将快照转换为 Prometheus 文本格式
+pub fn render(snapshot: &Snapshot) -> String {
+    let mut out = MetricsWriter::default();
+
+    out.family(
+        "tui_dash_cpu_usage_percent",
+        "gauge",
+        "Global CPU usage in percent.",
+    );
+    out.sample(
+        "tui_dash_cpu_usage_percent",
+        &[],
+        snapshot.cpu.global_cpu_usage,
+    );
+    out.family(
+        "tui_dash_cpu_core_usage_percent",
+        "gauge",
+        "Per-core CPU usage in percent.",
+    );
+    for (core, usage) in snapshot.cpu.core_usages.iter().enumerate() {
+        out.sample(
+            "tui_dash_cpu_core_usage_percent",
+            &[("core", &core.to_string())],
+            *usage,
+        );
+    }
+    if let Some(breakdown) = &snapshot.cpu.breakdown {
+        out.family(
+            "tui_dash_cpu_time_percent",
+            "gauge",
+            "Share of CPU time per mode since the previous sample, in percent.",
+        );
+        for (mode, value) in CpuBreakdown::NAMES.iter().zip(breakdown.values()) {
+            out.sample("tui_dash_cpu_time_percent", &[("mode", mode)], value);
+        }
+    }
+
+    let load = &snapshot.load;
+    out.family(
+        "tui_dash_load_average",
+        "gauge",
+        "System load average over 1, 5 and 15 minutes.",
+    );
+    for (period, value) in ["1m", "5m", "15m"].iter().zip(load.load_average) {
+        out.sample("tui_dash_load_average", &[("period", period)], value);
+    }
+    out.family(
+        "tui_dash_uptime_seconds",
+        "gauge",
+        "Time since boot in seconds.",
+    );
+    out.sample("tui_dash_uptime_seconds", &[], load.uptime_secs);
+    for (name, help, value) in [
+        (
+            "tui_dash_tasks_running",
+            "Runnable tasks.",
+            load.running_tasks.map(f64::from),
+        ),
+        (
+            "tui_dash_tasks_total",
+            "Total tasks including threads.",
+            load.total_tasks.map(f64::from),
+        ),
+        (
+            "tui_dash_context_switches_per_second",
+            "Context switches per second since the previous sample.",
+            load.context_switches_per_sec,
+        ),
+        (
+            "tui_dash_interrupts_per_second",
+            "Interrupts per second since the previous sample.",
+            load.interrupts_per_sec,
+        ),
+    ] {
+        if let Some(value) = value {
+            out.family(name, "gauge", help);
+            out.sample(name, &[], value);
+        }
+    }
+    if load.pressure.iter().any(Option::is_some) {
+        out.family(
+            "tui_dash_pressure_percent",
+            "gauge",
+            "Pressure stall information: share of time tasks were stalled on a resource, in percent.",
+        );
+        for (resource, pressure) in PRESSURE_RESOURCES.iter().zip(&load.pressure) {
+            let Some(pressure) = pressure else {
+                continue;
+            };
+            let kinds = [("some", Some(pressure.some)), ("full", pressure.full)];
+            for (kind, averages) in kinds {
+                for (window, value) in ["10s", "60s", "300s"]
+                    .iter()
+                    .zip(averages.into_iter().flatten())
+                {
+                    out.sample(
+                        "tui_dash_pressure_percent",
+                        &[("resource", resource), ("kind", kind), ("window", window)],
+                        value,
+                    );
+                }
+            }
+        }
+    }
+
+    let mem = &snapshot.memory;
+    for (name, help, value) in [
+        (
+            "tui_dash_memory_total_bytes",
+            "Total physical memory in bytes.",
+            mem.total_memory,
+        ),
+        (
+            "tui_dash_memory_used_bytes",
+            "Used physical memory in bytes.",
+            mem.used_memory,
+        ),
+        (
+            "tui_dash_swap_total_bytes",
+            "Total swap space in bytes.",
+            mem.total_swap,
+        ),
+        (
+            "tui_dash_swap_used_bytes",
+            "Used swap space in bytes.",
+            mem.used_swap,
+        ),
+    ] {
+        out.family(name, "gauge", help);
+        out.sample(name, &[], value);
+    }
+
+    render_disks(&mut out, &snapshot.disk.disks);
+
+    let interfaces = &snapshot.network.interfaces;
+    fn iface(i: &InterfaceInfo) -> &str {
+        &i.name
+    }
+    out.series(
+        "tui_dash_network_receive_bytes_total",
+        "counter",
+        "Bytes received since boot.",
+        interfaces,
+        ("interface", iface),
+        |i| i.received,
+    );
+    out.series(
+        "tui_dash_network_transmit_bytes_total",
+        "counter",
+        "Bytes transmitted since boot.",
+        interfaces,
+        ("interface", iface),
+        |i| i.transmitted,
+    );
+    out.series(
+        "tui_dash_network_receive_bytes_per_second",
+        "gauge",
+        "Receive rate in bytes per second.",
+        interfaces,
+        ("interface", iface),
+        |i| i.rx_speed,
+    );
+    out.series(
+        "tui_dash_network_transmit_bytes_per_second",
+        "gauge",
+        "Transmit rate in bytes per second.",
+        interfaces,
+        ("interface", iface),
+        |i| i.tx_speed,
+    );
+
+    let sensors = &snapshot.temperature.sensors;
+    if !sensors.is_empty() {
+        out.family(
+            "tui_dash_temperature_celsius",
+            "gauge",
+            "Current sensor temperature in degrees Celsius.",
+        );
+        // 同名的传感器很常见（如多个 `acpitz temp1`），用 `index` 标签区分同名传感器的出现次序
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        let indices: Vec<String> = sensors
+            .iter()
+            .map(|sensor| {
+                let count = occurrences.entry(sensor.name.as_str()).or_default();
+                *count += 1;
+                (*count - 1).to_string()
+            })
+            .collect();
+        for (sensor, index) in sensors.iter().zip(&indices) {
+            out.sample(
+                "tui_dash_temperature_celsius",
+                &[("sensor", &sensor.name), ("index", index)],
+                sensor.current_temp,
+            );
+        }
+        out.family(
+            "tui_dash_temperature_critical_celsius",
+            "gauge",
+            "Critical trip point in degrees Celsius.",
+        );
+        for (sensor, index) in sensors.iter().zip(&indices) {
+            if let Some(critical) = sensor.critical_temp {
+                out.sample(
+                    "tui_dash_temperature_critical_celsius",
+                    &[("sensor", &sensor.name), ("index", index)],
+                    critical,
+                );
+            }
+        }
+    }
+
+    out.family(
+        "tui_dash_collector_up",
+        "gauge",
+        "Whether the last run of a collector succeeded.",
+    );
+    for status in &snapshot.collectors {
+        out.sample(
+            "tui_dash_collector_up",
+            &[("collector", status.name)],
+            u8::from(status.last_error.is_none()),
+        );
+    }
+
+    out.finish()
+}
+
+/// 每个挂载点的容量和读写速率
+fn render_disks(out: &mut MetricsWriter, disks: &[DiskInfo]) {
+    fn mount(d: &DiskInfo) -> &str {
+        &d.mount_point
+    }
+    out.series(
+        "tui_dash_disk_total_bytes",
+        "gauge",
+        "Filesystem size in bytes.",
+        disks,
+        ("mountpoint", mount),
+        |d| d.total,
+    );
+    out.series(
+        "tui_dash_disk_used_bytes",
+        "gauge",
+        "Used filesystem space in bytes.",
+        disks,
+        ("mountpoint", mount),
+        |d| d.used,
+    );
+    out.series(
+        "tui_dash_disk_read_bytes_per_second",
+        "gauge",
+        "Disk read rate in bytes per second.",
+        disks,
+        ("mountpoint", mount),
+        |d| d.read_speed,
+    );
+    out.series(
+        "tui_dash_disk_write_bytes_per_second",
+        "gauge",
+        "Disk write rate in bytes per second.",
+        disks,
+        ("mountpoint", mount),
+        |d| d.write_speed,
+    );
+}
+
+/// 按 Prometheus 文本格式拼接指标
+///
+/// 同一指标中出现重复的标签组合时 Prometheus 会拒绝整次抓取，因此重复的样本只保留第一条，
+/// 例如绑定挂载使同一个挂载点出现多次
+#[derive(Default)]
+struct MetricsWriter {
+    out: String,
+    seen: HashSet<String>,
+}
+
+impl MetricsWriter {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl SampleValue) {
+        let mut series = name.to_string();
+        if !labels.is_empty() {
+            series.push('{');
+            for (i, (key, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    series.push(',');
+                }
+                let _ = write!(series, "{}=\"{}\"", key, escape_label(value));
+            }
+            series.push('}');
+        }
+        if self.seen.contains(&series) {
+            return;
+        }
+        let _ = writeln!(self.out, "{} {}", series, value.format());
+        self.seen.insert(series);
+    }
+
+    /// 输出一个指标族，每个元素一条样本，标签值由 `label` 从元素中取出
+    fn series<T, V: SampleValue>(
+        &mut self,
+        name: &str,
+        kind: &str,
+        help: &str,
+        items: &[T],
+        label: (&str, impl Fn(&T) -> &str),
+        value: impl Fn(&T) -> V,
+    ) {
+        self.family(name, kind, help);
+        let (key, label_value) = label;
+        for item in items {
+            self.sample(name, &[(key, label_value(item))], value(item));
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// 样本值的文本格式
+trait SampleValue {
+    fn format(&self) -> String;
+}
+
+impl SampleValue for u8 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SampleValue for i32 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SampleValue for u64 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl SampleValue for f32 {
+    fn format(&self) -> String {
+        format_float(f64::from(*self), self)
+    }
+}
+
+impl SampleValue for f64 {
+    fn format(&self) -> String {
+        format_float(*self, self)
+    }
+}
+
+/// Prometheus 把非有限值写作 `+Inf`、`-Inf` 和 `NaN`，而 Rust 输出 `inf`、`-inf`
+fn format_float(value: f64, display: &dyn Display) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        display.to_string()
+    }
+}
+
+/// 转义标签值中的反斜杠、双引号和换行
+fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("/mnt/data"), "/mnt/data");
+        assert_eq!(escape_label(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(escape_label("a\nb"), "a\\nb");
+    }
+
+    #[test]
+    fn test_metrics_writer_format() {
+        let mut out = MetricsWriter::default();
+        out.family("tui_dash_disk_used_bytes", "gauge", "Used bytes.");
+        out.sample("tui_dash_disk_used_bytes", &[("mountpoint", "/")], 42u64);
+        out.sample("tui_dash_up", &[], 1);
+
+        assert_eq!(
+            out.finish(),
+            "# HELP tui_dash_disk_used_bytes Used bytes.\n\
+             # TYPE tui_dash_disk_used_bytes gauge\n\
+             tui_dash_disk_used_bytes{mountpoint=\"/\"} 42\n\
+             tui_dash_up 1\n"
+        );
+    }
+
+    #[test]
+    fn test_disk_series() {
+        let disk = DiskInfo {
+            mount_point: "/home".to_string(),
+            used: 400,
+            total: 1000,
+            read_speed: 512 * 1024,
+            write_speed: 1024 * 1024,
+        };
+        let mut out = MetricsWriter::default();
+        render_disks(&mut out, &[disk]);
+        let text = out.finish();
+
+        assert!(text.contains("tui_dash_disk_used_bytes{mountpoint=\"/home\"} 400\n"));
+        assert!(text.contains("tui_dash_disk_read_bytes_per_second{mountpoint=\"/home\"} 524288\n"));
+        assert!(
+            text.contains("tui_dash_disk_write_bytes_per_second{mountpoint=\"/home\"} 1048576\n")
+        );
+    }
+
+    #[test]
+    fn test_duplicate_series_and_non_finite_values() {
+        let mut out = MetricsWriter::default();
+        out.sample("tui_dash_disk_used_bytes", &[("mountpoint", "/srv")], 1u64);
+        out.sample("tui_dash_disk_used_bytes", &[("mountpoint", "/srv")], 2u64);
+        out.sample("tui_dash_disk_total_bytes", &[("mountpoint", "/srv")], 3u64);
+        out.sample("tui_dash_temperature_celsius", &[], f64::INFINITY);
+        out.sample("tui_dash_temperature_celsius", &[("index", "1")], f64::NAN);
+        out.sample("tui_dash_cpu_usage_percent", &[], f32::NEG_INFINITY);
+        out.sample("tui_dash_load_average", &[], 0.5f32);
+
+        assert_eq!(
+            out.finish(),
+            "tui_dash_disk_used_bytes{mountpoint=\"/srv\"} 1\n\
+             tui_dash_disk_total_bytes{mountpoint=\"/srv\"} 3\n\
+             tui_dash_temperature_celsius +Inf\n\
+             tui_dash_temperature_celsius{index=\"1\"} NaN\n\
+             tui_dash_cpu_usage_percent -Inf\n\
+             tui_dash_load_average 0.5\n"
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cli::{ExportFormat, SnapshotFormat};
+use crate::collector::{CollectorHandle, DataCollector, Snapshot};
+use crate::config::Config;
+use crate::data::load::PRESSURE_RESOURCES;
+use crate::data::{
+    bytes_to_gb, bytes_to_mb, BatteryData, ContainerInfo, CpuBreakdown, CpuData, DiskInfo,
+    InterfaceInfo, LoadData, MemoryData, ProcessInfo, TemperatureSensor,
+};
+use crate::export;
+use crate::exporter::MetricsServer;
+use crate::recording::{Frame, Recording};
+
+/// 快照中列出的进程数量
+const TOP_PROCESSES: usize = 10;
+
+/// 采集一次系统数据并按指定格式打印到标准输出
+pub fn snapshot(config: Config, format: SnapshotFormat) -> Result<()> {
+    let collector = collect_once(config)?;
+    let snapshot = collector.snapshot();
+    match format {
+        SnapshotFormat::Text => print!("{}", format_snapshot(&snapshot)),
+        SnapshotFormat::Json => println!("{}", format_snapshot_json(&snapshot)?),
+    }
+    Ok(())
+}
+
+/// 按刷新间隔持续采集系统数据，每次采集以一行 JSON 追加一帧到 `path`
+pub fn record(config: Config, path: &Path, count: Option<u64>) -> Result<()> {
+    let interval = Duration::from_millis(config.general.update_interval_ms);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+
+    let mut collector = collect_once(config)?;
+    let mut written = 0;
+    loop {
+        let frame = Frame::capture(&collector.snapshot());
+        writeln!(file, "{}", serde_json::to_string(&frame)?)?;
+        file.flush()?;
+        written += 1;
+
+        if count.is_some_and(|count| written >= count) {
+            return Ok(());
+        }
+
+        std::thread::sleep(interval);
+        collector.collect();
+    }
+}
+
+/// 导出历史数据：从录制文件重新计算，或实时采集 `count` 次
+pub fn export(
+    mut config: Config,
+    recording: Option<&Path>,
+    format: ExportFormat,
+    output: Option<&Path>,
+    count: u64,
+) -> Result<()> {
+    let history = match recording {
+        Some(path) => {
+            let recording = Recording::load(path)?;
+            let frames = recording.frame_count();
+            recording.history(0..=frames - 1, frames)
+        }
+        None => {
+            let interval = Duration::from_millis(config.general.update_interval_ms);
+            config.general.history_points = config.general.history_points.max(count as usize);
+            let mut collector = collect_once(config)?;
+            for _ in 1..count {
+                std::thread::sleep(interval);
+                collector.collect();
+            }
+            collector.snapshot().history
+        }
+    };
+
+    match output {
+        Some(path) => export::export_to_file(&history, format, path),
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            export::write_history(&history, format, &mut stdout)?;
+            Ok(stdout.flush()?)
+        }
+    }
+}
+
+/// 不启动界面，只在 `addr` 上提供 Prometheus 指标，直到进程被中断
+pub fn serve(config: Config, addr: SocketAddr) -> Result<()> {
+    let collector = CollectorHandle::spawn(DataCollector::new(&config)?);
+    let server = MetricsServer::spawn(addr, collector.subscribe())?;
+    eprintln!("Serving metrics on http://{}/metrics", server.addr());
+
+    loop {
+        std::thread::park();
+    }
+}
+
+/// 创建采集器并等待一个刷新间隔后完成首次采集，使 CPU 与速率类数据有效
+///
+/// 无界面模式不需要后台线程，直接在当前线程中驱动采集器
+fn collect_once(config: Config) -> Result<DataCollector> {
+    let interval = Duration::from_millis(config.general.update_interval_ms);
+    let mut collector = DataCollector::new(&config)?;
+    collector.enable_all_modules();
+    std::thread::sleep(interval);
+    collector.collect();
+    Ok(collector)
+}
+
+fn format_snapshot(snapshot: &Snapshot) -> String {
+    let mut out = String::new();
+    let cpu = &snapshot.cpu;
+    out.push_str(&format!(
+        "CPU: {:.1}% ({}, {} cores, {} MHz)\n",
+        cpu.global_cpu_usage, cpu.cpu_brand, cpu.cpu_cores, cpu.cpu_frequency
+    ));
+    if let Some(breakdown) = &cpu.breakdown {
+        let modes: Vec<String> = CpuBreakdown::NAMES
+            .iter()
+            .zip(breakdown.values())
+            .map(|(name, value)| format!("{} {:.1}%", name, value))
+            .collect();
+        out.push_str(&format!("CPU time: {}\n", modes.join(" ")));
+    }
+
+    let load = &snapshot.load;
+    out.push_str(&format!(
+        "Load: {:.2} {:.2} {:.2} ({} cores), uptime {}",
+        load.load_average[0],
+        load.load_average[1],
+        load.load_average[2],
+        load.cpu_cores,
+        load.format_uptime()
+    ));
+    if let (Some(running), Some(total)) = (load.running_tasks, load.total_tasks) {
+        out.push_str(&format!(", tasks {}/{}", running, total));
+    }
+    out.push('\n');
+    for (resource, pressure) in PRESSURE_RESOURCES.iter().zip(&load.pressure) {
+        if let Some(pressure) = pressure {
+            let [avg10, avg60, avg300] = pressure.some;
+            out.push_str(&format!(
+                "Pressure {}: some {:.2} {:.2} {:.2}",
+                resource, avg10, avg60, avg300
+            ));
+            if let Some([avg10, avg60, avg300]) = pressure.full {
+                out.push_str(&format!(" full {:.2} {:.2} {:.2}", avg10, avg60, avg300));
+            }
+            out.push('\n');
+        }
+    }
+
+    let mem = &snapshot.memory;
+    out.push_str(&format!(
+        "Memory: {:.1}GB / {:.1}GB ({:.1}%)\n",
+        bytes_to_gb(mem.used_memory),
+        bytes_to_gb(mem.total_memory),
+        percent(mem.used_memory, mem.total_memory)
+    ));
+    out.push_str(&format!(
+        "Swap: {:.1}GB / {:.1}GB ({:.1}%)\n",
+        bytes_to_gb(mem.used_swap),
+        bytes_to_gb(mem.total_swap),
+        percent(mem.used_swap, mem.total_swap)
+    ));
+
+    out.push_str("Disks:\n");
+    for disk in &snapshot.disk.disks {
+        out.push_str(&format!(
+            "  {} {:.1}GB / {:.1}GB ({:.1}%) read {:.1}MB/s write {:.1}MB/s\n",
+            disk.mount_point,
+            bytes_to_gb(disk.used),
+            bytes_to_gb(disk.total),
+            disk.usage_percent(),
+            bytes_to_mb(disk.read_speed),
+            bytes_to_mb(disk.write_speed)
+        ));
+    }
+
+    out.push_str("Network:\n");
+    for iface in &snapshot.network.interfaces {
+        out.push_str(&format!(
+            "  {} rx {:.1}MB ({:.2}MB/s) tx {:.1}MB ({:.2}MB/s)\n",
+            iface.name,
+            bytes_to_mb(iface.received),
+            bytes_to_mb(iface.rx_speed),
+            bytes_to_mb(iface.transmitted),
+            bytes_to_mb(iface.tx_speed)
+        ));
+    }
+
+    out.push_str("Top processes:\n");
+    for p in snapshot.process.processes.iter().take(TOP_PROCESSES) {
+        out.push_str(&format!(
+            "  {:>7} {:<20} {:>5.1}% {:>8.1}MB\n",
+            p.pid, p.name, p.cpu_usage, p.memory_mb
+        ));
+    }
+
+    if !snapshot.temperature.sensors.is_empty() {
+        out.push_str("Temperatures:\n");
+        for sensor in &snapshot.temperature.sensors {
+            out.push_str(&format!("  {} {:.1}°C\n", sensor.name, sensor.current_temp));
+        }
+    }
+
+    if snapshot.battery.present {
+        out.push_str(&format!(
+            "Battery: {}% ({:?}, {})\n",
+            snapshot.battery.capacity,
+            snapshot.battery.status,
+            snapshot.battery.format_time_remaining()
+        ));
+    }
+
+    if !snapshot.docker.containers.is_empty() {
+        out.push_str("Containers:\n");
+        for c in &snapshot.docker.containers {
+            out.push_str(&format!(
+                "  {:<20} {:?} {:.1}% {:.1}MB\n",
+                c.name, c.state, c.cpu_percent, c.memory_usage_mb
+            ));
+        }
+    }
+
+    out
+}
+
+/// `snapshot --format json` 输出的文档结构
+#[derive(Serialize)]
+struct SnapshotDocument<'a> {
+    timestamp: String,
+    cpu: &'a CpuData,
+    load: &'a LoadData,
+    memory: &'a MemoryData,
+    disks: &'a [DiskInfo],
+    interfaces: &'a [InterfaceInfo],
+    top_processes: &'a [ProcessInfo],
+    temperatures: &'a [TemperatureSensor],
+    /// 没有电池时为 `null`
+    battery: Option<&'a BatteryData>,
+    containers: &'a [ContainerInfo],
+    /// 采集失败的模块及错误信息
+    errors: Vec<CollectorErrorEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct CollectorErrorEntry<'a> {
+    collector: &'static str,
+    error: &'a str,
+}
+
+fn format_snapshot_json(snapshot: &Snapshot) -> Result<String> {
+    let processes = &snapshot.process.processes;
+    let document = SnapshotDocument {
+        timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        cpu: &snapshot.cpu,
+        load: &snapshot.load,
+        memory: &snapshot.memory,
+        disks: &snapshot.disk.disks,
+        interfaces: &snapshot.network.interfaces,
+        top_processes: &processes[..processes.len().min(TOP_PROCESSES)],
+        temperatures: &snapshot.temperature.sensors,
+        battery: snapshot.battery.present.then_some(&snapshot.battery),
+        containers: &snapshot.docker.containers,
+        errors: snapshot
+            .failing_collectors()
+            .filter_map(|status| {
+                status
+                    .last_error
+                    .as_deref()
+                    .map(|error| CollectorErrorEntry {
+                        collector: status.name,
+                        error,
+                    })
+            })
+            .collect(),
+    };
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+fn percent(used: u64, total: u64) -> f64 {
+    if total > 0 {
+        used as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
@@ -149,6 +149,71 @@ impl I18n {
             "windows_log_unavailable" => "Windows event logs are not available",
             "journalctl_failed" => "journalctl execution failed (status code: {})",
             "syslog_read_failed" => "Failed to read syslog (status code: {})",
+            "collector_failed" => "Collector failed",
+            "replay" => "Replay",
+            "replay_paused" => "paused",
+            "replay_help" => "Space pause · [ ] ±10s · { } ±60s · +/- speed",
+            "history_exported" => "History exported to",
+            "export_failed" => "Export failed",
+            "send_signal" => "Send Signal",
+            "confirm_signal" => "Send",
+            "signal_picker_help" => "↑/↓ select · Enter choose · Esc cancel",
+            "confirm_help" => "Enter/y confirm · Esc/n cancel",
+            "signal_sent" => "Sent",
+            "signal_failed" => "Failed to send",
+            "not_in_replay" => "Not available during replay",
+            "renice" => "Renice",
+            "renice_help" => "←/→ adjust · Enter apply · Esc cancel",
+            "affinity" => "affinity",
+            "cpu_affinity" => "CPU Affinity",
+            "affinity_help" => "Arrows move · Space toggle · a all · Enter apply · Esc cancel",
+            "setting_applied" => "Set",
+            "setting_failed" => "Failed to set",
+            "process_details" => "Process Details",
+            "detail_help" => "r reveal command · ↑/↓ scroll · Esc close",
+            "process_exited" => "The process has exited",
+            "user" => "User",
+            "parent" => "Parent",
+            "start_time" => "Started",
+            "threads" => "Threads",
+            "open_fds" => "Open FDs",
+            "disk_read_write" => "Disk R/W",
+            "environment" => "Environment",
+            "environment_unavailable" => "Unavailable (permission denied)",
+            "virtual_memory_mb" => "VIRT MB",
+            "run_time" => "Time",
+            "disk_read_speed" => "Read/s",
+            "disk_write_speed" => "Write/s",
+            "columns" => "Columns",
+            "columns_help" => "↑/↓ move · Space toggle · a all · Enter apply · Esc cancel",
+            "select_one_column" => "Select at least one column",
+            "group_by_name" => "grouped by name",
+            "group_by_exe" => "grouped by executable",
+            "sockets" => "Sockets",
+            "connections" => "TCP connections",
+            "no_connections" => "No TCP connections",
+            "connections_unavailable" => "Unavailable (permission denied)",
+            "watched" => "Watched",
+            "watch_started" => "Watching",
+            "watch_stopped" => "Stopped watching",
+            "watch_exited" => "exited",
+            "watch_restarted" => "restarted",
+            "watch_waiting" => "not running",
+            "show_threads" => "threads",
+            "core_view_table" => "table",
+            "core_view_grid" => "grid",
+            "core_view_heatmap" => "heatmap",
+            "system_summary" => "System",
+            "load_average" => "Load average",
+            "load_per_core" => "per core",
+            "uptime" => "Uptime",
+            "tasks" => "Tasks",
+            "tasks_running" => "running",
+            "tasks_total" => "total",
+            "context_switches" => "Context switches/s",
+            "interrupts" => "Interrupts/s",
+            "pressure" => "Pressure (PSI)",
+            "psi_unavailable" => "PSI unavailable (requires Linux 4.20+ with CONFIG_PSI)",
 
             "unknown" => "unknown",
             "tcp" => "tcp",
@@ -252,6 +317,71 @@ impl I18n {
             "windows_log_unavailable" => "无法获取Windows事件日志",
             "journalctl_failed" => "journalctl执行失败 (状态码: {})",
             "syslog_read_failed" => "读取syslog失败 (状态码: {})",
+            "collector_failed" => "采集失败",
+            "replay" => "回放",
+            "replay_paused" => "已暂停",
+            "replay_help" => "空格 暂停 · [ ] ±10秒 · { } ±60秒 · +/- 速度",
+            "history_exported" => "历史数据已导出到",
+            "export_failed" => "导出失败",
+            "send_signal" => "发送信号",
+            "confirm_signal" => "确认发送",
+            "signal_picker_help" => "↑/↓ 选择 · Enter 确定 · Esc 取消",
+            "confirm_help" => "Enter/y 确认 · Esc/n 取消",
+            "signal_sent" => "已发送",
+            "signal_failed" => "发送失败",
+            "not_in_replay" => "回放时不可用",
+            "renice" => "调整优先级",
+            "renice_help" => "←/→ 调整 · Enter 应用 · Esc 取消",
+            "affinity" => "CPU 亲和性",
+            "cpu_affinity" => "CPU 亲和性",
+            "affinity_help" => "方向键 移动 · 空格 勾选 · a 全选 · Enter 应用 · Esc 取消",
+            "setting_applied" => "已设置",
+            "setting_failed" => "设置失败",
+            "process_details" => "进程详情",
+            "detail_help" => "r 显示命令 · ↑/↓ 滚动 · Esc 关闭",
+            "process_exited" => "进程已退出",
+            "user" => "用户",
+            "parent" => "父进程",
+            "start_time" => "启动时间",
+            "threads" => "线程数",
+            "open_fds" => "文件描述符",
+            "disk_read_write" => "磁盘读写",
+            "environment" => "环境变量",
+            "environment_unavailable" => "不可用（权限不足）",
+            "virtual_memory_mb" => "虚拟内存 (MB)",
+            "run_time" => "运行时长",
+            "disk_read_speed" => "读取/秒",
+            "disk_write_speed" => "写入/秒",
+            "columns" => "显示列",
+            "columns_help" => "↑/↓ 移动 · 空格 勾选 · a 全选 · Enter 应用 · Esc 取消",
+            "select_one_column" => "至少需要选择一列",
+            "group_by_name" => "按名称分组",
+            "group_by_exe" => "按可执行文件分组",
+            "sockets" => "套接字",
+            "connections" => "TCP 连接",
+            "no_connections" => "没有 TCP 连接",
+            "connections_unavailable" => "不可用（权限不足）",
+            "watched" => "监视",
+            "watch_started" => "开始监视",
+            "watch_stopped" => "停止监视",
+            "watch_exited" => "已退出",
+            "watch_restarted" => "已重新启动",
+            "watch_waiting" => "未运行",
+            "show_threads" => "显示线程",
+            "core_view_table" => "列表",
+            "core_view_grid" => "网格",
+            "core_view_heatmap" => "热力图",
+            "system_summary" => "系统概况",
+            "load_average" => "平均负载",
+            "load_per_core" => "每核",
+            "uptime" => "运行时间",
+            "tasks" => "任务",
+            "tasks_running" => "可运行",
+            "tasks_total" => "总计",
+            "context_switches" => "上下文切换/秒",
+            "interrupts" => "中断/秒",
+            "pressure" => "资源压力 (PSI)",
+            "psi_unavailable" => "PSI 不可用（需要 Linux 4.20 以上并启用 CONFIG_PSI）",
 
             "unknown" => "未知",
             "tcp" => "tcp",
@@ -261,12 +391,17 @@ impl I18n {
     }
 }
 
-use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 
-static I18N: Lazy<I18n> = Lazy::new(I18n::new);
+static I18N: OnceCell<I18n> = OnceCell::new();
+
+/// 指定界面语言，需在第一次调用 [`t`] 之前设置，否则使用自动检测的语言
+pub fn set_language(language: Language) {
+    let _ = I18N.set(I18n { language });
+}
 
 pub fn t(key: &str) -> &str {
-    I18N.get(key)
+    I18N.get_or_init(I18n::new).get(key)
 }
 
 #[cfg(test)]
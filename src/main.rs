@@ -1,10 +1,20 @@
 mod app;
+mod cli;
+mod collector;
+mod config;
 mod data;
+mod export;
+mod exporter;
+mod headless;
 mod i18n;
+mod recording;
 mod widgets;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use app::{ActiveTab, App};
+use clap::Parser;
+use cli::{Cli, Command, ExportFormat};
+use config::Config;
 use crossterm::{
     event::{
         self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEvent,
@@ -13,14 +23,18 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use data::masking::{self, Masker};
+use data::process::ProcessView;
+use data::process_control::Signal;
 use ratatui::{prelude::*, widgets::*};
 use std::panic;
 
 const MAX_SCROLL_OFFSET: u16 = u16::MAX;
-const MAX_PROCESS_SCROLL_OFFSET: usize = usize::MAX;
 const TAB_BAR_HEIGHT: u16 = 3;
 const SEARCH_BAR_HEIGHT: u16 = 6;
 const SCROLL_STEP: usize = 10;
+const REPLAY_SEEK_SECS: i64 = 10;
+const REPLAY_SEEK_LONG_SECS: i64 = 60;
 
 struct TerminalGuard {
     terminal: Terminal<CrosstermBackend<std::io::Stdout>>,
@@ -39,6 +53,57 @@ impl Drop for TerminalGuard {
 }
 
 fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut config = Config::load(cli.config.as_deref())?;
+    cli.apply_to(&mut config)?;
+
+    if let Some(lang) = cli.lang {
+        i18n::set_language(lang.into());
+    }
+    masking::init(Masker::new(&config.masking.patterns)?);
+
+    match cli.command {
+        Some(Command::Snapshot { format }) => headless::snapshot(config, format),
+        Some(Command::Record { ref path, count }) => headless::record(config, path, count),
+        Some(Command::Export {
+            ref recording,
+            format,
+            ref output,
+            count,
+        }) => headless::export(
+            config,
+            recording.as_deref(),
+            format,
+            output.as_deref(),
+            count,
+        ),
+        Some(Command::Replay { ref path }) => {
+            let recording = recording::Recording::load(path)?;
+            run_tui(&cli, App::replay(&config, recording))
+        }
+        None => match cli.serve {
+            Some(addr) if cli.headless => headless::serve(config, addr),
+            _ => run_tui(&cli, App::new(config)?),
+        },
+    }
+}
+
+/// 运行交互式终端界面
+fn run_tui(cli: &Cli, mut app: App) -> Result<()> {
+    if let Some(tab) = cli.tab {
+        app.set_active_tab(tab.into());
+    }
+    if cli.paused {
+        app.set_paused(true);
+    }
+    // 服务线程随进程退出，无需显式停止
+    if let Some(addr) = cli.serve {
+        let snapshots = app
+            .subscribe()
+            .context("--serve is not available during replay")?;
+        exporter::MetricsServer::spawn(addr, snapshots)?;
+    }
+
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
@@ -48,16 +113,15 @@ fn main() -> Result<()> {
 
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if !cli.no_mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let terminal = Terminal::new(CrosstermBackend::new(stdout))?;
     let mut terminal_guard = TerminalGuard { terminal };
 
-    let mut app = App::new()?;
-    let mut last_update = std::time::Instant::now();
-    let min_update_interval =
-        std::time::Duration::from_millis(crate::data::DEFAULT_UPDATE_INTERVAL_MS);
-
     loop {
+        app.sync_snapshot();
         terminal_guard.terminal.draw(|f| ui(f, &mut app))?;
 
         if event::poll(std::time::Duration::from_millis(
@@ -65,9 +129,40 @@ fn main() -> Result<()> {
         ))? {
             match event::read()? {
                 Event::Key(key) => {
-                    if app.search_mode {
+                    if app.process_dialog.is_some() {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                                app.cancel_dialog();
+                            }
+                            KeyCode::Enter | KeyCode::Char('y') => app.accept_dialog(),
+                            KeyCode::Up => app.move_dialog_selection(0, -1),
+                            KeyCode::Down => app.move_dialog_selection(0, 1),
+                            KeyCode::Left | KeyCode::Char('-') => {
+                                app.move_dialog_selection(-1, 0);
+                            }
+                            KeyCode::Right | KeyCode::Char('+') => {
+                                app.move_dialog_selection(1, 0);
+                            }
+                            KeyCode::Char(' ') => app.toggle_dialog_item(),
+                            KeyCode::Char('a') => app.toggle_all_dialog_items(),
+                            _ => {}
+                        }
+                    } else if app.process_detail.is_some() && app.active_tab == ActiveTab::Process {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                                app.close_process_detail();
+                            }
+                            KeyCode::Char('r') => app.toggle_reveal_command(),
+                            KeyCode::Up => app.scroll_process_detail(-1),
+                            KeyCode::Down => app.scroll_process_detail(1),
+                            KeyCode::PageUp => app.scroll_process_detail(-(SCROLL_STEP as i16)),
+                            KeyCode::PageDown => app.scroll_process_detail(SCROLL_STEP as i16),
+                            _ => {}
+                        }
+                    } else if app.search_mode {
+                        // 搜索框中 q 是普通字符，只能用 Esc 退出
                         match key.code {
-                            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
+                            KeyCode::Esc => {
                                 app.exit_search_mode();
                             }
                             KeyCode::Enter => {
@@ -84,53 +179,113 @@ fn main() -> Result<()> {
                     } else {
                         match key.code {
                             KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => break,
-                            KeyCode::Char('/') => {
-                                if app.active_tab == ActiveTab::Process {
-                                    app.enter_search_mode();
-                                }
+                            KeyCode::Char('/') if app.active_tab == ActiveTab::Process => {
+                                app.enter_search_mode();
                             }
                             KeyCode::Tab => app.next_tab(),
-                            KeyCode::Char('1') => app.active_tab = ActiveTab::Cpu,
-                            KeyCode::Char('2') => app.active_tab = ActiveTab::Memory,
-                            KeyCode::Char('3') => app.active_tab = ActiveTab::Disk,
-                            KeyCode::Char('4') => app.active_tab = ActiveTab::Network,
-                            KeyCode::Char('5') => app.active_tab = ActiveTab::Process,
+                            KeyCode::Char('1') => app.set_active_tab(ActiveTab::Cpu),
+                            KeyCode::Char('2') => app.set_active_tab(ActiveTab::Memory),
+                            KeyCode::Char('3') => app.set_active_tab(ActiveTab::Disk),
+                            KeyCode::Char('4') => app.set_active_tab(ActiveTab::Network),
+                            KeyCode::Char('5') => app.set_active_tab(ActiveTab::Process),
                             #[cfg(not(target_os = "windows"))]
-                            KeyCode::Char('6') => app.active_tab = ActiveTab::Docker,
+                            KeyCode::Char('6') => app.set_active_tab(ActiveTab::Docker),
                             #[cfg(target_os = "windows")]
-                            KeyCode::Char('6') => app.active_tab = ActiveTab::Logs,
+                            KeyCode::Char('6') => app.set_active_tab(ActiveTab::Logs),
                             #[cfg(not(target_os = "windows"))]
-                            KeyCode::Char('7') => app.active_tab = ActiveTab::Logs,
+                            KeyCode::Char('7') => app.set_active_tab(ActiveTab::Logs),
                             #[cfg(target_os = "windows")]
-                            KeyCode::Char('7') => app.active_tab = ActiveTab::Temperature,
+                            KeyCode::Char('7') => app.set_active_tab(ActiveTab::Temperature),
                             #[cfg(not(target_os = "windows"))]
-                            KeyCode::Char('8') => app.active_tab = ActiveTab::Temperature,
-                            KeyCode::Char('R') => {
-                                if let Err(_e) = app.update_data() {}
+                            KeyCode::Char('8') => app.set_active_tab(ActiveTab::Temperature),
+                            KeyCode::Char('R') => app.refresh(),
+                            KeyCode::Char('P') => app.toggle_paused(),
+                            KeyCode::Char(' ') if app.replay_status().is_some() => {
+                                app.toggle_paused();
                             }
-                            KeyCode::Char('P') => {
-                                app.paused = !app.paused;
+                            KeyCode::Char('[') => app.seek_replay(-REPLAY_SEEK_SECS),
+                            KeyCode::Char(']') => app.seek_replay(REPLAY_SEEK_SECS),
+                            KeyCode::Char('{') => app.seek_replay(-REPLAY_SEEK_LONG_SECS),
+                            KeyCode::Char('}') => app.seek_replay(REPLAY_SEEK_LONG_SECS),
+                            KeyCode::Char('+') | KeyCode::Char('=') => {
+                                app.change_replay_speed(true);
                             }
-                            KeyCode::Char('L') => {
-                                if app.active_tab == ActiveTab::Logs {
-                                    app.logs_data.toggle_log_level();
-                                    app.logs_scroll_offset = 0;
+                            KeyCode::Char('-') => app.change_replay_speed(false),
+                            KeyCode::Char('E') => app.export_history(ExportFormat::Csv),
+                            KeyCode::Char('I') => app.export_history(ExportFormat::Influx),
+                            KeyCode::Char('L') if app.active_tab == ActiveTab::Logs => {
+                                app.toggle_log_level();
+                            }
+                            KeyCode::Char('k') if app.active_tab == ActiveTab::Process => {
+                                app.open_signal_picker();
+                            }
+                            KeyCode::Char('t') if app.active_tab == ActiveTab::Process => {
+                                app.request_signal(Signal::Term);
+                            }
+                            KeyCode::Char('K') if app.active_tab == ActiveTab::Process => {
+                                app.request_signal(Signal::Kill);
+                            }
+                            KeyCode::Char('s') if app.active_tab == ActiveTab::Process => {
+                                app.request_signal(Signal::Stop);
+                            }
+                            KeyCode::Char('c') if app.active_tab == ActiveTab::Process => {
+                                app.request_signal(Signal::Cont);
+                            }
+                            KeyCode::Char('n') if app.active_tab == ActiveTab::Process => {
+                                app.open_renice();
+                            }
+                            KeyCode::Char('a') if app.active_tab == ActiveTab::Process => {
+                                app.open_affinity();
+                            }
+                            KeyCode::Char('T') if app.active_tab == ActiveTab::Process => {
+                                app.toggle_process_tree();
+                            }
+                            KeyCode::Char('g') if app.active_tab == ActiveTab::Process => {
+                                app.cycle_process_grouping();
+                            }
+                            KeyCode::Char('v') if app.active_tab == ActiveTab::Cpu => {
+                                app.cpu_core_view = app.cpu_core_view.next();
+                            }
+                            KeyCode::Char('H') if app.active_tab == ActiveTab::Process => {
+                                app.toggle_threads();
+                            }
+                            KeyCode::Char('p') if app.active_tab == ActiveTab::Process => {
+                                app.toggle_pin_selected();
+                            }
+                            KeyCode::Char('w') if app.active_tab == ActiveTab::Process => {
+                                app.toggle_watch_selected();
+                            }
+                            KeyCode::Enter if app.active_tab == ActiveTab::Process => {
+                                if app.selected_group().is_some() {
+                                    app.toggle_selected_group();
+                                } else {
+                                    app.open_process_detail();
                                 }
                             }
+                            KeyCode::Char('o') if app.active_tab == ActiveTab::Process => {
+                                app.open_column_chooser();
+                            }
+                            KeyCode::Char('<') if app.active_tab == ActiveTab::Process => {
+                                app.cycle_sort_column(-1);
+                            }
+                            KeyCode::Char('>') if app.active_tab == ActiveTab::Process => {
+                                app.cycle_sort_column(1);
+                            }
+                            KeyCode::Char('i') if app.active_tab == ActiveTab::Process => {
+                                app.reverse_sort();
+                            }
                             KeyCode::Up => {
                                 if app.active_tab == ActiveTab::Logs && app.logs_scroll_offset > 0 {
                                     app.logs_scroll_offset -= 1;
-                                } else if app.active_tab == ActiveTab::Process
-                                    && app.process_scroll_offset > 0
-                                {
-                                    app.process_scroll_offset -= 1;
+                                } else if app.active_tab == ActiveTab::Process {
+                                    app.move_process_selection(-1);
                                 }
                             }
                             KeyCode::Down => {
                                 if app.active_tab == ActiveTab::Logs {
                                     app.logs_scroll_offset += 1;
                                 } else if app.active_tab == ActiveTab::Process {
-                                    app.process_scroll_offset += 1;
+                                    app.move_process_selection(1);
                                 }
                             }
                             KeyCode::PageUp => {
@@ -140,39 +295,39 @@ fn main() -> Result<()> {
                                     app.logs_scroll_offset -= SCROLL_STEP as u16;
                                 } else if app.active_tab == ActiveTab::Logs {
                                     app.logs_scroll_offset = 0;
-                                } else if app.active_tab == ActiveTab::Process
-                                    && app.process_scroll_offset >= SCROLL_STEP
-                                {
-                                    app.process_scroll_offset -= SCROLL_STEP;
                                 } else if app.active_tab == ActiveTab::Process {
-                                    app.process_scroll_offset = 0;
+                                    app.move_process_selection(-(SCROLL_STEP as isize));
                                 }
                             }
                             KeyCode::PageDown => {
                                 if app.active_tab == ActiveTab::Logs {
                                     app.logs_scroll_offset += SCROLL_STEP as u16;
                                 } else if app.active_tab == ActiveTab::Process {
-                                    app.process_scroll_offset += SCROLL_STEP;
+                                    app.move_process_selection(SCROLL_STEP as isize);
                                 }
                             }
                             KeyCode::Home => {
                                 if app.active_tab == ActiveTab::Logs {
                                     app.logs_scroll_offset = 0;
                                 } else if app.active_tab == ActiveTab::Process {
-                                    app.process_scroll_offset = 0;
+                                    app.select_process(0);
                                 }
                             }
                             KeyCode::End => {
                                 if app.active_tab == ActiveTab::Logs {
                                     app.logs_scroll_offset = MAX_SCROLL_OFFSET;
                                 } else if app.active_tab == ActiveTab::Process {
-                                    app.process_scroll_offset = MAX_PROCESS_SCROLL_OFFSET;
+                                    app.select_process(usize::MAX);
                                 }
                             }
                             KeyCode::Right => {
                                 if app.active_tab == ActiveTab::Logs {
                                     app.active_log_category = app.active_log_category.next();
                                     app.logs_scroll_offset = 0;
+                                } else if app.active_tab == ActiveTab::Process
+                                    && app.process_view != ProcessView::Flat
+                                {
+                                    app.expand_selected();
                                 } else {
                                     app.next_tab();
                                 }
@@ -181,6 +336,10 @@ fn main() -> Result<()> {
                                 if app.active_tab == ActiveTab::Logs {
                                     app.active_log_category = app.active_log_category.previous();
                                     app.logs_scroll_offset = 0;
+                                } else if app.active_tab == ActiveTab::Process
+                                    && app.process_view != ProcessView::Flat
+                                {
+                                    app.collapse_selected();
                                 } else {
                                     app.previous_tab();
                                 }
@@ -195,11 +354,6 @@ fn main() -> Result<()> {
                 _ => {}
             }
         }
-
-        if last_update.elapsed() >= min_update_interval {
-            let _ = app.update_data();
-            last_update = std::time::Instant::now();
-        }
     }
 
     if let Err(_e) = app.cleanup() {}
@@ -223,7 +377,12 @@ fn ui(f: &mut Frame, app: &mut App) {
         .split(size);
 
     if app.search_mode {
-        render_search_box(f, main_layout[0], &app.search_query);
+        render_search_box(
+            f,
+            main_layout[0],
+            &app.search_query,
+            app.search_error.as_deref(),
+        );
     } else {
         render_tab_bar(f, main_layout[0], app);
     }
@@ -239,34 +398,104 @@ fn ui(f: &mut Frame, app: &mut App) {
         main_layout[1]
     };
 
+    let snapshot = app.snapshot.clone();
+
+    if app
+        .notice
+        .as_ref()
+        .is_some_and(|notice| notice.is_expired())
+    {
+        app.notice = None;
+    }
+    let content_area = match &app.notice {
+        Some(notice) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(content_area);
+            widgets::notice_bar::render(f, chunks[1], notice);
+            chunks[0]
+        }
+        None => content_area,
+    };
+
+    let content_area = match app.replay_status() {
+        Some(status) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(content_area);
+            widgets::replay_bar::render(f, chunks[1], &status);
+            chunks[0]
+        }
+        None => content_area,
+    };
+
+    let failing: Vec<_> = snapshot.failing_collectors().collect();
+    let content_area = if failing.is_empty() {
+        content_area
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(content_area);
+        widgets::collector_status::render(f, chunks[1], &failing);
+        chunks[0]
+    };
+
     match app.active_tab {
-        ActiveTab::Cpu => widgets::cpu_widget::render(f, content_area, &app.cpu_data, &app.history),
+        ActiveTab::Cpu => widgets::cpu_widget::render(
+            f,
+            content_area,
+            &snapshot.cpu,
+            &snapshot.load,
+            &snapshot.history,
+            app.cpu_core_view,
+        ),
         ActiveTab::Memory => {
-            widgets::memory_widget::render(f, content_area, &app.memory_data, &app.history)
+            widgets::memory_widget::render(f, content_area, &snapshot.memory, &snapshot.history)
         }
-        ActiveTab::Disk => widgets::disk_widget::render(f, content_area, &app.disk_data),
+        ActiveTab::Disk => widgets::disk_widget::render(f, content_area, &snapshot.disk),
         ActiveTab::Network => {
-            widgets::network_widget::render(f, content_area, &app.network_data, &app.history)
+            widgets::network_widget::render(f, content_area, &snapshot.network, &snapshot.history)
         }
         ActiveTab::Process => {
-            let actual_offset = widgets::process_widget::render(
+            let table = app.process_table();
+            let (table_area, detail_area, watch_area) = process_areas(app, content_area);
+            widgets::process_widget::render(
                 f,
-                content_area,
-                &app.process_data,
-                app.process_scroll_offset,
+                table_area,
+                &snapshot.process,
+                &table,
+                &app.process_columns,
+                &mut app.process_cursor,
                 app.mouse_x,
                 app.mouse_y,
             );
-            app.process_scroll_offset = actual_offset;
+            if let (Some(pane), Some(area)) = (&mut app.process_detail, detail_area) {
+                pane.scroll = widgets::process_detail::render(
+                    f,
+                    area,
+                    pane,
+                    snapshot.process.detail.as_ref(),
+                    &snapshot.history.process,
+                );
+            }
+            if let Some(area) = watch_area {
+                widgets::watch_widget::render(f, area, &snapshot.process.watches);
+            }
+            if let Some(dialog) = &app.process_dialog {
+                widgets::process_dialog::render(f, content_area, dialog);
+            }
         }
         ActiveTab::Docker => {
-            widgets::docker_widget::render(f, content_area, &app.docker_data);
+            widgets::docker_widget::render(f, content_area, &snapshot.docker);
         }
         ActiveTab::Logs => {
             let actual_offset = widgets::logs_widget::render(
                 f,
                 content_area,
-                &app.logs_data,
+                &snapshot.logs,
                 app.logs_scroll_offset,
                 &app.active_log_category,
                 app.mouse_x,
@@ -278,15 +507,37 @@ fn ui(f: &mut Frame, app: &mut App) {
             widgets::temperature_widget::render(
                 f,
                 content_area,
-                &app.temperature_data,
-                &app.battery_data,
+                &snapshot.temperature,
+                &snapshot.battery,
             );
         }
     }
 }
 
+/// 进程标签页的区域划分：进程表、详情面板和监视面板
+///
+/// 详情面板打开时进程表只占上半部分，有监视的进程时底部显示监视面板
+fn process_areas(app: &App, content_area: Rect) -> (Rect, Option<Rect>, Option<Rect>) {
+    let watch_height = widgets::watch_widget::height(&app.snapshot.process.watches);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(watch_height)])
+        .split(content_area);
+    let watch_area = (watch_height > 0).then_some(chunks[1]);
+    match app.process_detail {
+        Some(_) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .split(chunks[0]);
+            (chunks[0], Some(chunks[1]), watch_area)
+        }
+        None => (chunks[0], None, watch_area),
+    }
+}
+
 /// 渲染搜索框
-fn render_search_box(f: &mut Frame, area: Rect, query: &str) {
+fn render_search_box(f: &mut Frame, area: Rect, query: &str, error: Option<&str>) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Length(3)])
@@ -295,10 +546,19 @@ fn render_search_box(f: &mut Frame, area: Rect, query: &str) {
     render_tab_bar_simple(f, chunks[0]);
 
     let search_text = format!("/{}", query);
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .title(" Search ")
         .style(widgets::block_style());
+    // 表达式有误时在下边框显示原因
+    if let Some(error) = error {
+        block = block
+            .border_style(Style::default().fg(Color::Red))
+            .title_bottom(Line::styled(
+                format!(" {} ", error),
+                Style::default().fg(Color::Red),
+            ));
+    }
 
     let paragraph = Paragraph::new(search_text)
         .block(block)
@@ -315,7 +575,7 @@ fn render_tab_bar(f: &mut Frame, area: Rect, app: &App) {
         app.active_tab,
         app.mouse_x,
         app.mouse_y,
-        app.has_alert,
+        app.snapshot.has_alert,
     );
 }
 
@@ -328,6 +588,10 @@ fn render_tab_bar_simple(f: &mut Frame, area: Rect) {
 fn handle_mouse_event(mouse_event: MouseEvent, app: &mut App, terminal_size: Rect) {
     app.mouse_x = mouse_event.column;
     app.mouse_y = mouse_event.row;
+    // 对话框打开时只响应键盘
+    if app.process_dialog.is_some() {
+        return;
+    }
 
     match mouse_event.kind {
         MouseEventKind::Down(MouseButton::Left) => {
@@ -350,7 +614,7 @@ fn handle_mouse_event(mouse_event: MouseEvent, app: &mut App, terminal_size: Rec
                     content_area,
                     mouse_event.column,
                     mouse_event.row,
-                    &app.logs_data,
+                    &app.snapshot.logs,
                     app.logs_scroll_offset,
                     &app.active_log_category,
                 ) {
@@ -363,54 +627,66 @@ fn handle_mouse_event(mouse_event: MouseEvent, app: &mut App, terminal_size: Rec
                     content_area,
                     mouse_event.column,
                     mouse_event.row,
-                    &app.logs_data,
+                    &app.snapshot.logs,
                     app.logs_scroll_offset,
                     &app.active_log_category,
                 )
                 .is_some()
                 {}
             } else if app.active_tab == ActiveTab::Process {
-                if let Some(new_offset) = widgets::process_widget::handle_scrollbar_click(
+                if let Some(column) = widgets::process_widget::header_column_at(
                     content_area,
+                    &app.process_columns,
+                    app.process_view,
                     mouse_event.column,
                     mouse_event.row,
-                    &app.process_data,
-                    app.process_scroll_offset,
                 ) {
-                    app.process_scroll_offset = new_offset;
-                    app.is_dragging_scrollbar = true;
+                    app.sort_by_column(column);
+                    return;
+                }
+                if app.process_detail.is_some() {
                     return;
                 }
+                if let Some(new_offset) = widgets::process_widget::handle_scrollbar_click(
+                    process_areas(app, content_area).0,
+                    mouse_event.column,
+                    mouse_event.row,
+                    app.process_table().rows.len(),
+                    app.process_cursor.offset,
+                ) {
+                    app.process_cursor.offset = new_offset;
+                    app.process_cursor.follow_selection = false;
+                    app.is_dragging_scrollbar = true;
+                }
             }
         }
-        MouseEventKind::Drag(MouseButton::Left) => {
-            if app.is_dragging_scrollbar {
-                let main_layout = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Length(3), Constraint::Min(0)])
-                    .split(terminal_size);
-                let content_area = main_layout[1];
-
-                if app.active_tab == ActiveTab::Logs {
-                    if let Some(new_offset) = widgets::logs_widget::handle_scrollbar_drag(
-                        content_area,
-                        mouse_event.column,
-                        mouse_event.row,
-                        &app.logs_data,
-                        &app.active_log_category,
-                    ) {
-                        app.logs_scroll_offset = new_offset;
-                    }
-                } else if app.active_tab == ActiveTab::Process {
-                    if let Some(new_offset) = widgets::process_widget::handle_scrollbar_drag(
-                        content_area,
-                        mouse_event.column,
-                        mouse_event.row,
-                        &app.process_data,
-                        app.process_scroll_offset,
-                    ) {
-                        app.process_scroll_offset = new_offset;
-                    }
+        MouseEventKind::Drag(MouseButton::Left) if app.is_dragging_scrollbar => {
+            let main_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)])
+                .split(terminal_size);
+            let content_area = main_layout[1];
+
+            if app.active_tab == ActiveTab::Logs {
+                if let Some(new_offset) = widgets::logs_widget::handle_scrollbar_drag(
+                    content_area,
+                    mouse_event.column,
+                    mouse_event.row,
+                    &app.snapshot.logs,
+                    &app.active_log_category,
+                ) {
+                    app.logs_scroll_offset = new_offset;
+                }
+            } else if app.active_tab == ActiveTab::Process && app.process_detail.is_none() {
+                if let Some(new_offset) = widgets::process_widget::handle_scrollbar_drag(
+                    process_areas(app, content_area).0,
+                    mouse_event.column,
+                    mouse_event.row,
+                    app.process_table().rows.len(),
+                    app.process_cursor.offset,
+                ) {
+                    app.process_cursor.offset = new_offset;
+                    app.process_cursor.follow_selection = false;
                 }
             }
         }
@@ -420,15 +696,19 @@ fn handle_mouse_event(mouse_event: MouseEvent, app: &mut App, terminal_size: Rec
         MouseEventKind::ScrollUp => {
             if app.active_tab == ActiveTab::Logs && app.logs_scroll_offset > 0 {
                 app.logs_scroll_offset -= 1;
-            } else if app.active_tab == ActiveTab::Process && app.process_scroll_offset > 0 {
-                app.process_scroll_offset -= 1;
+            } else if app.active_tab == ActiveTab::Process && app.process_detail.is_some() {
+                app.scroll_process_detail(-1);
+            } else if app.active_tab == ActiveTab::Process {
+                app.move_process_selection(-1);
             }
         }
         MouseEventKind::ScrollDown => {
             if app.active_tab == ActiveTab::Logs {
                 app.logs_scroll_offset += 1;
+            } else if app.active_tab == ActiveTab::Process && app.process_detail.is_some() {
+                app.scroll_process_detail(1);
             } else if app.active_tab == ActiveTab::Process {
-                app.process_scroll_offset += 1;
+                app.move_process_selection(1);
             }
         }
         _ => {}
@@ -438,7 +718,7 @@ fn handle_mouse_event(mouse_event: MouseEvent, app: &mut App, terminal_size: Rec
 /// 处理标签栏点击
 fn handle_tab_click(column: u16, row: u16, app: &mut App, terminal_size: Rect) -> bool {
     if let Some(active_tab) = widgets::tab_bar::handle_tab_click(column, row, terminal_size) {
-        app.active_tab = active_tab;
+        app.set_active_tab(active_tab);
         true
     } else {
         false
@@ -454,4 +734,4 @@ fn handle_log_tab_click(column: u16, row: u16, app: &mut App, content_area: Rect
     } else {
         false
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,417 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::collector::Snapshot;
+use crate::config::Config;
+use crate::data::{
+    cpu::CpuData,
+    disk::DiskData,
+    load::LoadData,
+    logs::LogsData,
+    memory::MemoryData,
+    network::NetworkData,
+    process::{ProcessSort, ProcessView},
+    process_filter::{parse_patterns, ProcessFilter, ProcessPattern},
+    BatteryData, DockerData, ProcessData, SystemHistory, TemperatureData,
+};
+
+/// 回放速度的上下限
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 16.0;
+
+/// 录制文件中的一帧，每行一个 JSON 对象（NDJSON）
+///
+/// 只保存各模块的采集结果，历史曲线在回放时由相邻帧重新计算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frame {
+    /// RFC 3339 格式的采集时间
+    pub timestamp: String,
+    pub cpu: CpuData,
+    pub memory: MemoryData,
+    pub disk: DiskData,
+    pub network: NetworkData,
+    /// 较早的录制文件中没有这一项
+    #[serde(default)]
+    pub load: LoadData,
+    pub process: ProcessData,
+    pub temperature: TemperatureData,
+    pub battery: BatteryData,
+    pub docker: DockerData,
+}
+
+impl Frame {
+    /// 以当前时间记录快照中的数据
+    pub fn capture(snapshot: &Snapshot) -> Self {
+        Self {
+            timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+            cpu: snapshot.cpu.clone(),
+            memory: snapshot.memory.clone(),
+            disk: snapshot.disk.clone(),
+            network: snapshot.network.clone(),
+            load: snapshot.load.clone(),
+            process: snapshot.process.clone(),
+            temperature: snapshot.temperature.clone(),
+            battery: snapshot.battery.clone(),
+            docker: snapshot.docker.clone(),
+        }
+    }
+
+    fn time(&self) -> Result<DateTime<Local>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .map(|time| time.with_timezone(&Local))
+            .with_context(|| format!("invalid timestamp `{}`", self.timestamp))
+    }
+}
+
+/// 从文件加载的完整录制
+#[derive(Debug)]
+pub struct Recording {
+    frames: Vec<Frame>,
+    /// 每一帧的采集时间
+    times: Vec<DateTime<Local>>,
+    /// 每一帧相对第一帧的时间偏移
+    offsets: Vec<Duration>,
+}
+
+impl Recording {
+    /// 读取 `record` 子命令生成的 NDJSON 文件
+    ///
+    /// # Errors
+    ///
+    /// 文件无法读取、某一行不是合法的帧或文件中没有任何帧时返回错误
+    pub fn load(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+
+        let mut frames = Vec::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.with_context(|| format!("failed to read {}", path.display()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame = serde_json::from_str(&line)
+                .with_context(|| format!("{}:{}: invalid frame", path.display(), i + 1))?;
+            frames.push(frame);
+        }
+
+        Self::from_frames(frames).with_context(|| format!("failed to load {}", path.display()))
+    }
+
+    /// # Errors
+    ///
+    /// 没有帧或时间戳无法解析时返回错误
+    pub fn from_frames(frames: Vec<Frame>) -> Result<Self> {
+        let Some(first) = frames.first() else {
+            bail!("recording is empty");
+        };
+        let start = first.time()?;
+
+        let mut times = Vec::with_capacity(frames.len());
+        let mut offsets = Vec::with_capacity(frames.len());
+        let mut previous = Duration::ZERO;
+        for frame in &frames {
+            let time = frame.time()?;
+            // 时钟回拨时保持单调，避免定位时跳回前面的帧
+            let offset = (time - start).to_std().unwrap_or_default().max(previous);
+            times.push(time);
+            offsets.push(offset);
+            previous = offset;
+        }
+
+        Ok(Self {
+            frames,
+            times,
+            offsets,
+        })
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// 由指定范围内的帧重新计算历史曲线
+    pub fn history(&self, range: RangeInclusive<usize>, max_points: usize) -> SystemHistory {
+        let core_count = self.frames[*range.end()].cpu.core_usages.len();
+        let mut history = SystemHistory::new(core_count, max_points);
+        for i in range {
+            let (frame, time) = (&self.frames[i], self.times[i]);
+            history.record_cpu(time, &frame.cpu);
+            history.record_memory(time, &frame.memory);
+            history.record_network(time, &frame.network);
+            history.record_load(time, &frame.load);
+        }
+        history
+    }
+
+    /// 录制的总时长
+    pub fn duration(&self) -> Duration {
+        self.offsets.last().copied().unwrap_or_default()
+    }
+}
+
+/// 回放状态，用于在界面中显示
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayStatus {
+    pub timestamp: String,
+    pub position: usize,
+    pub len: usize,
+    pub elapsed: Duration,
+    pub duration: Duration,
+    pub speed: f64,
+    pub paused: bool,
+}
+
+/// 按录制时的时间间隔回放帧，支持暂停、定位和变速
+pub struct Player {
+    recording: Recording,
+    position: usize,
+    /// 回放时钟，相对第一帧的时间
+    clock: Duration,
+    speed: f64,
+    paused: bool,
+    last_tick: Instant,
+    history_points: usize,
+    filter: ProcessFilter,
+    view: ProcessView,
+    sort: ProcessSort,
+    pins: Vec<ProcessPattern>,
+    logs: LogsData,
+}
+
+impl Player {
+    pub fn new(recording: Recording, config: &Config) -> Self {
+        Self {
+            recording,
+            position: 0,
+            clock: Duration::ZERO,
+            speed: 1.0,
+            paused: false,
+            last_tick: Instant::now(),
+            history_points: config.general.history_points,
+            filter: ProcessFilter::default(),
+            view: ProcessView::default(),
+            sort: ProcessSort::default(),
+            pins: parse_patterns(&config.process.pinned),
+            logs: LogsData::empty(config),
+        }
+    }
+
+    /// 按经过的时间推进回放时钟，返回当前帧是否变化
+    ///
+    /// 播放到最后一帧时自动暂停
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_tick);
+        self.last_tick = now;
+        if self.paused {
+            return false;
+        }
+
+        let clock = self.clock + elapsed.mul_f64(self.speed);
+        let changed = self.move_to(clock);
+        if self.position + 1 >= self.recording.frame_count() {
+            self.paused = true;
+        }
+        changed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        // 在末尾继续播放时从头开始
+        if !paused && self.position + 1 >= self.recording.frame_count() {
+            self.move_to(Duration::ZERO);
+        }
+        self.paused = paused;
+        self.last_tick = Instant::now();
+    }
+
+    /// 向前或向后移动回放时钟，返回当前帧是否变化
+    pub fn seek(&mut self, seconds: i64) -> bool {
+        let delta = Duration::from_secs(seconds.unsigned_abs());
+        let clock = if seconds < 0 {
+            self.clock.saturating_sub(delta)
+        } else {
+            (self.clock + delta).min(self.recording.duration())
+        };
+        self.move_to(clock)
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed * 2.0).min(MAX_SPEED);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = (self.speed / 2.0).max(MIN_SPEED);
+    }
+
+    pub fn set_filter(&mut self, filter: ProcessFilter) {
+        self.filter = filter;
+    }
+
+    pub fn set_view(&mut self, view: ProcessView) {
+        self.view = view;
+    }
+
+    pub fn set_sort(&mut self, sort: ProcessSort) {
+        self.sort = sort;
+    }
+
+    pub fn set_pins(&mut self, pins: Vec<ProcessPattern>) {
+        self.pins = pins;
+    }
+
+    fn move_to(&mut self, clock: Duration) -> bool {
+        self.clock = clock;
+        let position = self
+            .recording
+            .offsets
+            .partition_point(|offset| *offset <= clock)
+            .saturating_sub(1);
+        let changed = position != self.position;
+        self.position = position;
+        changed
+    }
+
+    pub fn status(&self) -> ReplayStatus {
+        ReplayStatus {
+            timestamp: self.recording.frames[self.position].timestamp.clone(),
+            position: self.position,
+            len: self.recording.frame_count(),
+            elapsed: self.clock,
+            duration: self.recording.duration(),
+            speed: self.speed,
+            paused: self.paused,
+        }
+    }
+
+    /// 由当前帧生成快照，历史曲线取当前帧之前的 `history_points` 帧
+    pub fn snapshot(&self) -> Snapshot {
+        let frame = &self.recording.frames[self.position];
+
+        let start = (self.position + 1).saturating_sub(self.history_points);
+        let history = self
+            .recording
+            .history(start..=self.position, self.history_points);
+
+        let mut process = frame.process.clone();
+        process.set_filter(self.filter.clone());
+        process.set_view(self.view);
+        process.set_sort(self.sort);
+        process.set_pins(self.pins.clone());
+        process.apply_filter();
+        process.sort_processes();
+
+        Snapshot {
+            cpu: frame.cpu.clone(),
+            memory: frame.memory.clone(),
+            disk: frame.disk.clone(),
+            network: frame.network.clone(),
+            load: frame.load.clone(),
+            process,
+            logs: self.logs.clone(),
+            temperature: frame.temperature.clone(),
+            battery: frame.battery.clone(),
+            docker: frame.docker.clone(),
+            history,
+            has_alert: frame.temperature.has_warning
+                || frame.memory.has_warning
+                || frame.disk.has_warning,
+            collectors: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(frame: &Frame, timestamp: &str, cpu_usage: f32) -> Frame {
+        let mut frame = frame.clone();
+        frame.timestamp = timestamp.to_string();
+        frame.cpu.global_cpu_usage = cpu_usage;
+        frame
+    }
+
+    fn recording() -> Recording {
+        let line = r#"{"timestamp":"2026-01-01T10:00:00.000+00:00",
+            "cpu":{"global_cpu_usage":0.0,"core_usages":[0.0,0.0],"cpu_frequency":2000,
+                   "cpu_brand":"test","cpu_cores":2},
+            "memory":{"total_memory":100,"used_memory":50,"total_swap":0,"used_swap":0,
+                      "has_warning":false},
+            "disk":{"disks":[],"has_warning":false},
+            "network":{"interfaces":[]},
+            "process":{"processes":[]},
+            "temperature":{"sensors":[],"has_warning":false},
+            "battery":{"present":false,"status":"Unknown","capacity":0,"energy_now":0,
+                       "energy_full":0,"power_now":null,"voltage_now":null,
+                       "time_to_empty":null,"time_to_full":null},
+            "docker":{"containers":[]}}"#;
+        let base: Frame = serde_json::from_str(line).unwrap();
+        Recording::from_frames(vec![
+            frame(&base, "2026-01-01T10:00:00.000+00:00", 10.0),
+            frame(&base, "2026-01-01T10:00:01.000+00:00", 20.0),
+            frame(&base, "2026-01-01T10:00:02.500+00:00", 30.0),
+            frame(&base, "2026-01-01T10:00:10.000+00:00", 40.0),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_recording_offsets() {
+        let recording = recording();
+        assert_eq!(recording.frame_count(), 4);
+        assert_eq!(recording.offsets[2], Duration::from_millis(2500));
+        assert_eq!(recording.duration(), Duration::from_secs(10));
+        assert!(Recording::from_frames(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_player_tick_and_speed() {
+        let mut player = Player::new(recording(), &Config::default());
+        let start = player.last_tick;
+
+        assert!(!player.tick(start + Duration::from_millis(500)));
+        assert!(player.tick(start + Duration::from_millis(1000)));
+        assert_eq!(player.status().position, 1);
+
+        player.faster();
+        assert!(player.tick(start + Duration::from_millis(1750)));
+        assert_eq!(player.status().position, 2);
+        assert_eq!(player.snapshot().cpu.global_cpu_usage, 30.0);
+        assert_eq!(
+            player.snapshot().history.cpu.global_usage.get_all(),
+            vec![10.0, 20.0, 30.0]
+        );
+
+        player.set_paused(true);
+        assert!(!player.tick(start + Duration::from_secs(60)));
+        assert_eq!(player.status().position, 2);
+    }
+
+    #[test]
+    fn test_player_seek_and_end() {
+        let mut player = Player::new(recording(), &Config::default());
+        let start = player.last_tick;
+
+        assert!(player.seek(5));
+        assert_eq!(player.status().position, 2);
+        assert!(player.seek(60));
+        assert_eq!(player.status().position, 3);
+        assert!(player.seek(-8));
+        assert_eq!(player.status().position, 1);
+
+        player.seek(60);
+        player.tick(start);
+        assert!(player.is_paused());
+        player.set_paused(false);
+        assert_eq!(player.status().position, 0);
+    }
+}
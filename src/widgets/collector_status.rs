@@ -0,0 +1,39 @@
+use super::warning_style;
+use crate::data::CollectorStatus;
+use crate::i18n;
+use ratatui::{
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// 渲染采集失败的模块及其错误信息
+pub fn render(f: &mut Frame, area: Rect, failing: &[&CollectorStatus]) {
+    let mut spans = vec![Span::styled(
+        format!("✗ {}: ", i18n::t("collector_failed")),
+        warning_style().add_modifier(Modifier::BOLD),
+    )];
+
+    for (i, status) in failing.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" │ "));
+        }
+        spans.push(Span::styled(
+            status.name,
+            warning_style().add_modifier(Modifier::BOLD),
+        ));
+        let error = status.last_error.as_deref().unwrap_or_default();
+        if status.failures > 1 {
+            spans.push(Span::styled(
+                format!(" {} (×{})", error, status.failures),
+                warning_style(),
+            ));
+        } else {
+            spans.push(Span::styled(format!(" {}", error), warning_style()));
+        }
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
@@ -1,21 +1,62 @@
-use super::{block_style, default_style, highlight_style, BLOCK_BORDERS};
-use crate::data::history::SystemHistory;
-use crate::data::CpuData;
+use super::{block_style, default_style, highlight_style, warning_style, BLOCK_BORDERS};
+use crate::app::CpuCoreView;
+use crate::data::history::{HistoryData, SystemHistory};
+use crate::data::load::PRESSURE_RESOURCES;
+use crate::data::{CpuBreakdown, CpuData, LoadData};
 use crate::i18n;
 use ratatui::{
-    layout::Rect,
-    style,
-    widgets::{Block, Gauge, Row, Sparkline, Table},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{self, Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, Gauge, Paragraph, Row, Sparkline, Table},
     Frame,
 };
 
-pub fn render(f: &mut Frame, area: Rect, data: &CpuData, history: &SystemHistory) {
-    let chunks = ratatui::layout::Layout::default()
-        .direction(ratatui::layout::Direction::Vertical)
+/// 网格视图中每个核心的最小宽度，需要放下 `C127 100%` 和一段曲线
+const GRID_CELL_MIN_WIDTH: u16 = 16;
+/// 网格和热力图中核心编号和使用率标签的宽度
+const CORE_LABEL_WIDTH: u16 = 10;
+/// 热力图的颜色分级：使用率低于阈值时使用对应颜色
+const HEAT_LEVELS: [(f32, Color); 6] = [
+    (10.0, Color::DarkGray),
+    (30.0, Color::Blue),
+    (50.0, Color::Cyan),
+    (70.0, Color::Green),
+    (90.0, Color::Yellow),
+    (f32::INFINITY, Color::Red),
+];
+/// 各类 CPU 时间的颜色，与 `CpuBreakdown::NAMES` 的顺序一致
+const MODE_COLORS: [Color; 9] = [
+    Color::Green,
+    Color::Blue,
+    Color::Red,
+    Color::Yellow,
+    Color::Magenta,
+    Color::LightMagenta,
+    Color::Cyan,
+    Color::LightBlue,
+    Color::DarkGray,
+];
+/// 核心列表中除占比横条以外各列的宽度
+const CORE_TABLE_COLUMN_WIDTH: u16 = 8;
+/// PSI 达到该占比（%）时用警告色显示
+const PRESSURE_WARNING: f32 = 10.0;
+
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    data: &CpuData,
+    load: &LoadData,
+    history: &SystemHistory,
+    view: CpuCoreView,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
         .constraints([
-            ratatui::layout::Constraint::Length(6),
-            ratatui::layout::Constraint::Length(8),
-            ratatui::layout::Constraint::Min(0),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Min(0),
         ])
         .split(area);
 
@@ -30,60 +71,559 @@ pub fn render(f: &mut Frame, area: Rect, data: &CpuData, history: &SystemHistory
         format!("{} MHz | {} Cores", data.cpu_frequency, data.cpu_cores)
     };
 
+    let global_block = Block::default()
+        .title(i18n::t("global_cpu_usage"))
+        .borders(BLOCK_BORDERS)
+        .style(block_style());
+    let global_area = global_block.inner(chunks[0]);
+    f.render_widget(global_block, chunks[0]);
+    // 能读取 `/proc/stat` 时在使用率下方显示各类 CPU 时间的占比
+    let global_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(if data.breakdown.is_some() {
+            vec![
+                Constraint::Min(0),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ]
+        } else {
+            vec![Constraint::Min(0)]
+        })
+        .split(global_area);
     let global_gauge = Gauge::default()
-        .block(
-            Block::default()
-                .title(i18n::t("global_cpu_usage"))
-                .borders(BLOCK_BORDERS)
-                .style(block_style()),
-        )
         .gauge_style(default_style().fg(style::Color::Cyan))
         .percent(cpu_percent as u16)
         .label(format!("{:.1}% | {}", cpu_percent, cpu_info));
-    f.render_widget(global_gauge, chunks[0]);
+    f.render_widget(global_gauge, global_rows[0]);
+    if let Some(breakdown) = &data.breakdown {
+        f.render_widget(
+            Paragraph::new(stacked_bar(breakdown, global_rows[1].width)),
+            global_rows[1],
+        );
+        f.render_widget(
+            Paragraph::new(breakdown_legend(breakdown)).style(default_style()),
+            global_rows[2],
+        );
+    }
 
-    let history_data: Vec<u64> = history
-        .cpu
-        .global_usage
-        .get_all()
+    let summary_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    render_summary(f, summary_chunks[0], load);
+    render_pressure(f, summary_chunks[1], load);
+
+    let history_block = Block::default()
+        .title(i18n::t("cpu_history"))
+        .borders(BLOCK_BORDERS)
+        .style(block_style());
+    if history.cpu.breakdown.iter().next().is_some() {
+        render_breakdown_history(f, chunks[2], history_block, &history.cpu.breakdown);
+    } else {
+        let history_data: Vec<u64> = history
+            .cpu
+            .global_usage
+            .get_all()
+            .iter()
+            .map(|&v| v as u64)
+            .collect();
+        let sparkline = Sparkline::default()
+            .block(history_block)
+            .data(&history_data)
+            .style(default_style().fg(style::Color::Green))
+            .max(100);
+        f.render_widget(sparkline, chunks[2]);
+    }
+
+    let view_name = match view {
+        CpuCoreView::Table => i18n::t("core_view_table"),
+        CpuCoreView::Grid => i18n::t("core_view_grid"),
+        CpuCoreView::Heatmap => i18n::t("core_view_heatmap"),
+    };
+    let block = Block::default()
+        .title(format!("{} · {} (v) ", i18n::t("cpu_cores"), view_name))
+        .borders(BLOCK_BORDERS)
+        .style(block_style());
+
+    match view {
+        CpuCoreView::Table => render_core_table(f, chunks[3], block, data),
+        CpuCoreView::Grid => render_core_grid(f, chunks[3], block, data, history),
+        CpuCoreView::Heatmap => render_core_heatmap(f, chunks[3], block, history),
+    }
+}
+
+/// 平均负载、运行时间、任务数以及上下文切换和中断速率
+fn render_summary(f: &mut Frame, area: Rect, load: &LoadData) {
+    let label = |key: &str| Span::raw(format!("{}: ", i18n::t(key)));
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+    let mut load_line = vec![label("load_average")];
+    load_line.extend(
+        load.load_average
+            .iter()
+            .map(|value| Span::raw(format!("{:.2} ", value))),
+    );
+    // 每核负载超过 1 表示有任务在排队等待 CPU
+    load_line.push(Span::raw(format!("· {}: ", i18n::t("load_per_core"))));
+    for index in 0..load.load_average.len() {
+        let per_core = load.load_per_core(index);
+        let style = if per_core.is_some_and(|value| value > 1.0) {
+            warning_style()
+        } else {
+            Style::default()
+        };
+        load_line.push(Span::styled(
+            format!(
+                "{} ",
+                optional(per_core.map(|value| format!("{:.2}", value)))
+            ),
+            style,
+        ));
+    }
+
+    let lines = vec![
+        Line::from(load_line),
+        Line::from(vec![label("uptime"), Span::raw(load.format_uptime())]),
+        Line::from(vec![
+            label("tasks"),
+            Span::raw(format!(
+                "{} {} / {} {}",
+                optional(load.running_tasks.map(|n| n.to_string())),
+                i18n::t("tasks_running"),
+                optional(load.total_tasks.map(|n| n.to_string())),
+                i18n::t("tasks_total")
+            )),
+        ]),
+        Line::from(vec![
+            label("context_switches"),
+            Span::raw(optional(
+                load.context_switches_per_sec
+                    .map(|rate| format!("{:.0}", rate)),
+            )),
+            Span::raw(" · "),
+            label("interrupts"),
+            Span::raw(optional(
+                load.interrupts_per_sec.map(|rate| format!("{:.0}", rate)),
+            )),
+        ]),
+    ];
+    let paragraph = Paragraph::new(lines).style(default_style()).block(
+        Block::default()
+            .title(i18n::t("system_summary"))
+            .borders(BLOCK_BORDERS)
+            .style(block_style()),
+    );
+    f.render_widget(paragraph, area);
+}
+
+/// 每类资源一行，依次为 some 和 full 的 10、60、300 秒平均值
+fn render_pressure(f: &mut Frame, area: Rect, load: &LoadData) {
+    let block = Block::default()
+        .title(i18n::t("pressure"))
+        .borders(BLOCK_BORDERS)
+        .style(block_style());
+    if load.pressure.iter().all(Option::is_none) {
+        let paragraph = Paragraph::new(i18n::t("psi_unavailable"))
+            .style(default_style())
+            .block(block);
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let cell = |value: Option<f32>| match value {
+        Some(value) if value >= PRESSURE_WARNING => {
+            Cell::from(format!("{:.2}", value)).style(warning_style())
+        }
+        Some(value) => Cell::from(format!("{:.2}", value)),
+        None => Cell::from("-"),
+    };
+    let rows: Vec<Row> = PRESSURE_RESOURCES
         .iter()
-        .map(|&v| v as u64)
+        .zip(&load.pressure)
+        .map(|(resource, pressure)| {
+            let mut cells = vec![Cell::from(*resource)];
+            for i in 0..3 {
+                cells.push(cell(pressure.map(|pressure| pressure.some[i])));
+            }
+            for i in 0..3 {
+                cells.push(cell(
+                    pressure
+                        .and_then(|pressure| pressure.full)
+                        .map(|full| full[i]),
+                ));
+            }
+            Row::new(cells).style(default_style())
+        })
         .collect();
-    let sparkline = Sparkline::default()
-        .block(
-            Block::default()
-                .title(i18n::t("cpu_history"))
-                .borders(BLOCK_BORDERS)
-                .style(block_style()),
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(6),
+        ],
+    )
+    .block(block)
+    .header(
+        Row::new(vec![
+            "", "some 10s", "60s", "300s", "full 10s", "60s", "300s",
+        ])
+        .style(highlight_style()),
+    );
+    f.render_widget(table, area);
+}
+
+fn render_core_table(f: &mut Frame, area: Rect, block: Block, data: &CpuData) {
+    if data.core_breakdowns.is_empty() {
+        let core_rows: Vec<Row> = data
+            .core_usages
+            .iter()
+            .enumerate()
+            .map(|(i, &usage)| {
+                Row::new(vec![format!("Core {}", i), format!("{:.1}%", usage)])
+                    .style(default_style())
+            })
+            .collect();
+
+        let core_table = Table::new(
+            core_rows,
+            [Constraint::Percentage(50), Constraint::Percentage(50)],
         )
-        .data(&history_data)
-        .style(default_style().fg(style::Color::Green))
-        .max(100);
-    f.render_widget(sparkline, chunks[1]);
+        .block(block)
+        .header(Row::new(vec![i18n::t("core"), i18n::t("usage")]).style(highlight_style()));
 
+        f.render_widget(core_table, area);
+        return;
+    }
+
+    // 使用率之后是 iowait、steal 和各类 CPU 时间的堆叠横条
+    let bar_width = block
+        .inner(area)
+        .width
+        .saturating_sub((CORE_TABLE_COLUMN_WIDTH + 1) * 4);
     let core_rows: Vec<Row> = data
         .core_usages
         .iter()
         .enumerate()
         .map(|(i, &usage)| {
-            Row::new(vec![format!("Core {}", i), format!("{:.1}%", usage)]).style(default_style())
+            let breakdown = data.core_breakdowns.get(i).copied().unwrap_or_default();
+            Row::new(vec![
+                Cell::from(format!("Core {}", i)),
+                Cell::from(format!("{:.1}%", usage)),
+                Cell::from(format!("{:.1}%", breakdown.iowait)),
+                Cell::from(format!("{:.1}%", breakdown.steal)),
+                Cell::from(stacked_bar(&breakdown, bar_width)),
+            ])
+            .style(default_style())
         })
         .collect();
 
     let core_table = Table::new(
         core_rows,
         [
-            ratatui::layout::Constraint::Percentage(50),
-            ratatui::layout::Constraint::Percentage(50),
+            Constraint::Length(CORE_TABLE_COLUMN_WIDTH),
+            Constraint::Length(CORE_TABLE_COLUMN_WIDTH),
+            Constraint::Length(CORE_TABLE_COLUMN_WIDTH),
+            Constraint::Length(CORE_TABLE_COLUMN_WIDTH),
+            Constraint::Min(0),
         ],
     )
-    .block(
-        Block::default()
-            .title(i18n::t("cpu_cores"))
-            .borders(BLOCK_BORDERS)
-            .style(block_style()),
-    )
-    .header(Row::new(vec![i18n::t("core"), i18n::t("usage")]).style(highlight_style()));
+    .block(block)
+    .header(
+        Row::new(vec![
+            Line::from(i18n::t("core")),
+            Line::from(i18n::t("usage")),
+            Line::from("iowait"),
+            Line::from("steal"),
+            breakdown_legend(&CpuBreakdown::default()),
+        ])
+        .style(highlight_style()),
+    );
+
+    f.render_widget(core_table, area);
+}
+
+/// 每个核心一格，标签后面是该核心的使用率曲线
+///
+/// 列数按宽度尽量多放，核心太多放不下时每格只占一行；仍然放不下的核心不显示，在标题中注明
+fn render_core_grid(
+    f: &mut Frame,
+    area: Rect,
+    block: Block,
+    data: &CpuData,
+    history: &SystemHistory,
+) {
+    let inner = block.inner(area);
+    let count = history.cpu.core_usage.len();
+    let columns = (inner.width / GRID_CELL_MIN_WIDTH).clamp(1, count.max(1) as u16) as usize;
+    let rows = count.div_ceil(columns);
+    let cell_height = (inner.height as usize / rows.max(1)).max(1) as u16;
+    let visible_rows = (inner.height / cell_height) as usize;
+    let hidden = count.saturating_sub(visible_rows * columns);
+    let block = if hidden > 0 {
+        block.title_bottom(format!(" +{} ", hidden))
+    } else {
+        block
+    };
+    f.render_widget(block, area);
+
+    let cell_width = inner.width / columns as u16;
+    for (core, usage_history) in history
+        .cpu
+        .core_usage
+        .iter()
+        .enumerate()
+        .take(visible_rows * columns)
+    {
+        let cell = Rect {
+            x: inner.x + (core % columns) as u16 * cell_width,
+            y: inner.y + (core / columns) as u16 * cell_height,
+            width: cell_width.saturating_sub(1),
+            height: cell_height,
+        };
+        let usage = data.core_usages.get(core).copied().unwrap_or(0.0);
+        let parts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(CORE_LABEL_WIDTH), Constraint::Min(0)])
+            .split(cell);
+        let label = Paragraph::new(Line::from(vec![
+            Span::raw(format!("C{:<4}", core)),
+            Span::styled(
+                format!("{:>3.0}%", usage),
+                Style::default().fg(heat_color(usage)),
+            ),
+        ]))
+        .style(default_style());
+        f.render_widget(label, parts[0]);
+
+        let points = tail(usage_history, parts[1].width);
+        let sparkline = Sparkline::default()
+            .data(&points)
+            .style(default_style().fg(heat_color(usage)))
+            .max(100);
+        f.render_widget(sparkline, parts[1]);
+    }
+}
+
+/// 核心 × 时间的热力图，最新的数据在最右侧
+///
+/// 核心数不超过行数时每个核心占整行，否则每行用上下两个半格显示两组核心；
+/// 核心数超过两倍行数时相邻核心合为一组，显示组内最高的使用率
+fn render_core_heatmap(f: &mut Frame, area: Rect, block: Block, history: &SystemHistory) {
+    let legend: Vec<Span> = HEAT_LEVELS
+        .iter()
+        .scan(0.0, |lower, &(upper, color)| {
+            let text = if upper.is_finite() {
+                format!(" ■ {:.0}-{:.0}%", *lower, upper)
+            } else {
+                format!(" ■ {:.0}%+ ", *lower)
+            };
+            *lower = upper;
+            Some(Span::styled(text, Style::default().fg(color)))
+        })
+        .collect();
+    let block = block.title_bottom(Line::from(legend));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let cores = &history.cpu.core_usage;
+    if cores.is_empty() || inner.height == 0 || inner.width <= CORE_LABEL_WIDTH {
+        return;
+    }
+    let height = inner.height as usize;
+    // 行数足够时每个核心占整行（可以占多行），否则每行显示上下两组
+    let halves = cores.len() > height;
+    let groups = core_groups(cores.len(), if halves { height * 2 } else { height });
+    let width = (inner.width - CORE_LABEL_WIDTH) as usize;
+    // 每组在每个时间点的最高使用率，只取能显示下的最近数据
+    let series: Vec<Vec<f32>> = groups
+        .iter()
+        .map(|group| {
+            let histories: Vec<Vec<f32>> = cores[group.clone()]
+                .iter()
+                .map(|core| {
+                    let values = core.get_all();
+                    values[values.len().saturating_sub(width)..].to_vec()
+                })
+                .collect();
+            let len = histories.iter().map(Vec::len).max().unwrap_or(0);
+            (0..len)
+                .map(|i| {
+                    histories
+                        .iter()
+                        .filter_map(|values| values.get(i))
+                        .fold(0.0, |max: f32, &v| max.max(v))
+                })
+                .collect()
+        })
+        .collect();
+
+    let line = |label: String, top: &[f32], bottom: Option<&[f32]>| {
+        let mut spans = vec![Span::styled(
+            format!("{:<width$}", label, width = CORE_LABEL_WIDTH as usize),
+            default_style(),
+        )];
+        let len = top.len().max(bottom.map_or(0, <[f32]>::len));
+        spans.push(Span::raw(" ".repeat(width - len)));
+        for i in 0..len {
+            let upper = top.get(i).copied();
+            let lower = bottom.map_or(upper, |bottom| bottom.get(i).copied());
+            // 上半格用前景色，下半格用背景色；`bottom` 为 `None` 时整格同色，为空时下半格留空
+            let style = Style::default()
+                .fg(upper.map_or(Color::Reset, heat_color))
+                .bg(lower.map_or(Color::Reset, heat_color));
+            spans.push(Span::styled("▀", style));
+        }
+        Line::from(spans)
+    };
+
+    let lines: Vec<Line> = if halves {
+        groups
+            .chunks(2)
+            .zip(series.chunks(2))
+            .map(|(pair, values)| {
+                // 标签为这一行包含的核心范围
+                let label = group_label(&(pair[0].start..pair[pair.len() - 1].end));
+                let bottom = values.get(1).map(Vec::as_slice).unwrap_or_default();
+                line(label, &values[0], Some(bottom))
+            })
+            .collect()
+    } else {
+        let repeat = height / groups.len();
+        groups
+            .iter()
+            .zip(&series)
+            .flat_map(|(group, values)| {
+                (0..repeat).map(move |i| {
+                    let label = if i == 0 {
+                        group_label(group)
+                    } else {
+                        String::new()
+                    };
+                    line(label, values, None)
+                })
+            })
+            .collect()
+    };
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// 各类 CPU 时间依次堆叠成的横条，空闲部分留空
+fn stacked_bar(breakdown: &CpuBreakdown, width: u16) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut total = 0.0;
+    let mut filled = 0;
+    for (value, color) in breakdown
+        .values()
+        .iter()
+        .zip(MODE_COLORS)
+        .take(MODE_COLORS.len() - 1)
+    {
+        // 按累计占比取整，避免每段分别取整造成的误差累积
+        total += value;
+        let end = ((total / 100.0 * width as f32).round() as usize).min(width as usize);
+        if end > filled {
+            spans.push(Span::styled(
+                "█".repeat(end - filled),
+                Style::default().fg(color),
+            ));
+            filled = end;
+        }
+    }
+    Line::from(spans)
+}
+
+/// 各类 CPU 时间的名称（按颜色显示）和占比
+fn breakdown_legend(breakdown: &CpuBreakdown) -> Line<'static> {
+    let default = *breakdown == CpuBreakdown::default();
+    let mut spans = Vec::new();
+    for ((name, value), color) in CpuBreakdown::NAMES
+        .iter()
+        .zip(breakdown.values())
+        .zip(MODE_COLORS)
+    {
+        spans.push(Span::styled(*name, Style::default().fg(color)));
+        // 表头中只显示名称
+        spans.push(Span::raw(if default {
+            " ".to_string()
+        } else {
+            format!(" {:.1}%  ", value)
+        }));
+    }
+    Line::from(spans)
+}
+
+/// 各类 CPU 时间占比的历史，每列是一次采集的堆叠柱，最新的数据在最右侧
+fn render_breakdown_history(
+    f: &mut Frame,
+    area: Rect,
+    block: Block,
+    history: &HistoryData<CpuBreakdown>,
+) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let values = history.get_all();
+    let points = &values[values.len().saturating_sub(inner.width as usize)..];
+    let height = inner.height as usize;
+    let lines: Vec<Line> = (0..height)
+        .map(|row| {
+            // 按每行中点的高度决定显示哪一类
+            let level = (height - row) as f32 - 0.5;
+            let level = level / height as f32 * 100.0;
+            let mut spans = vec![Span::raw(" ".repeat(inner.width as usize - points.len()))];
+            spans.extend(points.iter().map(|breakdown| {
+                let mut total = 0.0;
+                breakdown
+                    .values()
+                    .iter()
+                    .zip(MODE_COLORS)
+                    .take(MODE_COLORS.len() - 1)
+                    .find(|(&value, _)| {
+                        total += value;
+                        total > level
+                    })
+                    .map_or(Span::raw(" "), |(_, color)| {
+                        Span::styled("█", Style::default().fg(color))
+                    })
+            }));
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// 把核心依次分成不超过 `slots` 组，每组的核心数相同（最后一组可能较少）
+fn core_groups(count: usize, slots: usize) -> Vec<std::ops::Range<usize>> {
+    let size = count.div_ceil(slots.max(1)).max(1);
+    (0..count)
+        .step_by(size)
+        .map(|start| start..(start + size).min(count))
+        .collect()
+}
+
+fn group_label(group: &std::ops::Range<usize>) -> String {
+    if group.len() == 1 {
+        group.start.to_string()
+    } else {
+        format!("{}-{}", group.start, group.end - 1)
+    }
+}
+
+fn heat_color(usage: f32) -> Color {
+    HEAT_LEVELS
+        .iter()
+        .find(|(upper, _)| usage < *upper)
+        .map_or(Color::Red, |&(_, color)| color)
+}
 
-    f.render_widget(core_table, chunks[1]);
-}
\ No newline at end of file
+/// 最近的 `width` 个数据点，使曲线的右端总是最新的取值
+fn tail(history: &HistoryData<f32>, width: u16) -> Vec<u64> {
+    let values = history.get_all();
+    let start = values.len().saturating_sub(width as usize);
+    values[start..].iter().map(|&v| v as u64).collect()
+}
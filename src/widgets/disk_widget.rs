@@ -12,9 +12,9 @@ pub fn render(f: &mut Frame, area: Rect, data: &DiskData) {
     let disk_rows: Vec<Row> = data
         .disks
         .iter()
-        .map(|(mount, used, total, _read_speed, _write_speed)| {
-            let used_gb = bytes_to_gb(*used);
-            let total_gb = bytes_to_gb(*total);
+        .map(|disk| {
+            let used_gb = bytes_to_gb(disk.used);
+            let total_gb = bytes_to_gb(disk.total);
             let percent = if total_gb > 0.0 {
                 (used_gb / total_gb) * 100.0
             } else {
@@ -22,7 +22,7 @@ pub fn render(f: &mut Frame, area: Rect, data: &DiskData) {
             };
 
             Row::new(vec![
-                mount.clone(),
+                disk.mount_point.clone(),
                 format!("{:.1}GB", used_gb),
                 format!("{:.1}GB", total_gb),
                 format!("{:.1}%", percent),
@@ -39,20 +39,20 @@ pub fn render(f: &mut Frame, area: Rect, data: &DiskData) {
     let disk_rows: Vec<Row> = data
         .disks
         .iter()
-        .map(|(mount, used, total, read_speed, write_speed)| {
-            let used_gb = bytes_to_gb(*used);
-            let total_gb = bytes_to_gb(*total);
+        .map(|disk| {
+            let used_gb = bytes_to_gb(disk.used);
+            let total_gb = bytes_to_gb(disk.total);
             let percent = if total_gb > 0.0 {
                 (used_gb / total_gb) * 100.0
             } else {
                 0.0
             };
 
-            let read_mb = bytes_to_mb(*read_speed);
-            let write_mb = bytes_to_mb(*write_speed);
+            let read_mb = bytes_to_mb(disk.read_speed);
+            let write_mb = bytes_to_mb(disk.write_speed);
 
             Row::new(vec![
-                mount.clone(),
+                disk.mount_point.clone(),
                 format!("{:.1}GB", used_gb),
                 format!("{:.1}GB", total_gb),
                 format!("{:.1}%", percent),
@@ -119,4 +119,4 @@ pub fn render(f: &mut Frame, area: Rect, data: &DiskData) {
         .header(header.style(highlight_style()));
 
     f.render_widget(disk_table, area);
-}
\ No newline at end of file
+}
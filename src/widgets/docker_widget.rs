@@ -1,10 +1,10 @@
 use super::{block_style, default_style, highlight_style, warning_style, BLOCK_BORDERS};
-use crate::data::docker::{ContainerInfo, ContainerState, DockerData};
+use crate::data::docker::{ContainerState, DockerData};
 use crate::i18n;
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
-    widgets::{Block, Row, Table},
+    widgets::{Block, Cell, Row, Table},
     Frame,
 };
 
@@ -52,12 +52,12 @@ pub fn render(f: &mut Frame, area: Rect, data: &DockerData) {
             };
 
             Row::new(vec![
-                truncate_string(&container.name, 20),
-                container.image.clone(),
-                container.status.clone(),
-                format!("{:.1}%", container.cpu_percent),
-                format!("{:.1}MB", container.memory_usage_mb),
-                format!("{:.1}%", container.memory_percent),
+                Cell::from(truncate_string(&container.name, 20)),
+                Cell::from(container.image.clone()),
+                Cell::from(container.status.clone()),
+                Cell::from(format!("{:.1}%", container.cpu_percent)).style(cpu_style),
+                Cell::from(format!("{:.1}MB", container.memory_usage_mb)),
+                Cell::from(format!("{:.1}%", container.memory_percent)).style(mem_style),
             ])
             .style(state_style)
         })
@@ -122,4 +122,4 @@ fn truncate_string(s: &str, max_len: usize) -> String {
         result.push('…');
     }
     result
-}
\ No newline at end of file
+}
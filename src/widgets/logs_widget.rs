@@ -298,4 +298,4 @@ fn get_log_level_name(level: LogLevel) -> &'static str {
         LogLevel::Info => "Info",
         LogLevel::Debug => "Debug",
     }
-}
\ No newline at end of file
+}
@@ -135,4 +135,4 @@ pub fn render(f: &mut Frame, area: Rect, data: &MemoryData, history: &SystemHist
             .max(100);
         f.render_widget(mem_sparkline, chunks[1]);
     }
-}
\ No newline at end of file
+}
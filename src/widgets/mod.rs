@@ -1,12 +1,18 @@
+pub mod collector_status;
 pub mod cpu_widget;
 pub mod disk_widget;
 pub mod docker_widget;
 pub mod logs_widget;
 pub mod memory_widget;
 pub mod network_widget;
+pub mod notice_bar;
+pub mod process_detail;
+pub mod process_dialog;
 pub mod process_widget;
+pub mod replay_bar;
 pub mod tab_bar;
 pub mod temperature_widget;
+pub mod watch_widget;
 use ratatui::style::{Color, Style};
 pub fn default_style() -> Style {
     Style::default().fg(Color::White).bg(Color::Black)
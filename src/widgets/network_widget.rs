@@ -68,14 +68,14 @@ pub fn render(f: &mut Frame, area: Rect, data: &NetworkData, history: &SystemHis
     let net_rows: Vec<Row> = data
         .interfaces
         .iter()
-        .map(|(name, received, transmitted, rx_speed, tx_speed)| {
-            let rx_speed_mb = *rx_speed as f64 / BYTES_PER_MB as f64;
-            let tx_speed_mb = *tx_speed as f64 / BYTES_PER_MB as f64;
+        .map(|iface| {
+            let rx_speed_mb = iface.rx_speed as f64 / BYTES_PER_MB as f64;
+            let tx_speed_mb = iface.tx_speed as f64 / BYTES_PER_MB as f64;
 
             Row::new(vec![
-                name.clone(),
-                format!("{:.1}MB", bytes_to_mb(*received)),
-                format!("{:.1}MB", bytes_to_mb(*transmitted)),
+                iface.name.clone(),
+                format!("{:.1}MB", bytes_to_mb(iface.received)),
+                format!("{:.1}MB", bytes_to_mb(iface.transmitted)),
                 format!("{:.1}MB/s", rx_speed_mb),
                 format!("{:.1}MB/s", tx_speed_mb),
             ])
@@ -111,4 +111,4 @@ pub fn render(f: &mut Frame, area: Rect, data: &NetworkData, history: &SystemHis
     );
 
     f.render_widget(net_table, chunks[2]);
-}
\ No newline at end of file
+}
@@ -0,0 +1,18 @@
+use super::warning_style;
+use crate::app::Notice;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// 渲染临时提示，错误以警告样式显示
+pub fn render(f: &mut Frame, area: Rect, notice: &Notice) {
+    let style = if notice.is_error {
+        warning_style()
+    } else {
+        Style::default().fg(Color::Green)
+    };
+    f.render_widget(Paragraph::new(notice.message.as_str()).style(style), area);
+}
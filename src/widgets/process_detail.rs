@@ -0,0 +1,259 @@
+use chrono::{Local, TimeZone};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Sparkline, Wrap},
+    Frame,
+};
+
+use super::{block_style, default_style, BLOCK_BORDERS};
+use crate::app::DetailPane;
+use crate::data::{bytes_to_mb, history::ProcessHistory, process::ProcessDetails};
+use crate::i18n;
+
+/// 信息区的行数，不含边框
+const INFO_LINES: u16 = 10;
+
+/// 渲染进程详情面板，返回调整后的环境变量滚动位置
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    pane: &DetailPane,
+    details: Option<&ProcessDetails>,
+    history: &ProcessHistory,
+) -> u16 {
+    f.render_widget(Clear, area);
+
+    let title = match details {
+        Some(details) => format!(
+            " {}: {} ({}) ",
+            i18n::t("process_details"),
+            details.pid,
+            details.name
+        ),
+        None => format!(" {}: {} ", i18n::t("process_details"), pane.pid),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(Line::from(format!(" {} ", i18n::t("detail_help"))).right_aligned())
+        .borders(BLOCK_BORDERS)
+        .style(block_style());
+
+    let Some(details) = details else {
+        let text = Paragraph::new(i18n::t("process_exited"))
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(block)
+            .style(default_style());
+        f.render_widget(text, area);
+        return 0;
+    };
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(INFO_LINES), Constraint::Min(0)])
+        .split(inner);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[0]);
+
+    let info = Paragraph::new(info_lines(pane, details))
+        .style(default_style())
+        .wrap(Wrap { trim: false });
+    f.render_widget(info, top[0]);
+
+    render_sparklines(f, top[1], details, history);
+
+    let bottom = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[1]);
+    render_connections(f, bottom[0], details);
+
+    render_environ(f, bottom[1], details, pane.scroll)
+}
+
+fn info_lines(pane: &DetailPane, details: &ProcessDetails) -> Vec<Line<'static>> {
+    let command = if pane.reveal_command {
+        details.command.clone()
+    } else {
+        details.display_command()
+    };
+    let user = match (&details.user, details.uid) {
+        (Some(user), Some(uid)) => format!("{} ({})", user, uid),
+        (None, Some(uid)) => uid.to_string(),
+        _ => "-".to_string(),
+    };
+    let parent = match (details.parent, &details.parent_name) {
+        (Some(parent), Some(name)) => format!("{} ({})", parent, name),
+        (Some(parent), None) => parent.to_string(),
+        (None, _) => "-".to_string(),
+    };
+    let start_time = Local
+        .timestamp_opt(details.start_time as i64, 0)
+        .single()
+        .map_or_else(
+            || "-".to_string(),
+            |time| time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        );
+    let disk_io = match (details.read_bytes, details.write_bytes) {
+        (Some(read), Some(write)) => {
+            format!("{:.1}MB / {:.1}MB", bytes_to_mb(read), bytes_to_mb(write))
+        }
+        _ => "-".to_string(),
+    };
+
+    vec![
+        field(i18n::t("command"), command),
+        field(i18n::t("user"), user),
+        field(i18n::t("parent"), parent),
+        field(i18n::t("start_time"), start_time),
+        field("CWD", optional(&details.cwd)),
+        field("EXE", optional(&details.exe)),
+        field(
+            i18n::t("threads"),
+            details.threads.map_or("-".to_string(), |n| n.to_string()),
+        ),
+        field(
+            i18n::t("open_fds"),
+            details.open_fds.map_or("-".to_string(), |n| n.to_string()),
+        ),
+        field(i18n::t("disk_read_write"), disk_io),
+    ]
+}
+
+fn field(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{:<10} ", label), Style::default().fg(Color::Cyan)),
+        Span::raw(value),
+    ])
+}
+
+fn optional(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| "-".to_string())
+}
+
+fn render_sparklines(
+    f: &mut Frame,
+    area: Rect,
+    details: &ProcessDetails,
+    history: &ProcessHistory,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let cpu: Vec<u64> = history
+        .cpu_usage
+        .get_all()
+        .iter()
+        .map(|&v| v as u64)
+        .collect();
+    // 多线程进程的 CPU 占用可以超过 100%
+    let cpu_max = cpu.iter().copied().max().unwrap_or(0).max(100);
+    let cpu_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!("CPU {:.1}%", details.cpu_usage))
+                .borders(BLOCK_BORDERS)
+                .style(block_style()),
+        )
+        .data(&cpu)
+        .style(default_style().fg(Color::Green))
+        .max(cpu_max);
+    f.render_widget(cpu_sparkline, chunks[0]);
+
+    let memory: Vec<u64> = history
+        .memory_mb
+        .get_all()
+        .iter()
+        .map(|&v| v as u64)
+        .collect();
+    let memory_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!("{} {:.1}MB", i18n::t("memory"), details.memory_mb))
+                .borders(BLOCK_BORDERS)
+                .style(block_style()),
+        )
+        .data(&memory)
+        .style(default_style().fg(Color::Yellow));
+    f.render_widget(memory_sparkline, chunks[1]);
+}
+
+fn render_connections(f: &mut Frame, area: Rect, details: &ProcessDetails) {
+    let (title, lines): (String, Vec<Line>) = match &details.connections {
+        Some(connections) if connections.is_empty() => (
+            format!(" {} ", i18n::t("connections")),
+            vec![Line::from(i18n::t("no_connections"))],
+        ),
+        Some(connections) => (
+            format!(" {} ({}) ", i18n::t("connections"), connections.len()),
+            connections
+                .iter()
+                .map(|connection| {
+                    let state_style = match connection.state {
+                        "LISTEN" => Style::default().fg(Color::Cyan),
+                        "ESTABLISHED" => Style::default().fg(Color::Green),
+                        _ => Style::default().fg(Color::Yellow),
+                    };
+                    Line::from(vec![
+                        Span::styled(format!("{:<12} ", connection.state), state_style),
+                        Span::raw(format!("{:<24} → {}", connection.local, connection.remote)),
+                    ])
+                })
+                .collect(),
+        ),
+        None => (
+            format!(" {} ", i18n::t("connections")),
+            vec![Line::from(i18n::t("connections_unavailable"))],
+        ),
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(BLOCK_BORDERS)
+                .style(block_style()),
+        )
+        .style(default_style());
+    f.render_widget(paragraph, area);
+}
+
+fn render_environ(f: &mut Frame, area: Rect, details: &ProcessDetails, scroll: u16) -> u16 {
+    let (title, lines): (String, Vec<Line>) = match &details.environ {
+        Some(environ) => (
+            format!(" {} ({}) ", i18n::t("environment"), environ.len()),
+            environ
+                .iter()
+                .map(|entry| Line::from(entry.as_str()))
+                .collect(),
+        ),
+        None => (
+            format!(" {} ", i18n::t("environment")),
+            vec![Line::from(i18n::t("environment_unavailable"))],
+        ),
+    };
+
+    let visible = area.height.saturating_sub(2);
+    let max_scroll = (lines.len() as u16).saturating_sub(visible);
+    let scroll = scroll.min(max_scroll);
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(BLOCK_BORDERS)
+                .style(block_style()),
+        )
+        .style(default_style())
+        .scroll((scroll, 0));
+    f.render_widget(paragraph, area);
+    scroll
+}
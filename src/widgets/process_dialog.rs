@@ -0,0 +1,166 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::process_widget::column_title;
+use super::{block_style, default_style, highlight_style, warning_style, BLOCK_BORDERS};
+use crate::app::{ProcessDialog, ProcessTarget, AFFINITY_GRID_COLUMNS};
+use crate::data::process::ProcessColumn;
+use crate::data::process_control::Signal;
+use crate::i18n;
+
+/// 对话框宽度（含边框）
+const DIALOG_WIDTH: u16 = 64;
+
+/// 在进程表上方居中绘制对话框
+pub fn render(f: &mut Frame, area: Rect, dialog: &ProcessDialog) {
+    let (title, lines) = match dialog {
+        ProcessDialog::SignalPicker { target, selected } => {
+            let mut lines = target_lines(target);
+            lines.push(Line::default());
+            for (i, signal) in Signal::ALL.iter().enumerate() {
+                let style = if i == *selected {
+                    highlight_style()
+                } else {
+                    default_style()
+                };
+                lines.push(Line::from(Span::styled(
+                    format!(" {:<10}", signal.name()),
+                    style,
+                )));
+            }
+            lines.push(Line::default());
+            lines.push(hint(i18n::t("signal_picker_help")));
+            (i18n::t("send_signal"), lines)
+        }
+        ProcessDialog::Confirm { target, signal } => {
+            let mut lines = vec![Line::from(Span::styled(
+                format!("{} {}?", i18n::t("confirm_signal"), signal.name()),
+                warning_style().add_modifier(Modifier::BOLD),
+            ))];
+            lines.push(Line::default());
+            lines.extend(target_lines(target));
+            lines.push(Line::default());
+            lines.push(hint(i18n::t("confirm_help")));
+            (i18n::t("send_signal"), lines)
+        }
+        ProcessDialog::Renice { target, nice } => {
+            let mut lines = target_lines(target);
+            lines.push(Line::default());
+            lines.push(
+                Line::from(vec![
+                    Span::raw("nice  ◂ "),
+                    Span::styled(format!("{:>3}", nice), highlight_style()),
+                    Span::raw(" ▸"),
+                ])
+                .alignment(Alignment::Center),
+            );
+            lines.push(Line::default());
+            lines.push(hint(i18n::t("renice_help")));
+            (i18n::t("renice"), lines)
+        }
+        ProcessDialog::Affinity {
+            target,
+            cpus,
+            selected,
+        } => {
+            let mut lines = target_lines(target);
+            lines.push(Line::default());
+            for (row, chunk) in cpus.chunks(AFFINITY_GRID_COLUMNS).enumerate() {
+                let cells = chunk.iter().enumerate().map(|(column, checked)| {
+                    let cpu = row * AFFINITY_GRID_COLUMNS + column;
+                    let style = if cpu == *selected {
+                        highlight_style()
+                    } else {
+                        default_style()
+                    };
+                    let mark = if *checked { 'x' } else { ' ' };
+                    Span::styled(format!("[{}]{:>3} ", mark, cpu), style)
+                });
+                lines.push(Line::from(cells.collect::<Vec<_>>()));
+            }
+            lines.push(Line::default());
+            lines.push(hint(i18n::t("affinity_help")));
+            (i18n::t("cpu_affinity"), lines)
+        }
+        ProcessDialog::Columns { visible, selected } => {
+            let mut lines: Vec<Line> = ProcessColumn::ALL
+                .iter()
+                .zip(visible)
+                .enumerate()
+                .map(|(i, (column, checked))| {
+                    let style = if i == *selected {
+                        highlight_style()
+                    } else {
+                        default_style()
+                    };
+                    let mark = if *checked { 'x' } else { ' ' };
+                    Line::from(Span::styled(
+                        format!(" [{}] {}", mark, column_title(*column)),
+                        style,
+                    ))
+                })
+                .collect();
+            lines.push(Line::default());
+            lines.push(hint(i18n::t("columns_help")));
+            (i18n::t("columns"), lines)
+        }
+    };
+
+    // 命令行较长时自动换行，高度按换行后的行数计算
+    let width = DIALOG_WIDTH.min(area.width);
+    let inner_width = width.saturating_sub(2).max(1) as usize;
+    let rows: usize = lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(inner_width))
+        .sum();
+    let height = (rows as u16).saturating_add(2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(BLOCK_BORDERS)
+        .style(block_style());
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .style(default_style())
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(Clear, popup);
+    f.render_widget(paragraph, popup);
+}
+
+fn target_lines(target: &ProcessTarget) -> Vec<Line<'static>> {
+    let label = Style::default().fg(Color::Cyan);
+    vec![
+        Line::from(vec![
+            Span::styled("PID      ", label),
+            Span::raw(target.pid.to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:<9}", i18n::t("name")), label),
+            Span::raw(target.name.clone()),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:<9}", i18n::t("command")), label),
+            Span::raw(target.command.clone()),
+        ]),
+    ]
+}
+
+fn hint(text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        text.to_string(),
+        Style::default().fg(Color::DarkGray),
+    ))
+    .alignment(Alignment::Center)
+}
@@ -1,3 +1,4 @@
+use chrono::{Local, TimeZone};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -9,112 +10,182 @@ use ratatui::{
 };
 
 use super::{block_style, default_style, highlight_style, warning_style, BLOCK_BORDERS};
-use crate::data::process::{ProcessData, ProcessSortField};
+use crate::app::ProcessCursor;
+use crate::data::bytes_to_mb;
+use crate::data::process::{
+    ProcessColumn, ProcessData, ProcessInfo, ProcessRow, ProcessTable, ProcessView,
+};
+use crate::data::procfs;
 use crate::i18n;
 
+/// 名称列宽度，树形和分组视图中需要额外容纳连线、折叠标记和进程数
+const NAME_WIDTH: u16 = 20;
+const TREE_NAME_WIDTH: u16 = 32;
+/// 列之间的间距
+const COLUMN_SPACING: u16 = 1;
+/// 置顶进程名称前的标记
+const PIN_MARK: &str = "★ ";
+/// 表头高度和表头下方的空行
+const HEADER_HEIGHT: u16 = 1;
+const HEADER_MARGIN: u16 = 1;
+
+/// 表格中能显示的进程行数：去掉上下边框、表头和表头下方的空行
+fn visible_rows(area: Rect) -> u16 {
+    area.height
+        .saturating_sub(2 + HEADER_HEIGHT + HEADER_MARGIN)
+}
+
+/// 列选择器和表头中的列名
+pub fn column_title(column: ProcessColumn) -> &'static str {
+    match column {
+        ProcessColumn::Pid => "PID",
+        ProcessColumn::Name => i18n::t("name"),
+        ProcessColumn::User => i18n::t("user"),
+        ProcessColumn::Cpu => i18n::t("cpu_percent"),
+        ProcessColumn::MemoryPercent => i18n::t("memory_percent"),
+        ProcessColumn::MemoryMb => i18n::t("memory_mb"),
+        ProcessColumn::VirtualMemory => i18n::t("virtual_memory_mb"),
+        ProcessColumn::Priority => "PRI",
+        ProcessColumn::Nice => "NI",
+        ProcessColumn::Affinity => "CPUs",
+        ProcessColumn::Threads => i18n::t("threads"),
+        ProcessColumn::RunTime => i18n::t("run_time"),
+        ProcessColumn::StartTime => i18n::t("start_time"),
+        ProcessColumn::DiskRead => i18n::t("disk_read_speed"),
+        ProcessColumn::DiskWrite => i18n::t("disk_write_speed"),
+        ProcessColumn::Sockets => i18n::t("sockets"),
+        ProcessColumn::Status => i18n::t("status"),
+        ProcessColumn::Command => i18n::t("command"),
+    }
+}
+
+/// 各列宽度，命令行列占用剩余宽度
+fn column_widths(columns: &[ProcessColumn], view: ProcessView, content_width: u16) -> Vec<u16> {
+    let fixed = |column: ProcessColumn| match column {
+        ProcessColumn::Pid
+        | ProcessColumn::MemoryMb
+        | ProcessColumn::Threads
+        | ProcessColumn::Sockets => 8,
+        ProcessColumn::Name if view != ProcessView::Flat => TREE_NAME_WIDTH,
+        ProcessColumn::Name => NAME_WIDTH,
+        ProcessColumn::MemoryPercent => 12,
+        ProcessColumn::Priority | ProcessColumn::Nice => 4,
+        ProcessColumn::RunTime | ProcessColumn::StartTime => 11,
+        ProcessColumn::Command => 0,
+        _ => 10,
+    };
+    let used: u16 = columns
+        .iter()
+        .map(|&column| fixed(column) + COLUMN_SPACING)
+        .sum();
+    let remaining = content_width.saturating_sub(used);
+    columns
+        .iter()
+        .map(|&column| match column {
+            ProcessColumn::Command => remaining,
+            _ => fixed(column),
+        })
+        .collect()
+}
+
+/// 鼠标位于表头的哪一列
+pub fn header_column_at(
+    area: Rect,
+    columns: &[ProcessColumn],
+    view: ProcessView,
+    x: u16,
+    y: u16,
+) -> Option<ProcessColumn> {
+    if y != area.y + 1 {
+        return None;
+    }
+    let mut left = area.x + 1;
+    for (&column, width) in
+        columns
+            .iter()
+            .zip(column_widths(columns, view, area.width.saturating_sub(2)))
+    {
+        if x >= left && x < left + width {
+            return Some(column);
+        }
+        left += width + COLUMN_SPACING;
+    }
+    None
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     f: &mut Frame,
     area: Rect,
     data: &ProcessData,
-    scroll_offset: usize,
+    table: &ProcessTable,
+    columns: &[ProcessColumn],
+    cursor: &mut ProcessCursor,
     mouse_x: u16,
     mouse_y: u16,
-) -> usize {
+) {
+    let mut title = format!(" {} ", i18n::t("processes"));
+    match table.view {
+        ProcessView::GroupByName => title.push_str(&format!("· {} ", i18n::t("group_by_name"))),
+        ProcessView::GroupByExe => title.push_str(&format!("· {} ", i18n::t("group_by_exe"))),
+        ProcessView::Flat | ProcessView::Tree => {}
+    }
+    if data.show_threads {
+        title.push_str(&format!("· {} ", i18n::t("show_threads")));
+    }
+    // 搜索框关闭后仍在标题中显示生效的过滤条件
+    if !data.filter.is_empty() {
+        title.push_str(&format!("[/{}] ", data.filter.query()));
+    }
     let block = Block::default()
-        .title(format!(" {} ", i18n::t("processes")))
+        .title(title)
         .borders(BLOCK_BORDERS)
         .style(block_style());
 
-    let col_widths = [
-        Constraint::Length(8),
-        Constraint::Length(20),
-        Constraint::Length(10),
-        Constraint::Length(12),
-        Constraint::Length(8),
-        Constraint::Length(10),
-        Constraint::Min(0),
-    ];
-
-    let content_width = area.width.saturating_sub(2);
-    let fixed_width: u16 = 8 + 20 + 10 + 12 + 8 + 10;
-    let variable_width = content_width.saturating_sub(fixed_width + 6);
-
-    let col_actual_widths = [
-        8u16,
-        20,
-        10,
-        12,
-        8,
-        10,
-        variable_width,
-    ];
-
-    let header_cells = vec![
-        format!("PID{}", data.get_sort_indicator(ProcessSortField::Pid)),
-        format!(
-            "{}{}",
-            i18n::t("name"),
-            data.get_sort_indicator(ProcessSortField::Name)
-        ),
-        format!(
-            "{}{}",
-            i18n::t("cpu_percent"),
-            data.get_sort_indicator(ProcessSortField::Cpu)
-        ),
-        format!(
-            "{}{}",
-            i18n::t("memory_percent"),
-            data.get_sort_indicator(ProcessSortField::Memory)
-        ),
-        i18n::t("memory_mb").to_string(),
-        format!(
-            "{}{}",
-            i18n::t("status"),
-            data.get_sort_indicator(ProcessSortField::Status)
-        ),
-        i18n::t("command").to_string(),
-    ];
-
-    let mut col_positions = vec![];
-    let mut current_x = area.x + 1;
-    for (_i, width) in col_actual_widths.iter().enumerate() {
-        col_positions.push(current_x);
-        current_x += width + 1;
-    }
-
-    let is_mouse_on_header = mouse_y == area.y + 1;
-
-    let header_cells_iter = header_cells.iter().enumerate().map(|(i, h)| {
-        let is_mouse_over_col = is_mouse_on_header
-            && i < col_actual_widths.len()
-            && mouse_x >= col_positions[i]
-            && mouse_x < col_positions[i] + col_actual_widths[i];
+    let has_totals = table.view != ProcessView::Flat;
+    let widths = column_widths(columns, table.view, area.width.saturating_sub(2));
+    let hovered = header_column_at(area, columns, table.view, mouse_x, mouse_y);
+
+    let header_cells = columns.iter().map(|&column| {
+        // 树形视图中 CPU 和内存为子树合计，分组视图中分组行为组内合计
+        let total_mark = match column {
+            ProcessColumn::Cpu | ProcessColumn::MemoryPercent | ProcessColumn::MemoryMb
+                if has_totals =>
+            {
+                "Σ"
+            }
+            _ => "",
+        };
+        let indicator = column
+            .sort_field()
+            .map_or("", |field| data.get_sort_indicator(field));
+        let title = format!("{}{}{}", total_mark, column_title(column), indicator);
 
-        let style = if is_mouse_over_col {
+        let style = if hovered == Some(column) && column.sort_field().is_some() {
             highlight_style().add_modifier(Modifier::BOLD)
         } else {
             highlight_style()
         };
-
-        Cell::from(Line::from(Span::styled(h.as_str(), style)))
+        Cell::from(Line::from(Span::styled(title, style)))
     });
 
-    let header = Row::new(header_cells_iter)
+    let header = Row::new(header_cells)
         .style(Style::default().fg(Color::White))
-        .height(1)
-        .bottom_margin(1);
+        .height(HEADER_HEIGHT)
+        .bottom_margin(HEADER_MARGIN);
 
-    let visible_rows = area.height.saturating_sub(3) as usize;
+    let visible_rows = visible_rows(area) as usize;
 
-    let total_processes = data.processes.len();
-    let max_scroll_offset = total_processes.saturating_sub(visible_rows);
-    let scroll_offset = scroll_offset.min(max_scroll_offset);
+    let total_processes = table.rows.len();
+    cursor.clamp(total_processes, visible_rows);
+    let scroll_offset = cursor.offset;
 
-    let visible_processes: Vec<_> = data
-        .processes
+    let visible_processes: Vec<_> = table
+        .rows
         .iter()
         .skip(scroll_offset)
         .take(visible_rows)
+        .map(|row| (row, data.row_process(row)))
         .collect();
 
     if visible_processes.is_empty() {
@@ -130,54 +201,25 @@ pub fn render(
             .style(default_style());
 
         f.render_widget(text, area);
-        return 0;
+        return;
     }
 
-    let rows = visible_processes.iter().map(|p| {
-        let cpu_style = if p.cpu_usage > 80.0 {
-            warning_style()
-        } else if p.cpu_usage > 50.0 {
-            Style::default().fg(Color::Yellow)
+    let rows = visible_processes.iter().enumerate().map(|(i, (row, p))| {
+        let is_selected = scroll_offset + i == cursor.selected;
+        let is_pinned = p.pinned && row.group.is_none();
+        let cells = columns
+            .iter()
+            .zip(&widths)
+            .map(|(&column, &width)| cell(column, width, row, p, is_selected));
+
+        let row = Row::new(cells).height(1).bottom_margin(0);
+        if is_selected {
+            row.style(highlight_style())
+        } else if is_pinned {
+            row.style(Style::default().add_modifier(Modifier::BOLD))
         } else {
-            default_style()
-        };
-
-        let mem_style = if p.memory_percent > 80.0 {
-            warning_style()
-        } else if p.memory_percent > 50.0 {
-            Style::default().fg(Color::Yellow)
-        } else {
-            default_style()
-        };
-
-        let status_style = match p.status.as_str() {
-            "Sleep" => Style::default().fg(Color::Cyan),
-            "Run" => Style::default().fg(Color::Green),
-            "Zombie" => Style::default().fg(Color::Red),
-            "Stopped" => Style::default().fg(Color::Red),
-            _ => default_style(),
-        };
-
-        let cells = [
-            Cell::from(format!("{}", p.pid)),
-            Cell::from(truncate_string(&p.name, 18)),
-            Cell::from(Line::from(Span::styled(
-                format!("{:.1}%", p.cpu_usage),
-                cpu_style,
-            ))),
-            Cell::from(Line::from(Span::styled(
-                format!("{:.1}%", p.memory_percent),
-                mem_style,
-            ))),
-            Cell::from(format!("{:.1}", p.memory_mb)),
-            Cell::from(Line::from(Span::styled(
-                format!("{}", translate_status(&p.status)),
-                status_style,
-            ))),
-            Cell::from(truncate_string(&p.command, 50)),
-        ];
-
-        Row::new(cells).height(1).bottom_margin(0)
+            row
+        }
     });
 
     let content_chunks = Layout::default()
@@ -185,10 +227,18 @@ pub fn render(
         .constraints([Constraint::Min(0), Constraint::Length(1)])
         .split(area);
 
-    let table = Table::new(rows, col_widths)
+    let constraints: Vec<Constraint> = columns
+        .iter()
+        .zip(&widths)
+        .map(|(&column, &width)| match column {
+            ProcessColumn::Command => Constraint::Min(0),
+            _ => Constraint::Length(width),
+        })
+        .collect();
+    let table = Table::new(rows, constraints)
         .header(header)
         .block(block)
-        .column_spacing(1);
+        .column_spacing(COLUMN_SPACING);
 
     f.render_widget(table, content_chunks[0]);
 
@@ -209,8 +259,149 @@ pub fn render(
             &mut scrollbar_state,
         );
     }
+}
+
+/// 生成一个单元格；选中行整行高亮，不保留各列的警告颜色
+fn cell(
+    column: ProcessColumn,
+    width: u16,
+    row: &ProcessRow,
+    p: &ProcessInfo,
+    is_selected: bool,
+) -> Cell<'static> {
+    let styled = |text: String, style: Style| {
+        let style = if is_selected {
+            highlight_style()
+        } else {
+            style
+        };
+        Cell::from(Line::from(Span::styled(text, style)))
+    };
+    let usage_style = |percent: f32| {
+        if percent > 80.0 {
+            warning_style()
+        } else if percent > 50.0 {
+            Style::default().fg(Color::Yellow)
+        } else {
+            default_style()
+        }
+    };
+
+    // 分组行只显示进程数和合计，逐个进程的字段留空
+    if let Some(key) = &row.group {
+        match column {
+            ProcessColumn::Name
+            | ProcessColumn::Cpu
+            | ProcessColumn::MemoryPercent
+            | ProcessColumn::MemoryMb => {}
+            // 按名称分组时分组键就是名称，不再重复显示
+            ProcessColumn::Command if *key != p.name => {
+                return Cell::from(truncate_string(key, 50))
+            }
+            _ => return Cell::from(""),
+        }
+    }
+
+    // 线程行只显示线程自己的字段，内存、磁盘等属于整个进程
+    if row.thread
+        && !matches!(
+            column,
+            ProcessColumn::Pid
+                | ProcessColumn::Name
+                | ProcessColumn::User
+                | ProcessColumn::Cpu
+                | ProcessColumn::Priority
+                | ProcessColumn::Nice
+                | ProcessColumn::Status
+        )
+    {
+        return Cell::from("");
+    }
 
-    scroll_offset
+    match column {
+        ProcessColumn::Pid => Cell::from(p.pid.to_string()),
+        ProcessColumn::Name => {
+            let name = if row.group.is_some() {
+                format!("{} ({})", p.name, row.count)
+            } else if p.pinned {
+                format!("{PIN_MARK}{}", p.name)
+            } else {
+                p.name.clone()
+            };
+            Cell::from(truncate_string(&tree_label(row, &name), width as usize - 2))
+        }
+        ProcessColumn::User => Cell::from(truncate_string(&p.user, width as usize)),
+        ProcessColumn::Cpu => styled(format!("{:.1}%", row.cpu_usage), usage_style(row.cpu_usage)),
+        ProcessColumn::MemoryPercent => styled(
+            format!("{:.1}%", row.memory_percent),
+            usage_style(row.memory_percent),
+        ),
+        ProcessColumn::MemoryMb => Cell::from(format!("{:.1}", row.memory_mb)),
+        ProcessColumn::VirtualMemory => Cell::from(format!("{:.1}", p.virtual_memory_mb)),
+        ProcessColumn::Priority => Cell::from(p.priority.to_string()),
+        ProcessColumn::Nice => Cell::from(p.nice.to_string()),
+        ProcessColumn::Affinity => Cell::from(if p.affinity.is_empty() {
+            "-".to_string()
+        } else {
+            truncate_string(&procfs::format_cpu_list(&p.affinity), width as usize)
+        }),
+        ProcessColumn::Threads => Cell::from(p.threads.to_string()),
+        ProcessColumn::Sockets => Cell::from(p.sockets.to_string()),
+        ProcessColumn::RunTime => Cell::from(format_run_time(p.run_time)),
+        ProcessColumn::StartTime => Cell::from(format_start_time(p.start_time)),
+        ProcessColumn::DiskRead => Cell::from(format!("{:.1}MB/s", bytes_to_mb(p.disk_read_speed))),
+        ProcessColumn::DiskWrite => {
+            Cell::from(format!("{:.1}MB/s", bytes_to_mb(p.disk_write_speed)))
+        }
+        ProcessColumn::Status => {
+            let style = match p.status.as_str() {
+                "Sleep" => Style::default().fg(Color::Cyan),
+                "Run" => Style::default().fg(Color::Green),
+                "Zombie" => Style::default().fg(Color::Red),
+                "Stopped" => Style::default().fg(Color::Red),
+                _ => default_style(),
+            };
+            styled(translate_status(&p.status), style)
+        }
+        ProcessColumn::Command => Cell::from(truncate_string(&p.display_command(), 50)),
+    }
+}
+
+/// 运行时长，超过一天时显示天数
+fn format_run_time(seconds: u64) -> String {
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+    if days > 0 {
+        format!("{}d {:02}:{:02}", days, hours, minutes)
+    } else {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    }
+}
+
+/// 启动时间，当天启动的只显示时刻
+fn format_start_time(timestamp: u64) -> String {
+    let Some(time) = Local.timestamp_opt(timestamp as i64, 0).single() else {
+        return "-".to_string();
+    };
+    if time.date_naive() == Local::now().date_naive() {
+        time.format("%H:%M:%S").to_string()
+    } else {
+        time.format("%m-%d %H:%M").to_string()
+    }
+}
+
+/// 树形和分组视图中名称前加上连线和折叠标记
+fn tree_label(row: &ProcessRow, name: &str) -> String {
+    let marker = match (row.has_children, row.collapsed) {
+        (true, true) => "▸ ",
+        (true, false) => "▾ ",
+        (false, _) => "",
+    };
+    format!("{}{}{}", row.prefix, marker, name)
 }
 
 fn translate_status(status: &str) -> String {
@@ -228,22 +419,22 @@ pub fn handle_scrollbar_click(
     area: Rect,
     column: u16,
     row: u16,
-    data: &ProcessData,
+    total_rows: usize,
     current_offset: usize,
 ) -> Option<usize> {
-    handle_scrollbar_drag(area, column, row, data, current_offset)
+    handle_scrollbar_drag(area, column, row, total_rows, current_offset)
 }
 
 pub fn handle_scrollbar_drag(
     area: Rect,
     _column: u16,
     row: u16,
-    data: &ProcessData,
+    total_rows: usize,
     _current_offset: usize,
 ) -> Option<usize> {
-    let total_processes = data.processes.len();
+    let total_processes = total_rows;
 
-    let content_height = area.height.saturating_sub(3);
+    let content_height = visible_rows(area);
     let total_items = total_processes;
 
     if total_items == 0 || content_height == 0 {
@@ -269,7 +460,7 @@ pub fn handle_scrollbar_drag(
     Some(new_offset)
 }
 
-fn truncate_string(s: &str, max_len: usize) -> String {
+pub(super) fn truncate_string(s: &str, max_len: usize) -> String {
     if max_len == 0 {
         return String::new();
     }
@@ -296,4 +487,4 @@ fn truncate_string(s: &str, max_len: usize) -> String {
         result.push('…');
     }
     result
-}
\ No newline at end of file
+}
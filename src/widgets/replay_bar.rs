@@ -0,0 +1,50 @@
+use super::block_style;
+use crate::i18n;
+use crate::recording::ReplayStatus;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::time::Duration;
+
+/// 渲染回放进度、速度和快捷键提示
+pub fn render(f: &mut Frame, area: Rect, status: &ReplayStatus) {
+    let state = if status.paused {
+        format!("⏸ {} ({})", i18n::t("replay"), i18n::t("replay_paused"))
+    } else {
+        format!("▶ {}", i18n::t("replay"))
+    };
+
+    let spans = vec![
+        Span::styled(
+            state,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            " {} {}/{} [{}/{}] {}x │ ",
+            status.timestamp,
+            format_duration(status.elapsed),
+            format_duration(status.duration),
+            status.position + 1,
+            status.len,
+            status.speed
+        )),
+        Span::styled(i18n::t("replay_help"), block_style()),
+    ];
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
@@ -247,4 +247,4 @@ pub fn handle_log_tab_click(column: u16, row: u16, content_area: Rect) -> Option
         4 => Some(LogCategory::All),
         _ => None,
     }
-}
\ No newline at end of file
+}
@@ -142,4 +142,4 @@ pub fn render(f: &mut Frame, area: Rect, temp_data: &TemperatureData, battery_da
     );
 
     f.render_widget(temp_table, chunks[2]);
-}
\ No newline at end of file
+}
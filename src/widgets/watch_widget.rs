@@ -0,0 +1,107 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Sparkline},
+    Frame,
+};
+
+use super::process_widget::truncate_string;
+use super::{block_style, default_style, warning_style, BLOCK_BORDERS};
+use crate::data::history::HistoryData;
+use crate::data::process_watch::WatchedProcess;
+use crate::i18n;
+
+/// 面板最多显示的监视进程数，超出的部分不显示
+const MAX_VISIBLE_WATCHES: usize = 6;
+/// 每行左侧名称和当前取值的宽度
+const LABEL_WIDTH: u16 = 44;
+
+/// 监视面板的高度，没有监视的进程时为 0
+pub fn height(watches: &[WatchedProcess]) -> u16 {
+    if watches.is_empty() {
+        0
+    } else {
+        watches.len().min(MAX_VISIBLE_WATCHES) as u16 + 2
+    }
+}
+
+/// 每个监视的进程一行：名称、PID 或退出状态，以及 CPU 和内存曲线
+pub fn render(f: &mut Frame, area: Rect, watches: &[WatchedProcess]) {
+    let block = Block::default()
+        .title(format!(" {} ({}) ", i18n::t("watched"), watches.len()))
+        .borders(BLOCK_BORDERS)
+        .style(block_style());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+            Constraint::Length(1);
+            watches.len().min(MAX_VISIBLE_WATCHES)
+        ])
+        .split(inner);
+    for (watch, &row) in watches.iter().zip(rows.iter()) {
+        render_row(f, row, watch);
+    }
+}
+
+fn render_row(f: &mut Frame, area: Rect, watch: &WatchedProcess) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(LABEL_WIDTH),
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ])
+        .split(area);
+
+    let cpu = latest(&watch.history.cpu_usage);
+    let memory = latest(&watch.history.memory_mb);
+    let status = match (watch.pid, watch.last_pid()) {
+        (Some(pid), _) => Span::raw(format!(
+            "PID {pid:<7} CPU {cpu:>5.1}% {} {memory:>7.1}MB",
+            i18n::t("memory")
+        )),
+        (None, Some(pid)) => Span::styled(
+            format!("{} (PID {pid})", i18n::t("watch_exited")),
+            warning_style(),
+        ),
+        (None, None) => Span::styled(
+            i18n::t("watch_waiting").to_string(),
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    let label = Line::from(vec![
+        Span::raw(format!("{:<14} ", truncate_string(&watch.name, 14))),
+        status,
+    ]);
+    f.render_widget(Paragraph::new(label).style(default_style()), columns[0]);
+
+    let cpu_data = tail(&watch.history.cpu_usage, columns[1].width);
+    // 多线程进程的 CPU 占用可以超过 100%
+    let cpu_max = cpu_data.iter().copied().max().unwrap_or(0).max(100);
+    let cpu_sparkline = Sparkline::default()
+        .data(&cpu_data)
+        .style(default_style().fg(Color::Green))
+        .max(cpu_max);
+    f.render_widget(cpu_sparkline, columns[1]);
+
+    let memory_data = tail(&watch.history.memory_mb, columns[2].width);
+    let memory_sparkline = Sparkline::default()
+        .data(&memory_data)
+        .style(default_style().fg(Color::Yellow));
+    f.render_widget(memory_sparkline, columns[2]);
+}
+
+fn latest(history: &HistoryData<f32>) -> f32 {
+    history.iter().last().map_or(0.0, |(_, value)| *value)
+}
+
+/// 最近的 `width` 个数据点，使曲线的右端总是最新的取值
+fn tail(history: &HistoryData<f32>, width: u16) -> Vec<u64> {
+    let values = history.get_all();
+    let start = values.len().saturating_sub(width as usize);
+    values[start..].iter().map(|&v| v as u64).collect()
+}